@@ -0,0 +1,517 @@
+use crate::parallel::{map_ordered, split_threads};
+use crate::runner::CRATE_VERSION;
+use crate::tests::time_seed_test::TimeSeedSearch;
+use crate::tests::block_test::{test_blocs_stream, BlockAccumulator};
+use crate::tests::correlation_test::{test_correlation_stream, CorrelationAccumulator};
+use crate::tests::entropy_test::{test_entropy_stream, EntropyAccumulator};
+use crate::tests::frequency_test::{test_frequence_stream, FrequencyAccumulator};
+use crate::tests::periodicity_test::{test_periodicity_stream, PeriodicityAccumulator};
+use crate::tests::poker_test::{test_poker_stream, PokerAccumulator};
+use crate::tests::sequence_length_test::{test_sequence_length_stream, SequenceLengthAccumulator};
+use crate::tests::{
+    analyze_seed_patterns, note_dropped_bits, test_autocorrelation_stream, test_collisions, test_compression_stream,
+    test_cycle_detection, test_ent_stream, test_imprevisibility, test_periodicity_advanced, test_predictability,
+    test_state_recovery, test_time_seed_with_search, BitStream, LagRange, StreamAccumulator,
+};
+use crate::types::TestResult;
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+// Nombre de valeurs tirées par défaut d'un générateur
+const DEFAULT_NUM_VALUES: usize = 1000;
+// Taille des blocs par défaut du test de blocs
+const DEFAULT_BLOCK_SIZE: usize = 8;
+
+/// Tests de la batterie, dans l'ordre d'exécution.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatteryTest {
+    Frequency,
+    Entropy,
+    Poker,
+    Periodicity,
+    Correlation,
+    SequenceLength,
+    Blocks,
+    Imprevisibility,
+    PeriodicityAdvanced,
+    Collisions,
+    Compression,
+    Autocorrelation,
+    CycleDetection,
+    Predictability,
+    StateRecovery,
+    TimeSeed,
+    Patterns,
+    /// Résumé ENT, qui produit un groupe de résultats.
+    Ent,
+}
+
+/// Lecture d'une entrée analysée en flux (voir `crate::streaming`).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamSupport {
+    /// Statistique accumulée morceau par morceau sur tout le flux, en mémoire bornée.
+    Streaming,
+    /// Test exécuté sur une fenêtre bornée : les premières valeurs du flux.
+    Window,
+}
+
+impl BatteryTest {
+    /// Tous les tests, dans l'ordre de la batterie du serveur.
+    pub const ALL: [BatteryTest; 18] = [
+        BatteryTest::Frequency,
+        BatteryTest::Entropy,
+        BatteryTest::Poker,
+        BatteryTest::Periodicity,
+        BatteryTest::Correlation,
+        BatteryTest::SequenceLength,
+        BatteryTest::Blocks,
+        BatteryTest::Imprevisibility,
+        BatteryTest::PeriodicityAdvanced,
+        BatteryTest::Collisions,
+        BatteryTest::Compression,
+        BatteryTest::Autocorrelation,
+        BatteryTest::CycleDetection,
+        BatteryTest::Predictability,
+        BatteryTest::StateRecovery,
+        BatteryTest::TimeSeed,
+        BatteryTest::Patterns,
+        BatteryTest::Ent,
+    ];
+
+    /// Identifiant du test (`frequency`, `time_seed`, ...).
+    pub fn id(&self) -> String {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    /// Nom affiché du test (celui de ses résultats, `test_name`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            BatteryTest::Frequency => "Test de fréquence des bits",
+            BatteryTest::Entropy => "Test d'entropie de Shannon",
+            BatteryTest::Poker => "Test de Poker",
+            BatteryTest::Periodicity => "Test de périodicité des bits",
+            BatteryTest::Correlation => "Test de corrélation des bits",
+            BatteryTest::SequenceLength => "Test de longueur de séquence",
+            BatteryTest::Blocks => "Test de blocs",
+            BatteryTest::Imprevisibility => "Test d'Imprévisibilité",
+            BatteryTest::PeriodicityAdvanced => "Test de Périodicité Avancé",
+            BatteryTest::Collisions => "Test de Collision",
+            BatteryTest::Compression => "Test de compression",
+            BatteryTest::Autocorrelation => "Test d'autocorrélation",
+            BatteryTest::CycleDetection => "Test de détection de cycle",
+            BatteryTest::Predictability => "Test de prévisibilité (LCG)",
+            BatteryTest::StateRecovery => "Test de récupération d'état (MT / xorshift)",
+            BatteryTest::TimeSeed => "Test de graine temporelle",
+            BatteryTest::Patterns => "Analyse des motifs",
+            BatteryTest::Ent => "ENT",
+        }
+    }
+
+    /// Vrai si `result` provient de ce test : même nom, ou nom suivi de « : » pour un groupe (ENT).
+    pub fn produced(&self, result: &TestResult) -> bool {
+        let name = self.name();
+        result.test_name == name || result.test_name.strip_prefix(name).is_some_and(|rest| rest.starts_with(" :"))
+    }
+
+    /// Exécute le test sur `data` avec les paramètres de `config`.
+    pub fn run(&self, data: &[u64], config: &BatteryConfig) -> Vec<TestResult> {
+        self.run_stream(&BitStream::from_u64s(data), config)
+    }
+
+    /// Vrai si le test lit le flux bit à bit ou échantillon par échantillon plutôt que par mots de 64 bits.
+    pub fn reads_stream(&self) -> bool {
+        matches!(
+            self,
+            BatteryTest::Frequency
+                | BatteryTest::Entropy
+                | BatteryTest::Poker
+                | BatteryTest::Periodicity
+                | BatteryTest::Correlation
+                | BatteryTest::SequenceLength
+                | BatteryTest::Blocks
+        )
+    }
+
+    /// Lecture du test lors d'une analyse en flux : les tests sur les bits et les échantillons accumulent
+    /// leur statistique, les tests sur les mots (recherches d'état, de cycle, compression...) ont besoin
+    /// de toutes leurs valeurs en mémoire et ne lisent qu'une fenêtre.
+    pub fn stream_support(&self) -> StreamSupport {
+        if self.reads_stream() {
+            StreamSupport::Streaming
+        } else {
+            StreamSupport::Window
+        }
+    }
+
+    /// Accumulateur du test pour une analyse en flux, ou `None` pour un test sur fenêtre.
+    pub fn accumulator(&self, config: &BatteryConfig) -> Option<Box<dyn StreamAccumulator>> {
+        let accumulator: Box<dyn StreamAccumulator> = match self {
+            BatteryTest::Frequency => Box::new(FrequencyAccumulator::default()),
+            BatteryTest::Entropy => Box::new(EntropyAccumulator::default()),
+            BatteryTest::Poker => Box::new(PokerAccumulator::default()),
+            BatteryTest::Periodicity => Box::new(PeriodicityAccumulator::default()),
+            BatteryTest::Correlation => Box::new(CorrelationAccumulator::default()),
+            BatteryTest::SequenceLength => Box::new(SequenceLengthAccumulator::default()),
+            BatteryTest::Blocks => Box::new(BlockAccumulator::new(config.block_size)),
+            _ => return None,
+        };
+        Some(accumulator)
+    }
+
+    /// Exécute le test sur un flux avec les paramètres de `config`.
+    ///
+    /// # Remarques
+    /// * L'autocorrélation lit les bits dans l'ordre du flux et les échantillons à leur largeur réelle ; ENT
+    ///   et la compression lisent `BitStream::bytes`.
+    /// * Les autres tests travaillent sur des mots de 64 bits (`BitStream::words64`) ; les bits d'un mot
+    ///   incomplet en fin de flux sont ignorés et signalés dans les détails.
+    pub fn run_stream(&self, stream: &BitStream, config: &BatteryConfig) -> Vec<TestResult> {
+        if self.reads_stream() {
+            let result = match self {
+                BatteryTest::Frequency => test_frequence_stream(stream),
+                BatteryTest::Entropy => test_entropy_stream(stream),
+                BatteryTest::Poker => test_poker_stream(stream),
+                BatteryTest::Periodicity => test_periodicity_stream(stream),
+                BatteryTest::Correlation => test_correlation_stream(stream),
+                BatteryTest::SequenceLength => test_sequence_length_stream(stream),
+                _ => test_blocs_stream(stream, config.block_size),
+            };
+            return vec![result];
+        }
+        match self {
+            BatteryTest::Autocorrelation => return vec![test_autocorrelation_stream(stream, config.lags.range())],
+            BatteryTest::Compression => return vec![test_compression_stream(stream)],
+            BatteryTest::Ent => return test_ent_stream(stream),
+            _ => {}
+        }
+
+        let words = stream.words64();
+        let data = words.as_slice();
+        let mut result = match self {
+            BatteryTest::Imprevisibility => test_imprevisibility(data),
+            BatteryTest::PeriodicityAdvanced => test_periodicity_advanced(data),
+            BatteryTest::Collisions => test_collisions(data),
+            BatteryTest::CycleDetection => test_cycle_detection(data),
+            BatteryTest::Predictability => test_predictability(data),
+            BatteryTest::StateRecovery => test_state_recovery(data),
+            BatteryTest::TimeSeed => {
+                let search = config.time_seed.clone().unwrap_or_default();
+                test_time_seed_with_search(data, &search)
+            }
+            BatteryTest::Patterns => analyze_seed_patterns(data),
+            _ => unreachable!("test sur flux ou sur octets traité plus haut"),
+        };
+        note_dropped_bits(&mut result, stream.len_bits() - words.len() * 64, "mot de 64 bits");
+        vec![result]
+    }
+}
+
+impl FromStr for BatteryTest {
+    type Err = String;
+
+    /// Lit l'identifiant d'un test (voir `id`).
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_string()))
+            .map_err(|_| format!("Test inconnu : {}", value))
+    }
+}
+
+/// Configuration de la batterie pour l'API bibliothèque.
+///
+/// # Remarques
+/// * `Default` exécute tous les tests sur 1000 valeurs, avec la recherche de graine temporelle par défaut
+///   (dernières 24 heures) ; fixer `time_seed` rend les résultats indépendants de l'heure d'exécution.
+/// * Les tests sont exécutés en parallèle sur `threads` threads ; le rapport est identique quel que soit
+///   leur nombre.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BatteryConfig {
+    /// Nombre de valeurs de 64 bits tirées d'un générateur (`test_rng`).
+    pub num_values: usize,
+    /// Tests à exécuter, dans l'ordre.
+    pub tests: Vec<BatteryTest>,
+    /// Taille des blocs du test de blocs.
+    pub block_size: usize,
+    /// Fenêtre de la recherche de graine temporelle ; `None` pour la fenêtre par défaut.
+    pub time_seed: Option<TimeSeedSearch>,
+    /// Décalages du test d'autocorrélation.
+    #[serde(default)]
+    pub lags: LagRange,
+    /// Nombre de threads d'exécution des tests ; 0 pour le parallélisme disponible.
+    #[serde(default)]
+    pub threads: usize,
+}
+
+impl Default for BatteryConfig {
+    fn default() -> Self {
+        BatteryConfig {
+            num_values: DEFAULT_NUM_VALUES,
+            tests: BatteryTest::ALL.to_vec(),
+            block_size: DEFAULT_BLOCK_SIZE,
+            time_seed: None,
+            lags: LagRange::default(),
+            threads: 0,
+        }
+    }
+}
+
+impl BatteryConfig {
+    /// Fixe le nombre de valeurs tirées d'un générateur.
+    pub fn num_values(mut self, num_values: usize) -> Self {
+        self.num_values = num_values;
+        self
+    }
+
+    /// Restreint la batterie aux tests donnés.
+    pub fn tests(mut self, tests: &[BatteryTest]) -> Self {
+        self.tests = tests.to_vec();
+        self
+    }
+
+    /// Retire un test de la batterie.
+    pub fn without(mut self, test: BatteryTest) -> Self {
+        self.tests.retain(|&current| current != test);
+        self
+    }
+
+    /// Fixe la fenêtre de recherche de graine temporelle.
+    pub fn time_seed(mut self, search: TimeSeedSearch) -> Self {
+        self.time_seed = Some(search);
+        self
+    }
+
+    /// Fixe les décalages du test d'autocorrélation.
+    pub fn lags(mut self, lags: LagRange) -> Self {
+        self.lags = lags;
+        self
+    }
+
+    /// Fixe le nombre de threads d'exécution (0 : parallélisme disponible, 1 : exécution séquentielle).
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+}
+
+/// Rapport structuré d'une exécution de la batterie.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BatteryReport {
+    /// Version du crate ayant produit le rapport.
+    pub version: String,
+    /// Nombre de valeurs de 64 bits analysées par les tests sur les mots.
+    pub num_values: usize,
+    /// Nombre de bits du flux analysé.
+    pub num_bits: usize,
+    /// Largeur des échantillons du flux en bits.
+    pub sample_bits: u32,
+    /// Nombre de résultats réussis.
+    pub passed: usize,
+    /// Nombre de résultats échoués.
+    pub failed: usize,
+    /// Résultats dans l'ordre de la batterie.
+    pub results: Vec<TestResult>,
+}
+
+impl BatteryReport {
+    /// Vrai si tous les résultats sont réussis.
+    pub fn all_passed(&self) -> bool {
+        self.failed == 0
+    }
+
+    /// Résultats échoués.
+    pub fn failures(&self) -> impl Iterator<Item = &TestResult> {
+        self.results.iter().filter(|result| !result.passed)
+    }
+
+    /// Résultat d'un test d'après son nom (`test_name`).
+    pub fn result(&self, test_name: &str) -> Option<&TestResult> {
+        self.results.iter().find(|result| result.test_name == test_name)
+    }
+}
+
+/// Exécute la batterie sur des valeurs de 64 bits.
+/// `data` : Valeurs à analyser (toutes sont utilisées, `num_values` est ignoré).
+/// `config` : Tests et paramètres de la batterie.
+///
+/// Retourne : Le rapport structuré.
+pub fn run_tests(data: &[u64], config: &BatteryConfig) -> BatteryReport {
+    test_stream(&BitStream::from_u64s(data), config)
+}
+
+/// Exécute la batterie sur un flux de bits (voir `BatteryTest::run_stream`).
+/// `stream` : Flux à analyser, de longueur et de largeur d'échantillon quelconques.
+/// `config` : Tests et paramètres de la batterie.
+///
+/// Retourne : Le rapport structuré.
+pub fn test_stream(stream: &BitStream, config: &BatteryConfig) -> BatteryReport {
+    let results: Vec<TestResult> =
+        map_ordered(&config.tests, config.threads, |test| test.run_stream(stream, config)).concat();
+    let passed = results.iter().filter(|result| result.passed).count();
+    BatteryReport {
+        version: CRATE_VERSION.to_string(),
+        num_values: stream.len_bits() / 64,
+        num_bits: stream.len_bits(),
+        sample_bits: stream.sample_bits(),
+        passed,
+        failed: results.len() - passed,
+        results,
+    }
+}
+
+/// Exécute la batterie sur plusieurs flux indépendants (par exemple les groupes d'un fichier CSV).
+/// `sequences` : Flux à analyser, chacun avec sa longueur et sa largeur d'échantillon.
+/// `config` : Tests et paramètres de la batterie ; `threads` est partagé entre les suites et leurs tests.
+///
+/// Retourne : Un rapport par suite, dans l'ordre de `sequences`.
+pub fn run_sequences(sequences: &[BitStream], config: &BatteryConfig) -> Vec<BatteryReport> {
+    let (outer, inner) = split_threads(config.threads, sequences.len());
+    let inner = config.clone().threads(inner);
+    map_ordered(sequences, outer, |sequence| test_stream(sequence, &inner))
+}
+
+/// Tire `config.num_values` valeurs de 64 bits de `rng` (`next_u64`) et exécute la batterie.
+pub fn test_rng<R: RngCore + ?Sized>(rng: &mut R, config: &BatteryConfig) -> BatteryReport {
+    let data: Vec<u64> = (0..config.num_values).map(|_| rng.next_u64()).collect();
+    run_tests(&data, config)
+}
+
+/// Exécute la batterie sur un itérateur de valeurs de 64 bits.
+pub fn test_u64s<I: IntoIterator<Item = u64>>(values: I, config: &BatteryConfig) -> BatteryReport {
+    let data: Vec<u64> = values.into_iter().collect();
+    run_tests(&data, config)
+}
+
+/// Exécute la batterie sur un itérateur de sorties de 32 bits.
+///
+/// # Remarques
+/// * Les tests sur les bits et les échantillons lisent les sorties dans l'ordre, à 32 bits ; les tests sur
+///   les mots les regroupent par deux, poids faible d'abord (comme `next_u64` des générateurs 32 bits).
+pub fn test_u32s<I: IntoIterator<Item = u32>>(values: I, config: &BatteryConfig) -> BatteryReport {
+    let words: Vec<u32> = values.into_iter().collect();
+    test_stream(&BitStream::from_u32s(&words), config)
+}
+
+/// Exécute la batterie sur un flux d'octets.
+///
+/// # Remarques
+/// * Les tests sur les mots regroupent les octets par huit en valeurs petit-boutistes (comme `fill_bytes`).
+pub fn test_bytes<I: IntoIterator<Item = u8>>(bytes: I, config: &BatteryConfig) -> BatteryReport {
+    let bytes: Vec<u8> = bytes.into_iter().collect();
+    test_stream(&BitStream::from_bytes(&bytes), config)
+}
+
+/// Exécute la batterie sur une suite de bits de longueur quelconque (par exemple un fichier NIST).
+///
+/// # Remarques
+/// * Les tests sur les mots lisent les bits par 64, premier bit en poids fort ; les derniers bits d'un mot
+///   incomplet ne sont vus que par les tests sur les bits.
+pub fn test_bits<I: IntoIterator<Item = bool>>(bits: I, config: &BatteryConfig) -> BatteryReport {
+    test_stream(&BitStream::from_bits(bits), config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::{CounterRng, Mt19937};
+    use crate::tests::time_seed_test::TimeSeedSearch;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    // Fenêtre de recherche temporelle courte et fixe, pour des tests rapides et déterministes
+    fn quick_config() -> BatteryConfig {
+        BatteryConfig::default().time_seed(TimeSeedSearch::ending_at(1_700_000_000, 60))
+    }
+
+    #[test]
+    fn test_any_rng_and_input_shape() {
+        let config = quick_config().num_values(500);
+        let report = test_rng(&mut ChaCha20Rng::seed_from_u64(7), &config);
+        assert_eq!(report.num_values, 500);
+        assert_eq!(report.passed + report.failed, report.results.len());
+        assert!(report.result("Test de récupération d'état (MT / xorshift)").unwrap().passed);
+        // Chaque résultat se rattache à un seul test, et chaque test produit au moins un résultat
+        for result in &report.results {
+            let tests = BatteryTest::ALL.iter().filter(|test| test.produced(result)).count();
+            assert_eq!(tests, 1, "{}", result.test_name);
+        }
+        for test in BatteryTest::ALL {
+            assert!(report.results.iter().any(|result| test.produced(result)), "{}", test.id());
+        }
+
+        // Les tests sur les mots et sur les octets voient les mêmes valeurs que les données soient des u64,
+        // des u32 ou des octets (l'autocorrélation, elle, lit les échantillons à leur largeur réelle)
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+        let values: Vec<u64> = (0..500).map(|_| rng.next_u64()).collect();
+        let words: Vec<u32> = values.iter().flat_map(|&value| [value as u32, (value >> 32) as u32]).collect();
+        let bytes: Vec<u8> = values.iter().flat_map(|value| value.to_le_bytes()).collect();
+        assert_eq!(
+            serde_json::to_string(&test_u64s(values.clone(), &config).results).unwrap(),
+            serde_json::to_string(&report.results).unwrap()
+        );
+        let word_tests: Vec<BatteryTest> = BatteryTest::ALL
+            .into_iter()
+            .filter(|test| !test.reads_stream() && *test != BatteryTest::Autocorrelation)
+            .collect();
+        let word_config = config.clone().tests(&word_tests);
+        let expected = serde_json::to_string(&test_u64s(values, &word_config).results).unwrap();
+        assert_eq!(serde_json::to_string(&test_u32s(words.clone(), &word_config).results).unwrap(), expected);
+        assert_eq!(serde_json::to_string(&test_bytes(bytes, &word_config).results).unwrap(), expected);
+
+        // Les tests sur les échantillons comparent des sorties de 32 bits
+        let report = test_u32s(words, &config);
+        assert_eq!((report.num_values, report.num_bits, report.sample_bits), (500, 32_000, 32));
+        assert!(report.result("Test de corrélation des bits").unwrap().passed);
+
+        // Une suite de bits non multiple de 64 est analysée entièrement par les tests sur les bits
+        let report = test_bits((0..1000).map(|i| i % 2 == 0), &config.clone().tests(&[BatteryTest::Frequency]));
+        assert_eq!((report.num_values, report.num_bits), (15, 1000));
+        assert_eq!(report.results[0].score, 0.5);
+
+        // Les bits qui ne forment pas un mot ou un octet complet sont signalés par les autres tests
+        let tests = [BatteryTest::Collisions, BatteryTest::Compression, BatteryTest::Autocorrelation];
+        let report = test_bits((0..1003).map(|i| i % 3 == 0), &config.tests(&tests));
+        assert!(report.results[0].details.ends_with("(43 bit(s) final(s) ignoré(s) : mot de 64 bits incomplet)"));
+        assert!(report.results[1].details.ends_with("(3 bit(s) final(s) ignoré(s) : octet incomplet)"));
+        assert!(!report.results[2].details.contains("ignoré"), "{}", report.results[2].details);
+    }
+
+    #[test]
+    fn test_parallel_reports_are_deterministic() {
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        let sequences: Vec<Vec<u64>> = (0..3).map(|_| (0..400).map(|_| rng.next_u64()).collect()).collect();
+        let sequential = quick_config().threads(1);
+        let expected: Vec<String> = sequences
+            .iter()
+            .map(|sequence| serde_json::to_string(&run_tests(sequence, &sequential)).unwrap())
+            .collect();
+
+        let streams: Vec<BitStream> = sequences.iter().map(|sequence| BitStream::from_u64s(sequence)).collect();
+        for threads in [0, 2, 8] {
+            let config = quick_config().threads(threads);
+            assert_eq!(serde_json::to_string(&run_tests(&sequences[0], &config)).unwrap(), expected[0]);
+            let reports = run_sequences(&streams, &config);
+            let reports: Vec<String> = reports.iter().map(|report| serde_json::to_string(report).unwrap()).collect();
+            assert_eq!(reports, expected);
+        }
+    }
+
+    #[test]
+    fn test_selected_tests_reject_bad_generators() {
+        let config = quick_config().tests(&[BatteryTest::StateRecovery, BatteryTest::Compression]);
+        assert_eq!("state_recovery".parse::<BatteryTest>(), Ok(BatteryTest::StateRecovery));
+
+        let report = test_rng(&mut Mt19937::new(5489), &config.clone().num_values(1000));
+        assert_eq!(report.results.len(), 2);
+        assert!(!report.all_passed());
+        assert_eq!(report.failures().count(), 1);
+
+        let report = test_rng(&mut CounterRng::new(0), &config.without(BatteryTest::StateRecovery));
+        assert_eq!(report.results.len(), 1);
+        assert!(!report.all_passed());
+    }
+}
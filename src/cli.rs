@@ -0,0 +1,802 @@
+//! Ligne de commande : sous-commandes `run` (batterie sur un fichier, l'entrée standard ou un générateur),
+//! `list` (tests et générateurs disponibles), `schema` (schéma JSON des rapports), `compare` (comparaison de
+//! deux rapports), `history` (historique des exécutions enregistrées) et `serve` (serveur web).
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::json;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use seed_tester::compare::{compare_reports, format_comparison, ReportComparison};
+use seed_tester::battery::{run_sequences, run_tests, BatteryConfig, BatteryTest};
+use seed_tester::generators::ReferenceGenerator;
+use seed_tester::report::{
+    format_table, FingerprintReader, InputDescription, InputFingerprint, InputKind, Report, RunMetadata, RunParameters, SequenceReport,
+};
+use seed_tester::seed_generator::generate_seeds_with;
+use seed_tester::store::{format_history, RetentionPolicy, RunStore, DEFAULT_DATA_DIR};
+use seed_tester::streaming::{StreamInput, StreamReader, StreamingBattery};
+use seed_tester::types::{TestResult, Verdict};
+use seed_tester::tests::ent_test::{ent_summary, EntSummary, ENT_TERSE_HEADER};
+use seed_tester::tests::time_seed_test::TimeSeedSearch;
+use seed_tester::tests::{
+    generate_junit_report, generate_tap_report, parse_delimiter, parse_raw, parse_text_groups, BitOrder, BitStream, ColumnSelector, DelimitedFormat, Endianness,
+    InputFormat, LagRange, ParseReport, RawFormat, ValueEncoding,
+};
+
+/// Batterie de tests statistiques pour générateurs de nombres aléatoires.
+#[derive(Parser, Debug)]
+#[command(name = "seed_tester", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Exécute la batterie sur un fichier, l'entrée standard ou un générateur de référence.
+    Run(Box<RunArgs>),
+    /// Liste les tests de la batterie et les générateurs de référence.
+    List {
+        /// Présentation de la liste.
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        output: ListFormat,
+    },
+    /// Affiche le schéma JSON des rapports (`run --output json`, routes du serveur).
+    Schema,
+    /// Compare deux rapports JSON (`run --output json`) : évolution de chaque test, tests nouvellement en
+    /// échec et distributions des p-values.
+    Compare {
+        /// Rapport de référence (ex. la version précédente du générateur).
+        base: PathBuf,
+        /// Nouveau rapport.
+        head: PathBuf,
+        /// Présentation de la comparaison.
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        output: ListFormat,
+    },
+    /// Historique des exécutions enregistrées (`run --save`, analyses du serveur).
+    History {
+        #[command(flatten)]
+        store: StoreArgs,
+        #[command(subcommand)]
+        action: HistoryCommand,
+    },
+    /// Lance le serveur web (configuration Rocket : Rocket.toml ou variables `ROCKET_*`).
+    Serve,
+}
+
+/// Opérations sur l'historique des exécutions.
+#[derive(Subcommand, Debug)]
+pub enum HistoryCommand {
+    /// Liste les exécutions conservées, de la plus récente à la plus ancienne.
+    List {
+        /// Présentation de la liste.
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        output: ListFormat,
+    },
+    /// Affiche le rapport d'une exécution conservée.
+    Show {
+        /// Identifiant de l'exécution (16 chiffres hexadécimaux).
+        id: String,
+        /// Présentation du rapport.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+        /// Affiche les détails de tous les résultats dans le tableau.
+        #[arg(long)]
+        details: bool,
+    },
+    /// Supprime une exécution conservée.
+    Delete {
+        /// Identifiant de l'exécution.
+        id: String,
+    },
+    /// Affiche la politique de rétention ; avec une option, l'enregistre modifiée et l'applique aussitôt.
+    Retention {
+        /// Nombre maximal d'exécutions conservées (0 : illimité).
+        #[arg(long)]
+        max_runs: Option<usize>,
+        /// Âge maximal des exécutions conservées, en jours (0 : illimité).
+        #[arg(long)]
+        max_age_days: Option<u64>,
+    },
+    /// Applique la politique de rétention enregistrée, ou ses limites remplacées par les options sans
+    /// l'enregistrer.
+    Prune {
+        /// Nombre maximal d'exécutions conservées (0 : illimité).
+        #[arg(long)]
+        max_runs: Option<usize>,
+        /// Âge maximal des exécutions conservées, en jours (0 : illimité).
+        #[arg(long)]
+        max_age_days: Option<u64>,
+    },
+}
+
+/// Répertoire de l'historique des exécutions.
+#[derive(Args, Debug, Clone)]
+pub struct StoreArgs {
+    /// Répertoire de l'historique, partagé avec le serveur (`ROCKET_DATA_DIR`).
+    #[arg(long, env = "SEED_TESTER_DATA_DIR", default_value = DEFAULT_DATA_DIR)]
+    pub data_dir: PathBuf,
+}
+
+impl StoreArgs {
+    fn open(&self) -> Result<RunStore, String> {
+        RunStore::open(&self.data_dir).map_err(|err| format!("{} : {}", self.data_dir.display(), err))
+    }
+}
+
+/// Présentation des résultats sur la sortie standard.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Tableau lisible, une ligne par résultat.
+    Table,
+    /// Rapport JSON versionné (voir la sous-commande `schema`).
+    Json,
+    /// JUnit XML, une `testsuite` par suite analysée.
+    Junit,
+    /// TAP version 13.
+    Tap,
+    /// Format CSV concis de `ent -t`, une ligne par suite analysée (nécessite le test `ent`).
+    EntTerse,
+}
+
+/// Présentation de la liste des tests et des générateurs, ou d'une comparaison.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListFormat {
+    Table,
+    Json,
+}
+
+/// Verdict à partir duquel `run` se termine avec le code 1.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailOn {
+    /// Rejets limites et rejets francs.
+    Weak,
+    /// Rejets francs seulement.
+    Fail,
+}
+
+impl FailOn {
+    fn threshold(self) -> Verdict {
+        match self {
+            FailOn::Weak => Verdict::Weak,
+            FailOn::Fail => Verdict::Fail,
+        }
+    }
+}
+
+/// Format d'un fichier analysé : binaire brut ou l'un des formats texte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Raw,
+    Text(InputFormat),
+}
+
+impl FromStr for FileFormat {
+    type Err = String;
+
+    /// Lit `raw` ou l'identifiant d'un format texte (voir `InputFormat`).
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.trim().eq_ignore_ascii_case("raw") {
+            Ok(FileFormat::Raw)
+        } else {
+            value.parse().map(FileFormat::Text)
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    /// Fichier à analyser ; `-` ou absent pour l'entrée standard.
+    pub file: Option<PathBuf>,
+
+    /// Analyse un générateur de référence au lieu d'un fichier (identifiants : voir `list`).
+    #[arg(long, conflicts_with = "file")]
+    pub generator: Option<ReferenceGenerator>,
+    /// Graine du générateur ; tirée au hasard et affichée si absente.
+    #[arg(long, requires = "generator")]
+    pub seed: Option<u64>,
+    /// Nombre de valeurs de 64 bits tirées du générateur.
+    #[arg(long, default_value_t = 1000)]
+    pub num_values: usize,
+
+    /// Format du fichier : auto, decimal, json, hex, base64, bits, csv, ou raw pour du binaire brut.
+    #[arg(long, default_value = "auto")]
+    pub format: FileFormat,
+    /// Taille des mots du binaire brut et des octets hex / base64 (8, 16, 32 ou 64).
+    #[arg(long, default_value_t = 64)]
+    pub word_bits: u32,
+    /// Boutisme des mots : little ou big.
+    #[arg(long, default_value = "little")]
+    pub endian: Endianness,
+    /// Ordre des bits dans chaque octet : msb_first ou lsb_first.
+    #[arg(long, default_value = "msb_first")]
+    pub bit_order: BitOrder,
+    /// Séparateur des fichiers délimités (un caractère ou `tab`) ; détecté si absent.
+    #[arg(long, value_parser = parse_delimiter)]
+    pub delimiter: Option<char>,
+    /// Présence d'une ligne d'en-tête dans les fichiers délimités ; détectée si absente.
+    #[arg(long)]
+    pub header: Option<bool>,
+    /// Colonne lue dans un fichier délimité (nom ou index à partir de 0).
+    #[arg(long)]
+    pub column: Option<ColumnSelector>,
+    /// Encodage des valeurs de la colonne : unsigned, signed ou hex.
+    #[arg(long, default_value = "unsigned")]
+    pub encoding: ValueEncoding,
+    /// Colonne de regroupement : chaque valeur distincte est analysée comme une suite séparée.
+    #[arg(long)]
+    pub group_by: Option<ColumnSelector>,
+    /// Refuse le fichier si une ligne est rejetée, au lieu de l'ignorer et de le signaler.
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Analyse en flux, en mémoire bornée (binaire brut ou décimal, une valeur par ligne).
+    #[arg(long, conflicts_with = "generator")]
+    pub stream: bool,
+    /// Nombre de valeurs conservées pour les tests sur fenêtre de l'analyse en flux.
+    #[arg(long, requires = "stream")]
+    pub window: Option<usize>,
+
+    /// Tests à exécuter, séparés par des virgules (identifiants : voir `list`) ; par défaut, tous sauf la
+    /// recherche de graine temporelle (`time_seed`), dont le résultat dépend de l'heure du lancement.
+    #[arg(long, value_delimiter = ',')]
+    pub tests: Vec<BatteryTest>,
+    /// Tests à retirer de la batterie, séparés par des virgules.
+    #[arg(long, value_delimiter = ',')]
+    pub skip: Vec<BatteryTest>,
+    /// Décalages du test d'autocorrélation : `DEBUT-FIN` ou `FIN` (à partir de 1).
+    #[arg(long, default_value = "1-4096")]
+    pub lags: LagRange,
+    /// Nombre de threads d'exécution ; 0 pour le parallélisme disponible.
+    #[arg(long, default_value_t = 0)]
+    pub threads: usize,
+
+    /// Verdict qui fait échouer la commande (code de sortie 1) : weak (rejets limites compris) ou fail.
+    #[arg(long, value_enum, default_value_t = FailOn::Fail)]
+    pub fail_on: FailOn,
+    /// Tests affichés mais exclus du verdict (tests instables connus), séparés par des virgules.
+    #[arg(long, value_delimiter = ',')]
+    pub ignore: Vec<BatteryTest>,
+
+    /// Présentation des résultats.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+    /// Affiche les détails de tous les résultats dans le tableau (toujours affichés pour les échecs).
+    #[arg(long)]
+    pub details: bool,
+    /// N'affiche que la ligne de résumé, quel que soit `--output`.
+    #[arg(long)]
+    pub quiet: bool,
+
+    /// Enregistre l'exécution dans l'historique (sous-commande `history`) ; son identifiant est affiché sur
+    /// la sortie d'erreur.
+    #[arg(long)]
+    pub save: bool,
+    #[command(flatten)]
+    pub store: StoreArgs,
+}
+
+/// Ligne de résumé pour les journaux d'intégration continue : verdict, nombre de résultats par verdict,
+/// politique `--fail-on` appliquée et origine des valeurs.
+pub fn summary_line(report: &Report) -> String {
+    let summary = &report.summary;
+    let policy = summary.policy.as_ref().map_or(String::new(), |policy| {
+        let fail_on = if policy.fail_on == Verdict::Weak { "weak" } else { "fail" };
+        format!(" (--fail-on {} : {})", fail_on, if policy.failing { "refusé" } else { "accepté" })
+    });
+    let not_applicable = match summary.not_applicable {
+        0 => String::new(),
+        count => format!(", {} non applicable(s)", count),
+    };
+    format!(
+        "{} : {} réussi(s), {} faible(s), {} échec(s), {} ignoré(s){}{} ; {}",
+        summary.verdict.label(),
+        summary.passed,
+        summary.weak,
+        summary.failed,
+        summary.ignored,
+        not_applicable,
+        policy,
+        report.metadata.input.description
+    )
+}
+
+impl RunArgs {
+    // Batterie demandée : tests choisis moins les tests retirés ; sans `--tests`, la recherche de graine
+    // temporelle est retirée pour qu'un même fichier donne le même verdict quelle que soit l'heure. Sa
+    // fenêtre est fixée au lancement pour être enregistrée dans le rapport
+    fn battery_config(&self) -> BatteryConfig {
+        let mut config = BatteryConfig::default()
+            .num_values(self.num_values)
+            .threads(self.threads)
+            .lags(self.lags)
+            .time_seed(TimeSeedSearch::default());
+        config = if self.tests.is_empty() { config.without(BatteryTest::TimeSeed) } else { config.tests(&self.tests) };
+        for &test in &self.skip {
+            config = config.without(test);
+        }
+        config
+    }
+
+    fn raw_format(&self) -> Result<RawFormat, String> {
+        RawFormat::new(self.word_bits, self.endian, self.bit_order)
+    }
+
+    // Fichier ou entrée standard, décrit avec le format demandé
+    fn input(&self) -> Result<(InputDescription, Box<dyn Read>), String> {
+        let (mut input, reader): (InputDescription, Box<dyn Read>) = match &self.file {
+            Some(path) if path.as_os_str() != "-" => {
+                let file = File::open(path).map_err(|err| format!("{} : {}", path.display(), err))?;
+                (InputDescription::new(InputKind::File, &path.display().to_string()), Box::new(file))
+            }
+            _ => (InputDescription::new(InputKind::Stdin, "entrée standard"), Box::new(std::io::stdin().lock())),
+        };
+        if let FileFormat::Text(format) = self.format {
+            input.format = Some(format);
+        }
+        input.raw = Some(self.raw_format()?);
+        Ok((input, reader))
+    }
+}
+
+/// Exécute la sous-commande `run`.
+///
+/// Retourne : Le rapport, avec la politique `--fail-on` et `--ignore` appliquée et son identifiant si
+/// `--save` l'a enregistré, ou un message d'erreur (lecture impossible, lecture stricte refusée...).
+pub fn run(args: &RunArgs) -> Result<Report, String> {
+    let report = analyze(args)?.with_policy(args.fail_on.threshold(), &args.ignore);
+    if !args.save {
+        return Ok(report);
+    }
+    let store = args.store.open()?;
+    store.insert(report).map_err(|err| format!("{} : {}", store.dir().display(), err))
+}
+
+// Lit les valeurs demandées et exécute la batterie sur chaque suite
+fn analyze(args: &RunArgs) -> Result<Report, String> {
+    let config = args.battery_config();
+    if config.tests.is_empty() {
+        return Err("aucun test à exécuter.".to_string());
+    }
+    if args.output == OutputFormat::EntTerse && !config.tests.contains(&BatteryTest::Ent) {
+        return Err("--output ent-terse nécessite le test ent.".to_string());
+    }
+    let mut parameters = RunParameters::new(&config);
+    parameters.strict = args.strict;
+
+    if let Some(generator) = args.generator {
+        let seed = args.seed.unwrap_or_else(rand::random);
+        let values = generate_seeds_with(generator, seed, args.num_values);
+        let mut metadata = RunMetadata::generator(generator, seed, parameters);
+        metadata.input.sha256 = Some(InputFingerprint::of_values(&values));
+        return Ok(Report::new(metadata, vec![SequenceReport::new(None, run_tests(&values, &config))]));
+    }
+
+    let (input, reader) = args.input()?;
+    let mut reader = FingerprintReader::new(reader);
+    if args.stream {
+        let stream_input = match args.format {
+            FileFormat::Raw => StreamInput::Raw(args.raw_format()?),
+            FileFormat::Text(InputFormat::Auto | InputFormat::Decimal) => StreamInput::Decimal,
+            FileFormat::Text(_) => {
+                return Err("l'analyse en flux accepte le binaire brut (raw) et le décimal.".to_string())
+            }
+        };
+        let mut battery = StreamingBattery::new(config);
+        if let Some(window) = args.window {
+            battery = battery.window(window);
+        }
+        parameters.window = Some(battery.window_len());
+        let mut metadata = RunMetadata::new(input, parameters);
+        let analysis = StreamReader::new(stream_input, battery).read_from(&mut reader)?;
+        metadata.input.sha256 = Some(reader.finish());
+        let parse = if args.strict { analysis.parse.into_strict()? } else { analysis.parse };
+        return Ok(Report::new(metadata, vec![SequenceReport::new(Some(parse), analysis.report)]));
+    }
+
+    let mut metadata = RunMetadata::new(input, parameters);
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).map_err(|err| format!("{} : {}", metadata.input.description, err))?;
+    metadata.input.sha256 = Some(reader.finish());
+    let raw = args.raw_format()?;
+    let reports = match args.format {
+        FileFormat::Raw => vec![ParseReport::accepted(InputFormat::Auto, parse_raw(&bytes, &raw)?)],
+        FileFormat::Text(format) => {
+            let content = String::from_utf8(bytes)
+                .map_err(|_| "le fichier n'est pas du texte UTF-8 ; utilisez --format raw pour du binaire.")?;
+            let delimited = DelimitedFormat {
+                delimiter: args.delimiter,
+                header: args.header,
+                column: args.column.clone(),
+                encoding: args.encoding,
+                group_by: args.group_by.clone(),
+            };
+            // Une colonne choisie désigne un fichier délimité, comme sur le serveur
+            let format = match format {
+                InputFormat::Auto if args.column.is_some() || args.group_by.is_some() => InputFormat::Csv,
+                format => format,
+            };
+            parse_text_groups(&content, format, &raw, &delimited)?
+        }
+    };
+    let reports = if args.strict {
+        reports.into_iter().map(ParseReport::into_strict).collect::<Result<Vec<_>, _>>()?
+    } else {
+        reports
+    };
+
+    let sequences: Vec<BitStream> = reports.iter().map(|report| report.stream.clone()).collect();
+    let battery_reports = run_sequences(&sequences, &config);
+    let sequences =
+        reports.into_iter().zip(battery_reports).map(|(parse, report)| SequenceReport::new(Some(parse), report)).collect();
+    Ok(Report::new(metadata, sequences))
+}
+
+/// Met en forme les résultats de `run` selon `--output` ; le tableau se termine par la ligne de résumé,
+/// seule affichée avec `--quiet`.
+pub fn render_run(report: &Report, args: &RunArgs) -> String {
+    if args.quiet {
+        return summary_line(report);
+    }
+    render_report(report, args.output, args.details)
+}
+
+// Met en forme un rapport selon `output` ; le tableau se termine par la ligne de résumé
+fn render_report(report: &Report, output: OutputFormat, details: bool) -> String {
+    let summary = summary_line(report);
+    let source = &report.metadata.input.description;
+    match output {
+        OutputFormat::Table => {}
+        OutputFormat::Json => return serde_json::to_string_pretty(report).unwrap_or_default(),
+        OutputFormat::Junit => {
+            let suites: Vec<(String, Vec<TestResult>)> = report
+                .sequences
+                .iter()
+                .map(|sequence| {
+                    let name = match &sequence.group {
+                        Some(group) => format!("{} [{}]", source, group),
+                        None => source.clone(),
+                    };
+                    (name, sequence.test_results())
+                })
+                .collect();
+            let suites: Vec<(&str, &[TestResult])> =
+                suites.iter().map(|(name, results)| (name.as_str(), results.as_slice())).collect();
+            return generate_junit_report(&suites).trim_end().to_string();
+        }
+        OutputFormat::Tap => {
+            // Noms préfixés par le groupe, comme sur le serveur
+            let results: Vec<TestResult> = report
+                .sequences
+                .iter()
+                .flat_map(|sequence| {
+                    sequence.test_results().into_iter().map(|mut result| {
+                        if let Some(group) = &sequence.group {
+                            result.test_name = format!("[{}] {}", group, result.test_name);
+                        }
+                        result
+                    })
+                })
+                .collect();
+            return generate_tap_report(&results).trim_end().to_string();
+        }
+        OutputFormat::EntTerse => {
+            // Une ligne par suite : celles sans résumé ENT (trop courtes) sont marquées `error`
+            let rows = report.sequences.iter().map(|sequence| match ent_summary(&sequence.test_results()) {
+                Some(summary) => summary.to_terse_row(),
+                None => EntSummary::terse_error_row(sequence.num_bits / 8),
+            });
+            return std::iter::once(ENT_TERSE_HEADER.to_string()).chain(rows).collect::<Vec<_>>().join("\n");
+        }
+    }
+    let mut sections = vec![format!("Source : {}", source)];
+    for sequence in &report.sequences {
+        let mut section = Vec::new();
+        if let Some(group) = &sequence.group {
+            section.push(format!("[{}]", group));
+        }
+        match &sequence.parse {
+            Some(parse) if !parse.is_clean() => section.push(parse.summary()),
+            _ => {}
+        }
+        section.push(format_table(&sequence.test_results(), details));
+        sections.push(section.join("\n"));
+    }
+    sections.push(summary);
+    sections.join("\n\n")
+}
+
+/// Met en forme la liste des tests et des générateurs de référence (sous-commande `list`).
+pub fn render_list(format: ListFormat) -> String {
+    if format == ListFormat::Json {
+        let tests: Vec<_> = BatteryTest::ALL
+            .iter()
+            .map(|test| json!({ "id": test.id(), "name": test.name(), "stream_support": test.stream_support() }))
+            .collect();
+        let generators: Vec<_> = ReferenceGenerator::ALL
+            .iter()
+            .map(|generator| {
+                json!({
+                    "id": generator.id(),
+                    "name": generator.name(),
+                    "expected_to_pass": generator.expected_to_pass(),
+                })
+            })
+            .collect();
+        return serde_json::to_string_pretty(&json!({ "tests": tests, "generators": generators })).unwrap_or_default();
+    }
+
+    let mut lines = vec!["Tests (--tests, --skip) :".to_string()];
+    for test in BatteryTest::ALL {
+        let support = if test.reads_stream() { "flux" } else { "fenêtre" };
+        lines.push(format!("  {:<22}{:<46}{}", test.id(), test.name(), support));
+    }
+    lines.push(String::new());
+    lines.push("Générateurs de référence (--generator) :".to_string());
+    for generator in ReferenceGenerator::ALL {
+        let expected = if generator.expected_to_pass() { "réussite attendue" } else { "échec attendu" };
+        lines.push(format!("  {:<22}{:<46}{}", generator.id(), generator.name(), expected));
+    }
+    lines.join("\n")
+}
+
+// Lit un rapport JSON produit par `run --output json` ou par le serveur
+fn read_report(path: &PathBuf) -> Result<Report, String> {
+    let file = File::open(path).map_err(|err| format!("{} : {}", path.display(), err))?;
+    let report: Report = serde_json::from_reader(std::io::BufReader::new(file))
+        .map_err(|err| format!("{} : rapport invalide : {}", path.display(), err))?;
+    report.check_schema_version().map_err(|err| format!("{} : {}", path.display(), err))?;
+    Ok(report)
+}
+
+/// Exécute la sous-commande `compare`.
+///
+/// Retourne : La comparaison de `head` au rapport de référence `base`, ou un message d'erreur (fichier
+/// illisible, rapport invalide ou d'une autre version de schéma).
+pub fn compare(base: &PathBuf, head: &PathBuf) -> Result<ReportComparison, String> {
+    Ok(compare_reports(&read_report(base)?, &read_report(head)?))
+}
+
+/// Met en forme une comparaison (sous-commande `compare`).
+pub fn render_comparison(comparison: &ReportComparison, format: ListFormat) -> String {
+    match format {
+        ListFormat::Table => format_comparison(comparison),
+        ListFormat::Json => serde_json::to_string_pretty(comparison).unwrap_or_default(),
+    }
+}
+
+/// Exécute la sous-commande `history`.
+///
+/// Retourne : Le texte à afficher, ou un message d'erreur (historique illisible, exécution inconnue...).
+pub fn history(store: &StoreArgs, action: &HistoryCommand) -> Result<String, String> {
+    let dir = store.data_dir.display();
+    let runs = store.open()?;
+    let io_error = |err: std::io::Error| format!("{} : {}", dir, err);
+    let unknown = |id: &str| format!("exécution {} inconnue dans l'historique ({}).", id, dir);
+    let removed = |ids: Vec<String>| match ids.len() {
+        0 => "Aucune exécution supprimée.".to_string(),
+        count => format!("{} exécution(s) supprimée(s) : {}", count, ids.join(", ")),
+    };
+    match action {
+        HistoryCommand::List { output } => {
+            let list = runs.list().map_err(io_error)?;
+            match output {
+                ListFormat::Table => Ok(format_history(&list, &runs.retention().map_err(io_error)?)),
+                ListFormat::Json => Ok(serde_json::to_string_pretty(&list).unwrap_or_default()),
+            }
+        }
+        HistoryCommand::Show { id, output, details } => {
+            let report = runs.get(id).map_err(io_error)?.ok_or_else(|| unknown(id))?;
+            Ok(render_report(&report, *output, *details))
+        }
+        HistoryCommand::Delete { id } => match runs.delete(id).map_err(io_error)? {
+            true => Ok(format!("Exécution {} supprimée.", id)),
+            false => Err(unknown(id)),
+        },
+        HistoryCommand::Retention { max_runs, max_age_days } => {
+            let mut policy = runs.retention().map_err(io_error)?;
+            if max_runs.is_none() && max_age_days.is_none() {
+                return Ok(format!("Rétention : {}.", policy.describe()));
+            }
+            policy.max_runs = max_runs.unwrap_or(policy.max_runs);
+            policy.max_age_days = max_age_days.unwrap_or(policy.max_age_days);
+            let ids = runs.set_retention(policy).map_err(io_error)?;
+            Ok(format!("Rétention : {}.\n{}", policy.describe(), removed(ids)))
+        }
+        HistoryCommand::Prune { max_runs, max_age_days } => {
+            let stored = runs.retention().map_err(io_error)?;
+            let policy = RetentionPolicy {
+                max_runs: max_runs.unwrap_or(stored.max_runs),
+                max_age_days: max_age_days.unwrap_or(stored.max_age_days),
+            };
+            Ok(removed(runs.prune(&policy).map_err(io_error)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_run(args: &[&str]) -> RunArgs {
+        let args = [&["seed_tester", "run"], args].concat();
+        match Cli::try_parse_from(args).unwrap().command {
+            Command::Run(run) => *run,
+            command => panic!("{:?}", command),
+        }
+    }
+
+    #[test]
+    fn test_run_generator_and_grouped_file() {
+        let args =
+            parse_run(&["--generator", "cha_cha20", "--seed", "7", "--tests", "frequency,ent", "--output", "json"]);
+        let output = run(&args).unwrap();
+        assert_eq!(output.metadata.input.description, "générateur cha_cha20, graine 7");
+        assert_eq!((output.metadata.generator, output.metadata.seed), (Some(ReferenceGenerator::ChaCha20), Some(7)));
+        assert_eq!(output.sequences[0].num_values, 1000);
+        let json: serde_json::Value = serde_json::from_str(&render_run(&output, &args)).unwrap();
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(json["sequences"][0]["results"][0]["test_name"], "Test de fréquence des bits");
+        assert_eq!(json["sequences"][0]["results"][0]["test"], "frequency");
+        assert_eq!(json["metadata"]["parameters"]["tests"], json!(["frequency", "ent"]));
+        assert_eq!(json["metadata"]["parameters"]["lags"], json!({ "start": 1, "end": 4096 }));
+        assert_eq!(json["summary"]["policy"]["fail_on"], "fail");
+        assert!(json["sequences"][0].get("parse").is_none());
+
+        // Sortie concise de `ent -t`, refusée sans le test ent
+        let terse = parse_run(&["--generator", "cha_cha20", "--seed", "7", "--tests", "ent", "--output", "ent-terse"]);
+        let csv = render_run(&run(&terse).unwrap(), &terse);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!((lines.len(), lines[0]), (2, ENT_TERSE_HEADER));
+        assert!(lines[1].starts_with("1,8000,"), "{}", csv);
+        let terse = parse_run(&["--generator", "cha_cha20", "--tests", "frequency", "--output", "ent-terse"]);
+        assert!(run(&terse).unwrap_err().contains("ent-terse"));
+        // Une suite trop courte pour ENT garde sa ligne, marquée `error`
+        let short = ["--generator", "cha_cha20", "--num-values", "0", "--tests", "ent", "--output", "ent-terse"];
+        let short = parse_run(&short);
+        let csv = render_run(&run(&short).unwrap(), &short);
+        assert_eq!(csv.lines().collect::<Vec<_>>(), [ENT_TERSE_HEADER, "error,0,,,,,"]);
+
+        let path = std::env::temp_dir().join(format!("seed_tester_cli_{}.csv", std::process::id()));
+        let rows: String = (0..400u64).map(|i| format!("{},{}\n", ["a", "b"][i as usize % 2], i * 0x9E37)).collect();
+        std::fs::write(&path, format!("source,value\n{}b,x\n", rows)).unwrap();
+        let file = path.to_str().unwrap();
+        let grouped = [file, "--column", "value", "--group-by", "source"];
+        let args = parse_run(&[&grouped[..], &["--tests", "frequency", "--threads", "2"]].concat());
+        let output = run(&args).unwrap();
+        assert_eq!((output.metadata.input.kind, output.metadata.input.format), (InputKind::File, Some(InputFormat::Auto)));
+        assert_eq!(output.metadata.num_values, 400);
+        let groups: Vec<_> = output.sequences.iter().map(|sequence| sequence.group.as_deref()).collect();
+        assert_eq!(groups, [Some("a"), Some("b")]);
+        let table = render_run(&output, &args);
+        assert!(table.contains("[b]\nGroupe « b » : 200 enregistrement(s) accepté(s), 1 rejeté(s)."), "{}", table);
+        assert!(table.contains("Test de fréquence des bits"));
+
+        let strict = parse_run(&[&grouped[..], &["--strict"]].concat());
+        assert!(run(&strict).unwrap_err().contains("Lecture stricte refusée"));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(Cli::try_parse_from(["seed_tester", "run", "--tests", "unknown"]).is_err());
+        assert_eq!(parse_run(&["--lags", "2-128"]).battery_config().lags, LagRange::new(2, 128).unwrap());
+        // La recherche de graine temporelle dépend de l'heure : uniquement sur demande
+        assert!(!parse_run(&[]).battery_config().tests.contains(&BatteryTest::TimeSeed));
+        let time_seed = parse_run(&["--tests", "frequency,time_seed"]).battery_config();
+        assert_eq!(time_seed.tests, [BatteryTest::Frequency, BatteryTest::TimeSeed]);
+        assert!(time_seed.time_seed.is_some());
+        assert!(Cli::try_parse_from(["seed_tester", "run", "--lags", "0-8"]).is_err());
+        assert!(Cli::try_parse_from(["seed_tester", "run", "x.bin", "--generator", "mt19937"]).is_err());
+        assert!(render_list(ListFormat::Table).contains("state_recovery"));
+    }
+
+    #[test]
+    fn test_compare_reads_saved_reports() {
+        let report = |generator| {
+            let args = parse_run(&["--generator", generator, "--seed", "1", "--tests", "frequency,poker,ent", "--output", "json"]);
+            render_run(&run(&args).unwrap(), &args)
+        };
+        let dir = std::env::temp_dir();
+        let paths = ["base", "head", "future"]
+            .map(|name| dir.join(format!("seed_tester_compare_{}_{}.json", name, std::process::id())));
+        std::fs::write(&paths[0], report("cha_cha20")).unwrap();
+        std::fs::write(&paths[1], report("counter")).unwrap();
+        std::fs::write(&paths[2], report("cha_cha20").replacen("\"schema_version\": 1", "\"schema_version\": 99", 1))
+            .unwrap();
+
+        let comparison = compare(&paths[0], &paths[1]).unwrap();
+        assert_eq!(comparison.head.description, "générateur counter, graine 1");
+        assert!(comparison.has_regressions());
+        assert!(render_comparison(&comparison, ListFormat::Table).contains("Évolution"));
+        let json: serde_json::Value =
+            serde_json::from_str(&render_comparison(&comparison, ListFormat::Json)).unwrap();
+        assert_eq!(json["changes"][0]["test"], "frequency");
+        assert!(!compare(&paths[0], &paths[0]).unwrap().has_regressions());
+
+        assert!(compare(&paths[0], &paths[2]).unwrap_err().contains("version de schéma 99"));
+        assert!(compare(&paths[0], &dir.join("seed_tester_absent.json")).is_err());
+        assert!(Cli::try_parse_from(["seed_tester", "compare", "a.json"]).is_err());
+        for path in paths {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_history_saves_and_manages_runs() {
+        let dir = std::env::temp_dir().join(format!("seed_tester_history_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let data_dir = dir.to_str().unwrap();
+        let command = |args: &[&str]| {
+            match Cli::try_parse_from([&["seed_tester", "history", "--data-dir", data_dir], args].concat()) {
+                Ok(Cli { command: Command::History { store, action } }) => history(&store, &action),
+                result => panic!("{:?}", result),
+            }
+        };
+        let save = |generator| {
+            let args = parse_run(&["--generator", generator, "--seed", "3", "--tests", "frequency", "--save", "--data-dir", data_dir]);
+            run(&args).unwrap().metadata.id.unwrap()
+        };
+        let saved = [save("cha_cha20"), save("counter")];
+        assert!(run(&parse_run(&["--generator", "counter", "--tests", "frequency"])).unwrap().metadata.id.is_none());
+
+        let list: Vec<serde_json::Value> = serde_json::from_str(&command(&["list", "--output", "json"]).unwrap()).unwrap();
+        assert_eq!(list.len(), 2);
+        let expected = InputFingerprint::of_values(&generate_seeds_with(ReferenceGenerator::Counter, 3, 1000));
+        let counter = list.iter().find(|run| run["id"] == saved[1].as_str()).unwrap();
+        assert_eq!(counter["sha256"], expected.as_str());
+        assert!(command(&["list"]).unwrap().contains("2 exécution(s) conservée(s)"));
+
+        let shown = command(&["show", &saved[0], "--output", "json"]).unwrap();
+        assert_eq!(serde_json::from_str::<Report>(&shown).unwrap().metadata.id.as_deref(), Some(saved[0].as_str()));
+        assert!(command(&["show", &saved[0]]).unwrap().contains("Test de fréquence des bits"));
+
+        assert_eq!(command(&["delete", &saved[0]]).unwrap(), format!("Exécution {} supprimée.", saved[0]));
+        assert!(command(&["delete", &saved[0]]).unwrap_err().contains("inconnue"));
+        assert!(command(&["show", "invalide"]).unwrap_err().contains("inconnue"));
+
+        assert_eq!(command(&["prune", "--max-runs", "5"]).unwrap(), "Aucune exécution supprimée.");
+        assert_eq!(command(&["retention"]).unwrap(), "Rétention : 100 exécution(s) au plus, sans limite d'âge.");
+        let retention = command(&["retention", "--max-age-days", "30"]).unwrap();
+        assert_eq!(retention, "Rétention : 100 exécution(s) au plus, 30 jour(s) au plus.\nAucune exécution supprimée.");
+        save("mt19937");
+        assert!(command(&["prune", "--max-runs", "1"]).unwrap().starts_with("1 exécution(s) supprimée(s) : "));
+        assert_eq!(command(&["retention"]).unwrap(), "Rétention : 100 exécution(s) au plus, 30 jour(s) au plus.");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_summary_line_shows_policy() {
+        let result = |test_name: &str, passed, p_value| TestResult {
+            test_name: test_name.to_string(),
+            passed,
+            p_value,
+            ..Default::default()
+        };
+        let battery = seed_tester::battery::BatteryReport {
+            version: String::new(),
+            num_values: 0,
+            num_bits: 0,
+            sample_bits: 64,
+            passed: 0,
+            failed: 0,
+            results: vec![
+                result("Test de fréquence des bits", true, None),
+                result("Test de compression", false, Some(0.003)),
+                result("Test de Poker", false, None),
+            ],
+        };
+        let metadata = RunMetadata::new(InputDescription::new(InputKind::Stdin, "entrée standard"), Default::default());
+        let report = Report::new(metadata, vec![SequenceReport::new(None, battery)]);
+        assert_eq!(summary_line(&report), "ÉCHEC : 1 réussi(s), 1 faible(s), 1 échec(s), 0 ignoré(s) ; entrée standard");
+
+        let report = report.with_policy(FailOn::Fail.threshold(), &[BatteryTest::Poker]);
+        assert!(!report.failing());
+        assert_eq!(
+            summary_line(&report),
+            "FAIBLE : 1 réussi(s), 1 faible(s), 0 échec(s), 1 ignoré(s) (--fail-on fail : accepté) ; entrée standard"
+        );
+        assert!(report.with_policy(FailOn::Weak.threshold(), &[BatteryTest::Poker]).failing());
+    }
+}
@@ -0,0 +1,484 @@
+//! Comparaison de deux rapports (ex. la version d'hier d'un générateur et celle d'aujourd'hui) : évolution
+//! du verdict et de la p-value de chaque test, tests nouvellement en échec, et test de Kolmogorov-Smirnov
+//! sur les distributions de p-values des deux exécutions.
+
+use crate::battery::BatteryTest;
+use crate::report::{align_columns, Report, ReportedResult};
+use crate::tests::stats::ks_two_sample;
+use crate::types::Verdict;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Nombre minimal de p-values de chaque côté pour comparer les distributions (en deçà, le test ne peut pas
+// descendre sous le seuil de 1 %)
+const MIN_DISTRIBUTION_P_VALUES: usize = 4;
+// P-value du test de Kolmogorov-Smirnov en dessous de laquelle les distributions diffèrent
+const DISTRIBUTION_THRESHOLD: f64 = 0.01;
+
+/// Évolution d'un test entre les deux rapports.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Change {
+    /// Même verdict.
+    Unchanged,
+    /// Verdict meilleur dans le nouveau rapport.
+    Improved,
+    /// Verdict moins bon dans le nouveau rapport.
+    Regressed,
+    /// Test absent du rapport de référence.
+    Added,
+    /// Test absent du nouveau rapport.
+    Removed,
+}
+
+impl Change {
+    /// Libellé affiché.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Change::Unchanged => "inchangé",
+            Change::Improved => "amélioré",
+            Change::Regressed => "régression",
+            Change::Added => "ajouté",
+            Change::Removed => "retiré",
+        }
+    }
+}
+
+/// Verdict, score et p-value d'un test dans l'un des rapports.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ResultSnapshot {
+    pub verdict: Verdict,
+    pub score: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p_value: Option<f64>,
+}
+
+impl From<&ReportedResult> for ResultSnapshot {
+    fn from(reported: &ReportedResult) -> Self {
+        ResultSnapshot { verdict: reported.verdict, score: reported.result.score, p_value: reported.result.p_value }
+    }
+}
+
+/// Évolution d'un test, identifié par sa suite et son nom.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TestChange {
+    /// Groupe de la suite (`group_by`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    pub test_name: String,
+    /// Test de la batterie ayant produit le résultat.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test: Option<BatteryTest>,
+    /// Résultat dans le rapport de référence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<ResultSnapshot>,
+    /// Résultat dans le nouveau rapport.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<ResultSnapshot>,
+    pub change: Change,
+    /// Écart de p-value (nouveau - référence), lorsque les deux résultats en ont une.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p_value_delta: Option<f64>,
+    /// Vrai si le test n'était pas en échec dans le rapport de référence et l'est dans le nouveau (un verdict
+    /// `Weak` seul n'est pas un échec).
+    pub newly_failing: bool,
+}
+
+/// Résumé de l'un des rapports comparés.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RunSnapshot {
+    /// Description de l'entrée analysée.
+    pub description: String,
+    pub tool_version: String,
+    pub started_at: String,
+    pub num_values: usize,
+    pub verdict: Verdict,
+}
+
+impl From<&Report> for RunSnapshot {
+    fn from(report: &Report) -> Self {
+        RunSnapshot {
+            description: report.metadata.input.description.clone(),
+            tool_version: report.tool.version.clone(),
+            started_at: report.metadata.started_at.clone(),
+            num_values: report.metadata.num_values,
+            verdict: report.summary.verdict,
+        }
+    }
+}
+
+/// Test de Kolmogorov-Smirnov à deux échantillons sur les p-values des deux rapports.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DistributionComparison {
+    /// Nombre de p-values du rapport de référence.
+    pub base_count: usize,
+    /// Nombre de p-values du nouveau rapport.
+    pub head_count: usize,
+    /// Statistique D (plus grand écart entre les fonctions de répartition empiriques).
+    pub statistic: f64,
+    pub p_value: f64,
+    /// Vrai si la p-value est sous le seuil de 1 %.
+    pub differ: bool,
+}
+
+/// Nombre de tests par évolution.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComparisonSummary {
+    pub regressed: usize,
+    pub improved: usize,
+    pub unchanged: usize,
+    pub added: usize,
+    pub removed: usize,
+    pub newly_failing: usize,
+}
+
+/// Comparaison d'un nouveau rapport à un rapport de référence.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReportComparison {
+    /// Rapport de référence.
+    pub base: RunSnapshot,
+    /// Nouveau rapport.
+    pub head: RunSnapshot,
+    /// Différences de version ou de paramètres qui rendent la comparaison moins significative.
+    pub warnings: Vec<String>,
+    pub summary: ComparisonSummary,
+    /// Comparaison des distributions de p-values ; absente si l'un des rapports en a moins de 4.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<DistributionComparison>,
+    /// Évolution de chaque test : ceux du nouveau rapport dans son ordre, puis ceux qui en ont disparu.
+    pub changes: Vec<TestChange>,
+}
+
+impl ReportComparison {
+    /// Vrai si au moins un test est nouvellement en échec.
+    pub fn has_regressions(&self) -> bool {
+        self.summary.newly_failing > 0
+    }
+}
+
+// Clé d'un résultat : groupe, nom du test et rang parmi les résultats de même nom de la suite
+type ResultKey = (Option<String>, String, usize);
+
+// Résultats d'un rapport indexés par clé, dans l'ordre du rapport
+fn keyed_results(report: &Report) -> Vec<(ResultKey, &ReportedResult)> {
+    let mut occurrences: HashMap<(Option<String>, String), usize> = HashMap::new();
+    let mut keyed = Vec::new();
+    for sequence in &report.sequences {
+        for reported in &sequence.results {
+            let name = (sequence.group.clone(), reported.result.test_name.clone());
+            let occurrence = occurrences.entry(name.clone()).or_default();
+            keyed.push(((name.0, name.1, *occurrence), reported));
+            *occurrence += 1;
+        }
+    }
+    keyed
+}
+
+// P-values des résultats applicables d'un rapport
+fn p_values(report: &Report) -> Vec<f64> {
+    report
+        .results()
+        .filter(|result| !result.not_applicable)
+        .filter_map(|result| result.p_value)
+        .filter(|p| p.is_finite())
+        .collect()
+}
+
+/// Compare `head` au rapport de référence `base`.
+///
+/// # Remarques
+/// * Les tests sont appariés par groupe et par nom ; un nom répété dans une suite est apparié dans l'ordre.
+/// * L'évolution d'un test est celle de son verdict (`Pass` < `Weak` < `Fail`).
+/// * Un test est nouvellement en échec lorsque son verdict devient `Fail` ; passer de `Pass` à `Weak` est une
+///   régression mais pas un échec.
+/// * Les résultats non applicables (`TestResult::not_applicable`) ne sont jamais nouvellement en échec et
+///   sont exclus de la comparaison des distributions.
+pub fn compare_reports(base: &Report, head: &Report) -> ReportComparison {
+    let base_results = keyed_results(base);
+    let mut base_index: HashMap<&ResultKey, &ReportedResult> =
+        base_results.iter().map(|(key, reported)| (key, *reported)).collect();
+
+    let change = |key: &ResultKey, base: Option<&ReportedResult>, head: Option<&ReportedResult>| {
+        let (base_snapshot, head_snapshot) = (base.map(ResultSnapshot::from), head.map(ResultSnapshot::from));
+        let change = match (&base_snapshot, &head_snapshot) {
+            (Some(old), Some(new)) if new.verdict > old.verdict => Change::Regressed,
+            (Some(old), Some(new)) if new.verdict < old.verdict => Change::Improved,
+            (Some(_), Some(_)) => Change::Unchanged,
+            (None, _) => Change::Added,
+            (_, None) => Change::Removed,
+        };
+        let p_value_delta = match (&base_snapshot, &head_snapshot) {
+            (Some(old), Some(new)) => old.p_value.zip(new.p_value).map(|(old, new)| new - old),
+            _ => None,
+        };
+        // Un test qui ne peut plus s'appliquer (données plus courtes) n'est pas en échec
+        let applicable = head.is_some_and(|reported| !reported.result.not_applicable);
+        let newly_failing = applicable
+            && matches!((&base_snapshot, &head_snapshot),
+                (Some(old), Some(new)) if old.verdict < Verdict::Fail && new.verdict == Verdict::Fail);
+        TestChange {
+            group: key.0.clone(),
+            test_name: key.1.clone(),
+            test: head.or(base).and_then(|reported| reported.test),
+            base: base_snapshot,
+            head: head_snapshot,
+            change,
+            p_value_delta,
+            newly_failing,
+        }
+    };
+
+    let mut changes = Vec::new();
+    for (key, reported) in keyed_results(head) {
+        let base = base_index.remove(&key);
+        changes.push(change(&key, base, Some(reported)));
+    }
+    for (key, reported) in &base_results {
+        if base_index.contains_key(key) {
+            changes.push(change(key, Some(reported), None));
+        }
+    }
+
+    let mut summary = ComparisonSummary::default();
+    for test_change in &changes {
+        match test_change.change {
+            Change::Unchanged => summary.unchanged += 1,
+            Change::Improved => summary.improved += 1,
+            Change::Regressed => summary.regressed += 1,
+            Change::Added => summary.added += 1,
+            Change::Removed => summary.removed += 1,
+        }
+        summary.newly_failing += test_change.newly_failing as usize;
+    }
+
+    let (base_p, head_p) = (p_values(base), p_values(head));
+    let distribution =
+        (base_p.len() >= MIN_DISTRIBUTION_P_VALUES && head_p.len() >= MIN_DISTRIBUTION_P_VALUES).then(|| {
+            let (statistic, p_value) = ks_two_sample(&base_p, &head_p);
+            DistributionComparison {
+                base_count: base_p.len(),
+                head_count: head_p.len(),
+                statistic,
+                p_value,
+                differ: p_value < DISTRIBUTION_THRESHOLD,
+            }
+        });
+
+    let mut warnings = Vec::new();
+    if base.schema_version != head.schema_version {
+        warnings.push(format!("Versions de schéma différentes : {} et {}.", base.schema_version, head.schema_version));
+    }
+    if base.tool.version != head.tool.version {
+        warnings.push(format!("Versions de l'outil différentes : {} et {}.", base.tool.version, head.tool.version));
+    }
+    if base.metadata.parameters.tests != head.metadata.parameters.tests {
+        warnings.push("Les deux exécutions n'ont pas lancé les mêmes tests.".to_string());
+    }
+    if base.metadata.num_values != head.metadata.num_values {
+        warnings.push(format!(
+            "Nombres de valeurs différents : {} et {}.",
+            base.metadata.num_values, head.metadata.num_values
+        ));
+    }
+
+    ReportComparison { base: base.into(), head: head.into(), warnings, summary, distribution, changes }
+}
+
+/// Met en forme une comparaison en tableau texte : une ligne par test, puis les tests nouvellement en
+/// échec, la comparaison des distributions de p-values et le bilan.
+pub fn format_comparison(comparison: &ReportComparison) -> String {
+    let verdict = |snapshot: &Option<ResultSnapshot>| snapshot.as_ref().map_or("-", |s| s.verdict.label()).to_string();
+    let p_value = |snapshot: &Option<ResultSnapshot>| {
+        snapshot.as_ref().and_then(|s| s.p_value).map_or("-".to_string(), |p| format!("{:.4}", p))
+    };
+    let name = |change: &TestChange| match &change.group {
+        Some(group) => format!("[{}] {}", group, change.test_name),
+        None => change.test_name.clone(),
+    };
+    let rows: Vec<[String; 7]> = comparison
+        .changes
+        .iter()
+        .map(|change| {
+            [
+                name(change),
+                verdict(&change.base),
+                verdict(&change.head),
+                p_value(&change.base),
+                p_value(&change.head),
+                change.p_value_delta.map_or("-".to_string(), |delta| format!("{:+.4}", delta)),
+                change.change.label().to_string(),
+            ]
+        })
+        .collect();
+
+    let mut lines = vec![
+        format!(
+            "Référence : {} ({}, {})",
+            comparison.base.description,
+            comparison.base.started_at,
+            comparison.base.verdict.label()
+        ),
+        format!(
+            "Nouveau   : {} ({}, {})",
+            comparison.head.description,
+            comparison.head.started_at,
+            comparison.head.verdict.label()
+        ),
+    ];
+    lines.extend(comparison.warnings.iter().map(|warning| format!("Attention : {}", warning)));
+    lines.push(String::new());
+    lines.extend(align_columns(["Test", "Référence", "Nouveau", "p réf.", "p nouv.", "Δp", "Évolution"], &rows));
+    lines.push(String::new());
+
+    let failing: Vec<String> = comparison.changes.iter().filter(|change| change.newly_failing).map(name).collect();
+    if failing.is_empty() {
+        lines.push("Aucun test nouvellement en échec.".to_string());
+    } else {
+        lines.push(format!("Nouvellement en échec : {}", failing.join(", ")));
+    }
+    lines.push(match &comparison.distribution {
+        Some(distribution) => format!(
+            "Distributions des p-values (Kolmogorov-Smirnov, {} et {} valeurs) : D = {:.4}, p = {:.4} ({})",
+            distribution.base_count,
+            distribution.head_count,
+            distribution.statistic,
+            distribution.p_value,
+            if distribution.differ { "différentes" } else { "compatibles" }
+        ),
+        None => format!(
+            "Distributions des p-values : moins de {} p-values dans l'un des rapports, non comparées",
+            MIN_DISTRIBUTION_P_VALUES
+        ),
+    });
+    let summary = &comparison.summary;
+    lines.push(format!(
+        "{} régression(s), {} amélioration(s), {} inchangé(s), {} ajouté(s), {} retiré(s)",
+        summary.regressed, summary.improved, summary.unchanged, summary.added, summary.removed
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battery::{run_tests, BatteryConfig};
+    use crate::report::{InputDescription, InputKind, ReportSummary, RunMetadata, RunParameters, SequenceReport};
+    use crate::types::TestResult;
+
+    fn report(results: Vec<TestResult>) -> Report {
+        let metadata =
+            RunMetadata::new(InputDescription::new(InputKind::File, "valeurs.txt"), RunParameters::default());
+        let results: Vec<ReportedResult> = results.into_iter().map(ReportedResult::from).collect();
+        let sequence = SequenceReport {
+            group: None,
+            parse: None,
+            num_values: 100,
+            num_bits: 6400,
+            sample_bits: 64,
+            summary: ReportSummary::new(&results, &[]),
+            results,
+        };
+        Report::new(metadata, vec![sequence])
+    }
+
+    fn result(name: &str, passed: bool, p_value: f64) -> TestResult {
+        TestResult { test_name: name.to_string(), passed, score: p_value, p_value: Some(p_value), ..Default::default() }
+    }
+
+    #[test]
+    fn test_compare_reports_classifies_changes() {
+        let base = report(vec![
+            result("A", true, 0.5),
+            result("B", true, 0.4),
+            result("C", false, 1e-9),
+            result("D", true, 0.3),
+        ]);
+        let head = report(vec![
+            result("A", true, 0.6),
+            result("B", false, 1e-9),
+            result("C", true, 0.2),
+            result("E", true, 0.7),
+        ]);
+        let comparison = compare_reports(&base, &head);
+        let changes: Vec<(&str, Change, bool)> = comparison
+            .changes
+            .iter()
+            .map(|change| (change.test_name.as_str(), change.change, change.newly_failing))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("A", Change::Unchanged, false),
+                ("B", Change::Regressed, true),
+                ("C", Change::Improved, false),
+                ("E", Change::Added, false),
+                ("D", Change::Removed, false),
+            ]
+        );
+        assert!((comparison.changes[0].p_value_delta.unwrap() - 0.1).abs() < 1e-12);
+        assert_eq!(comparison.changes[1].head.as_ref().unwrap().verdict, Verdict::Fail);
+        assert_eq!(
+            comparison.summary,
+            ComparisonSummary { regressed: 1, improved: 1, unchanged: 1, added: 1, removed: 1, newly_failing: 1 }
+        );
+        assert!(comparison.has_regressions());
+        assert_eq!(comparison.distribution.as_ref().map(|distribution| distribution.head_count), Some(4));
+        assert!(comparison.warnings.is_empty());
+
+        let table = format_comparison(&comparison);
+        assert!(table.contains("Nouvellement en échec : B"), "{}", table);
+        assert!(table.contains("+0.1000"), "{}", table);
+        assert!(table.contains("1 régression(s), 1 amélioration(s)"), "{}", table);
+        assert!(!compare_reports(&base, &base).has_regressions());
+
+        // `Pass` -> `Weak` : régression, mais pas un échec
+        let weak = compare_reports(&base, &report(vec![result("A", false, 1e-3)]));
+        assert_eq!(weak.changes[0].head.as_ref().unwrap().verdict, Verdict::Weak);
+        assert_eq!((weak.changes[0].change, weak.changes[0].newly_failing), (Change::Regressed, false));
+        assert!(!weak.has_regressions());
+
+        let skipped = TestResult { not_applicable: true, ..result("A", false, 1e-9) };
+        let comparison = compare_reports(&base, &report(vec![skipped; 4]));
+        assert!(!comparison.has_regressions());
+        assert!(comparison.distribution.is_none());
+    }
+
+    #[test]
+    fn test_compare_reports_distribution_of_p_values() {
+        let uniform: Vec<TestResult> =
+            (0..40).map(|i| result(&format!("T{}", i), true, (i as f64 + 0.5) / 40.0)).collect();
+        let low: Vec<TestResult> =
+            (0..40).map(|i| result(&format!("T{}", i), true, ((i as f64 + 0.5) / 40.0).powi(4))).collect();
+        let comparison = compare_reports(&report(uniform.clone()), &report(uniform.clone()));
+        let distribution = comparison.distribution.unwrap();
+        assert_eq!((distribution.base_count, distribution.statistic, distribution.differ), (40, 0.0, false));
+
+        let distribution = compare_reports(&report(uniform.clone()), &report(low)).distribution.unwrap();
+        assert!(distribution.differ && distribution.p_value < 1e-3, "{:?}", distribution);
+
+        // Moins de 4 p-values dans le nouveau rapport
+        let comparison = compare_reports(&report(uniform.clone()), &report(uniform[..3].to_vec()));
+        assert!(comparison.distribution.is_none());
+        assert!(format_comparison(&comparison).contains("moins de 4 p-values"));
+    }
+
+    #[test]
+    fn test_compare_battery_runs_and_round_trip() {
+        let data: Vec<u64> = (0..2000u64).map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15)).collect();
+        let config = BatteryConfig::default().tests(&[BatteryTest::Frequency, BatteryTest::Poker]);
+        let base = Report::new(
+            RunMetadata::new(InputDescription::new(InputKind::File, "hier"), RunParameters::new(&config)),
+            vec![SequenceReport::new(None, run_tests(&data, &config))],
+        );
+        let mut head = base.clone();
+        head.tool.version = "0.0.0".to_string();
+        head.sequences[0].group = Some("g".to_string());
+
+        let comparison = compare_reports(&base, &head);
+        assert_eq!(comparison.summary.added, comparison.summary.removed);
+        assert_eq!(comparison.changes[0].test, Some(BatteryTest::Frequency));
+        assert!(comparison.warnings[0].contains("0.0.0"));
+        let json = serde_json::to_string(&comparison).unwrap();
+        assert_eq!(serde_json::from_str::<ReportComparison>(&json).unwrap(), comparison);
+    }
+}
@@ -0,0 +1,67 @@
+use rand_core::{impls, Error, RngCore};
+
+/// Source dégénérée qui répète toujours la même valeur.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstantRng {
+    value: u64,
+}
+
+impl ConstantRng {
+    /// Crée la source ; `value` est retournée à chaque appel.
+    pub fn new(value: u64) -> Self {
+        ConstantRng { value }
+    }
+}
+
+/// Source dégénérée qui retourne un compteur incrémenté à chaque appel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CounterRng {
+    next: u64,
+}
+
+impl CounterRng {
+    /// Crée la source ; la première valeur est `start`.
+    pub fn new(start: u64) -> Self {
+        CounterRng { next: start }
+    }
+}
+
+impl RngCore for ConstantRng {
+    fn next_u32(&mut self) -> u32 {
+        self.value as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.value
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl RngCore for CounterRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let value = self.next;
+        self.next = self.next.wrapping_add(1);
+        value
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
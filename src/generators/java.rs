@@ -0,0 +1,45 @@
+// Paramètres du LCG 48 bits de java.util.Random
+const MULTIPLIER: u64 = 0x5_DEEC_E66D;
+const INCREMENT: u64 = 0xB;
+const MASK: u64 = (1 << 48) - 1;
+
+/// `java.util.Random`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaRandom {
+    state: u64,
+}
+
+impl JavaRandom {
+    /// Équivalent de `new Random(seed)` (la graine est brouillée par XOR avec le multiplicateur).
+    pub fn new(seed: i64) -> Self {
+        JavaRandom { state: (seed as u64 ^ MULTIPLIER) & MASK }
+    }
+
+    /// Équivalent de `next(bits)`.
+    fn next(&mut self, bits: u32) -> i32 {
+        self.state = self.state.wrapping_mul(MULTIPLIER).wrapping_add(INCREMENT) & MASK;
+        (self.state >> (48 - bits)) as i32
+    }
+
+    /// Équivalent de `nextInt()`.
+    pub fn next_int(&mut self) -> i32 {
+        self.next(32)
+    }
+
+    /// Équivalent de `nextLong()`.
+    pub fn next_long(&mut self) -> i64 {
+        ((self.next(32) as i64) << 32).wrapping_add(self.next(32) as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_outputs() {
+        // new Random(42).nextInt() et new Random(42).nextLong()
+        assert_eq!(JavaRandom::new(42).next_int(), -1_170_105_035);
+        assert_eq!(JavaRandom::new(42).next_long(), -5_025_562_857_975_149_833);
+    }
+}
@@ -0,0 +1,93 @@
+use rand_core::{impls, Error, RngCore};
+
+// RANDU (IBM) : x_(n+1) = 65539 · x_n mod 2^31
+const RANDU_MULTIPLIER: u32 = 65_539;
+const RANDU_MASK: u32 = 0x7FFF_FFFF;
+// Minimal standard de Park et Miller (minstd_rand0) : x_(n+1) = 16807 · x_n mod (2^31 - 1)
+const MINSTD_MULTIPLIER: u64 = 16_807;
+const MINSTD_MODULUS: u64 = 0x7FFF_FFFF;
+
+/// RANDU, générateur congruentiel d'IBM notoirement défectueux (sorties de 31 bits).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Randu {
+    state: u32,
+}
+
+impl Randu {
+    /// Initialise le générateur ; la graine est ramenée à 31 bits et rendue impaire, comme l'exige RANDU.
+    pub fn new(seed: u64) -> Self {
+        Randu { state: (seed as u32 & RANDU_MASK) | 1 }
+    }
+}
+
+/// `minstd_rand0` (Park et Miller, 1988), sorties dans [1, 2^31 - 2].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Minstd {
+    state: u64,
+}
+
+impl Minstd {
+    /// Initialise le générateur ; la graine est ramenée dans [1, 2^31 - 2].
+    pub fn new(seed: u64) -> Self {
+        Minstd { state: seed % (MINSTD_MODULUS - 1) + 1 }
+    }
+}
+
+// Les sorties de 31 bits sont retournées telles quelles par `next_u32` (bit de poids fort nul)
+// et regroupées par deux dans `next_u64`, comme le ferait un programme qui les concatène naïvement.
+impl RngCore for Randu {
+    fn next_u32(&mut self) -> u32 {
+        self.state = self.state.wrapping_mul(RANDU_MULTIPLIER) & RANDU_MASK;
+        self.state
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_u32(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl RngCore for Minstd {
+    fn next_u32(&mut self) -> u32 {
+        self.state = self.state * MINSTD_MULTIPLIER % MINSTD_MODULUS;
+        self.state as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_u32(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_outputs() {
+        // RANDU depuis 1 : 65539, 393225, 1769499
+        let mut rng = Randu::new(1);
+        assert_eq!([rng.next_u32(), rng.next_u32(), rng.next_u32()], [65_539, 393_225, 1_769_499]);
+
+        // minstd_rand0 depuis 1 : la 10000e valeur vaut 1043618065 (C++ [rand.predef])
+        let mut rng = Minstd::new(0);
+        let value = (0..10_000).map(|_| rng.next_u32()).last().unwrap();
+        assert_eq!(value, 1_043_618_065);
+    }
+}
@@ -0,0 +1,106 @@
+use rand_core::{impls, Error, RngCore};
+
+// Taille de la table de l'algorithme TYPE_3 de glibc (x_i = x_(i-3) + x_(i-31))
+const GLIBC_DEGREE: usize = 31;
+const GLIBC_SEPARATION: usize = 3;
+// Nombre de sorties écartées par `srand` (10 fois le degré)
+const GLIBC_DISCARD: usize = 310;
+
+/// `rand()` de glibc (générateur additif TYPE_3 initialisé par `srand`), sorties de 31 bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlibcRand {
+    table: [u32; GLIBC_DEGREE],
+    front: usize,
+    rear: usize,
+}
+
+impl GlibcRand {
+    /// Équivalent de `srand(seed)`.
+    pub fn new(seed: u32) -> Self {
+        let seed = if seed == 0 { 1 } else { seed };
+        let mut table = [0u32; GLIBC_DEGREE];
+        table[0] = seed;
+        // Remplissage par le générateur minimal standard (16807, méthode de Schrage), comme `srandom_r`
+        let mut word = seed as i64;
+        for slot in table.iter_mut().skip(1) {
+            let hi = word / 127_773;
+            let lo = word % 127_773;
+            word = 16_807 * lo - 2_836 * hi;
+            if word < 0 {
+                word += 2_147_483_647;
+            }
+            *slot = word as u32;
+        }
+
+        let mut rng = GlibcRand { table, front: GLIBC_SEPARATION, rear: 0 };
+        for _ in 0..GLIBC_DISCARD {
+            rng.next_rand();
+        }
+        rng
+    }
+
+    /// Équivalent de `rand()` : valeur dans [0, 2^31).
+    pub fn next_rand(&mut self) -> u32 {
+        let value = self.table[self.front].wrapping_add(self.table[self.rear]);
+        self.table[self.front] = value;
+        self.front = (self.front + 1) % GLIBC_DEGREE;
+        self.rear = (self.rear + 1) % GLIBC_DEGREE;
+        value >> 1
+    }
+}
+
+/// `rand()` de la bibliothèque C de Microsoft (LCG 214013, 2531011), sorties de 15 bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsvcRand {
+    state: u32,
+}
+
+impl MsvcRand {
+    /// Équivalent de `srand(seed)`.
+    pub fn new(seed: u32) -> Self {
+        MsvcRand { state: seed }
+    }
+
+    /// Équivalent de `rand()` : valeur dans [0, 2^15).
+    pub fn next_rand(&mut self) -> u32 {
+        self.state = self.state.wrapping_mul(214_013).wrapping_add(2_531_011);
+        (self.state >> 16) & 0x7FFF
+    }
+}
+
+// `next_u32` retourne la valeur de `rand()` (bit de poids fort nul) ; `next_u64` en regroupe deux.
+impl RngCore for GlibcRand {
+    fn next_u32(&mut self) -> u32 {
+        self.next_rand()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_u32(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_outputs() {
+        // srand(1) : premières valeurs de rand() sous glibc et MSVC
+        let mut rng = GlibcRand::new(1);
+        assert_eq!(rng.next_rand(), 1_804_289_383);
+        assert_eq!(rng.next_rand(), 846_930_886);
+
+        let mut rng = MsvcRand::new(1);
+        assert_eq!(rng.next_rand(), 41);
+        assert_eq!(rng.next_rand(), 18_467);
+    }
+}
@@ -0,0 +1,60 @@
+//! Implémentations de générateurs pseudo-aléatoires de référence, utilisées par les détecteurs
+//! de récupération d'état et comme générateurs de comparaison.
+
+pub mod degenerate;
+pub mod java;
+pub mod lcg;
+pub mod libc;
+pub mod mt19937;
+pub mod reference;
+pub mod splitmix;
+pub mod xorshift;
+
+pub use self::degenerate::{ConstantRng, CounterRng};
+pub use self::java::JavaRandom;
+pub use self::lcg::{Minstd, Randu};
+pub use self::libc::{GlibcRand, MsvcRand};
+pub use self::mt19937::{Mt19937, Mt19937_64};
+pub use self::reference::ReferenceGenerator;
+pub use self::splitmix::SplitMix64;
+pub use self::xorshift::{
+    Xoroshiro128Plus, Xoroshiro128StarStar, Xorshift128, Xorshift128Plus, Xorshift32, Xorshift64, Xorshift64Star,
+    Xoshiro256Plus, Xoshiro256StarStar,
+};
+
+use std::ops::BitXor;
+
+/// Mot de 64 bits transformé uniquement par des opérations linéaires sur GF(2).
+///
+/// # Remarques
+/// * Les transitions des familles xorshift / xoshiro sont écrites une seule fois pour ce trait :
+///   exécutées sur `u64`, elles produisent les valeurs ; exécutées sur un mot symbolique, elles
+///   donnent chaque bit comme combinaison linéaire des bits de l'état initial.
+pub trait LinearWord: Clone + BitXor<Output = Self> {
+    /// Décalage logique à gauche (les bits sortants sont perdus).
+    fn shl(&self, n: u32) -> Self;
+    /// Décalage logique à droite.
+    fn shr(&self, n: u32) -> Self;
+    /// Rotation à gauche sur 64 bits.
+    fn rotl(&self, n: u32) -> Self;
+    /// Conserve uniquement les bits présents dans `mask`.
+    fn mask(&self, mask: u64) -> Self;
+}
+
+impl LinearWord for u64 {
+    fn shl(&self, n: u32) -> Self {
+        self << n
+    }
+
+    fn shr(&self, n: u32) -> Self {
+        self >> n
+    }
+
+    fn rotl(&self, n: u32) -> Self {
+        self.rotate_left(n)
+    }
+
+    fn mask(&self, mask: u64) -> Self {
+        self & mask
+    }
+}
@@ -0,0 +1,285 @@
+use rand_core::{impls, Error, RngCore};
+use std::sync::OnceLock;
+
+// Paramètres de MT19937 (Matsumoto et Nishimura, 1998)
+const N32: usize = 624;
+const M32: usize = 397;
+const MATRIX_A32: u32 = 0x9908_B0DF;
+const UPPER_MASK32: u32 = 0x8000_0000;
+const LOWER_MASK32: u32 = 0x7FFF_FFFF;
+
+// Paramètres de MT19937-64
+const N64: usize = 312;
+const M64: usize = 156;
+const MATRIX_A64: u64 = 0xB502_6F5A_A966_19E9;
+const UPPER_MASK64: u64 = 0xFFFF_FFFF_8000_0000;
+const LOWER_MASK64: u64 = 0x7FFF_FFFF;
+
+/// Mersenne Twister 32 bits (MT19937), identique à `init_genrand` / `genrand_int32` de la référence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mt19937 {
+    state: [u32; N32],
+    index: usize,
+}
+
+impl Mt19937 {
+    /// Taille de l'état en mots, aussi le nombre de sorties nécessaires pour le reconstruire.
+    pub const STATE_WORDS: usize = N32;
+
+    /// Initialise l'état à partir d'une graine (`init_genrand`).
+    pub fn new(seed: u32) -> Self {
+        let mut state = [0u32; N32];
+        state[0] = seed;
+        for i in 1..N32 {
+            state[i] = 1_812_433_253u32
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+        Mt19937 { state, index: N32 }
+    }
+
+    /// Initialise l'état à partir d'une clé (`init_by_array`), comme `random.seed` de Python.
+    /// `key` : Mots de 32 bits de la clé (pour Python, la valeur absolue de la graine découpée en poids faible d'abord).
+    pub fn from_key(key: &[u32]) -> Self {
+        // État de départ fixe de `init_by_array`, calculé une seule fois
+        static BASE: OnceLock<Mt19937> = OnceLock::new();
+        let mut rng = BASE.get_or_init(|| Mt19937::new(19_650_218)).clone();
+        let state = &mut rng.state;
+        let key: &[u32] = if key.is_empty() { &[0] } else { key };
+        let (mut i, mut j) = (1usize, 0usize);
+        for _ in 0..N32.max(key.len()) {
+            let previous = state[i - 1] ^ (state[i - 1] >> 30);
+            state[i] = (state[i] ^ previous.wrapping_mul(1_664_525))
+                .wrapping_add(key[j])
+                .wrapping_add(j as u32);
+            i += 1;
+            j += 1;
+            if i >= N32 {
+                state[0] = state[N32 - 1];
+                i = 1;
+            }
+            if j >= key.len() {
+                j = 0;
+            }
+        }
+        for _ in 0..N32 - 1 {
+            let previous = state[i - 1] ^ (state[i - 1] >> 30);
+            state[i] = (state[i] ^ previous.wrapping_mul(1_566_083_941)).wrapping_sub(i as u32);
+            i += 1;
+            if i >= N32 {
+                state[0] = state[N32 - 1];
+                i = 1;
+            }
+        }
+        state[0] = 0x8000_0000;
+        rng
+    }
+
+    /// Première sortie de `Mt19937::new(seed)`, sans construire tout l'état.
+    /// Seuls les mots 0, 1 et 397 de l'état initial interviennent : utile pour les recherches exhaustives de graine.
+    pub fn first_output(seed: u32) -> u32 {
+        let mut words = [0u32; M32 + 1];
+        words[0] = seed;
+        for i in 1..=M32 {
+            words[i] = 1_812_433_253u32
+                .wrapping_mul(words[i - 1] ^ (words[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+        let y = (words[0] & UPPER_MASK32) | (words[1] & LOWER_MASK32);
+        let mag = if y & 1 == 1 { MATRIX_A32 } else { 0 };
+        temper(words[M32] ^ (y >> 1) ^ mag)
+    }
+
+    /// Reconstruit le générateur à partir d'un état brut.
+    /// `state` : Les 624 mots de l'état (par exemple obtenus par `untemper` de 624 sorties consécutives).
+    /// `index` : Position du prochain mot à extraire ; 624 force une régénération complète au prochain appel.
+    pub fn from_state(state: [u32; N32], index: usize) -> Self {
+        Mt19937 { state, index: index.min(N32) }
+    }
+
+    /// Régénère les 624 mots de l'état.
+    fn twist(&mut self) {
+        for i in 0..N32 {
+            let y = (self.state[i] & UPPER_MASK32) | (self.state[(i + 1) % N32] & LOWER_MASK32);
+            let mag = if y & 1 == 1 { MATRIX_A32 } else { 0 };
+            self.state[i] = self.state[(i + M32) % N32] ^ (y >> 1) ^ mag;
+        }
+        self.index = 0;
+    }
+}
+
+impl RngCore for Mt19937 {
+    fn next_u32(&mut self) -> u32 {
+        if self.index >= N32 {
+            self.twist();
+        }
+        let y = self.state[self.index];
+        self.index += 1;
+        temper(y)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_u32(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Transformation de sortie (tempering) de MT19937.
+pub fn temper(mut y: u32) -> u32 {
+    y ^= y >> 11;
+    y ^= (y << 7) & 0x9D2C_5680;
+    y ^= (y << 15) & 0xEFC6_0000;
+    y ^ (y >> 18)
+}
+
+/// Inverse exact de `temper` : retrouve le mot d'état à partir d'une sortie.
+pub fn untemper(mut y: u32) -> u32 {
+    y = undo_right_shift_xor(y as u64, 18, u32::MAX as u64, 32) as u32;
+    y = undo_left_shift_xor(y as u64, 15, 0xEFC6_0000, 32) as u32;
+    y = undo_left_shift_xor(y as u64, 7, 0x9D2C_5680, 32) as u32;
+    undo_right_shift_xor(y as u64, 11, u32::MAX as u64, 32) as u32
+}
+
+/// Mersenne Twister 64 bits (MT19937-64), identique à `init_genrand64` / `genrand64_int64` de la référence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mt19937_64 {
+    state: [u64; N64],
+    index: usize,
+}
+
+impl Mt19937_64 {
+    /// Taille de l'état en mots, aussi le nombre de sorties nécessaires pour le reconstruire.
+    pub const STATE_WORDS: usize = N64;
+
+    /// Initialise l'état à partir d'une graine (`init_genrand64`).
+    pub fn new(seed: u64) -> Self {
+        let mut state = [0u64; N64];
+        state[0] = seed;
+        for i in 1..N64 {
+            state[i] = 6_364_136_223_846_793_005u64
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 62))
+                .wrapping_add(i as u64);
+        }
+        Mt19937_64 { state, index: N64 }
+    }
+
+    /// Reconstruit le générateur à partir d'un état brut (voir `Mt19937::from_state`).
+    pub fn from_state(state: [u64; N64], index: usize) -> Self {
+        Mt19937_64 { state, index: index.min(N64) }
+    }
+
+    /// Régénère les 312 mots de l'état.
+    fn twist(&mut self) {
+        for i in 0..N64 {
+            let y = (self.state[i] & UPPER_MASK64) | (self.state[(i + 1) % N64] & LOWER_MASK64);
+            let mag = if y & 1 == 1 { MATRIX_A64 } else { 0 };
+            self.state[i] = self.state[(i + M64) % N64] ^ (y >> 1) ^ mag;
+        }
+        self.index = 0;
+    }
+}
+
+impl RngCore for Mt19937_64 {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        if self.index >= N64 {
+            self.twist();
+        }
+        let y = self.state[self.index];
+        self.index += 1;
+        temper64(y)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Transformation de sortie (tempering) de MT19937-64.
+pub fn temper64(mut y: u64) -> u64 {
+    y ^= (y >> 29) & 0x5555_5555_5555_5555;
+    y ^= (y << 17) & 0x71D6_7FFF_EDA6_0000;
+    y ^= (y << 37) & 0xFFF7_EEE0_0000_0000;
+    y ^ (y >> 43)
+}
+
+/// Inverse exact de `temper64`.
+pub fn untemper64(mut y: u64) -> u64 {
+    y = undo_right_shift_xor(y, 43, u64::MAX, 64);
+    y = undo_left_shift_xor(y, 37, 0xFFF7_EEE0_0000_0000, 64);
+    y = undo_left_shift_xor(y, 17, 0x71D6_7FFF_EDA6_0000, 64);
+    undo_right_shift_xor(y, 29, 0x5555_5555_5555_5555, 64)
+}
+
+/// Inverse de y = x ^ ((x >> shift) & mask) sur `bits` bits : chaque itération fixe `shift` bits de plus.
+fn undo_right_shift_xor(y: u64, shift: u32, mask: u64, bits: u32) -> u64 {
+    let mut x = y;
+    for _ in 0..bits.div_ceil(shift) {
+        x = y ^ ((x >> shift) & mask);
+    }
+    x
+}
+
+/// Inverse de y = x ^ ((x << shift) & mask) sur `bits` bits.
+fn undo_left_shift_xor(y: u64, shift: u32, mask: u64, bits: u32) -> u64 {
+    let width = if bits == 64 { u64::MAX } else { (1u64 << bits) - 1 };
+    let mut x = y;
+    for _ in 0..bits.div_ceil(shift) {
+        x = y ^ ((x << shift) & mask & width);
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mt19937_reference_outputs() {
+        // Valeurs de référence : 10000e sortie pour la graine 5489 (C++ std::mt19937 et std::mt19937_64)
+        let mut rng = Mt19937::new(5489);
+        assert_eq!(rng.next_u32(), Mt19937::first_output(5489));
+        let value = (0..9_999).map(|_| rng.next_u32()).last().unwrap();
+        assert_eq!(value, 4_123_659_995);
+
+        let mut rng = Mt19937_64::new(5489);
+        let value = (0..10_000).map(|_| rng.next_u64()).last().unwrap();
+        assert_eq!(value, 9_981_545_732_273_789_042);
+    }
+
+    #[test]
+    fn test_from_key_matches_python() {
+        // random.seed(42) ; random.getrandbits(32) (deux fois)
+        let mut rng = Mt19937::from_key(&[42]);
+        assert_eq!(rng.next_u32(), 2_746_317_213);
+        assert_eq!(rng.next_u32(), 478_163_327);
+        // random.seed(2**40 + 5) : clé de deux mots
+        assert_eq!(Mt19937::from_key(&[5, 256]).next_u32(), 2_166_296_868);
+    }
+
+    #[test]
+    fn test_untemper_inverts_temper() {
+        for y in [0u32, 1, 0xDEAD_BEEF, u32::MAX, 0x8000_0001] {
+            assert_eq!(untemper(temper(y)), y);
+        }
+        for y in [0u64, 1, 0xDEAD_BEEF_CAFE_BABE, u64::MAX, 0x8000_0000_0000_0001] {
+            assert_eq!(untemper64(temper64(y)), y);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::generators::{
+    ConstantRng, CounterRng, GlibcRand, Minstd, Mt19937, Randu, SplitMix64, Xorshift64, Xoshiro256StarStar,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use rand_core::RngCore;
+use rand_pcg::Pcg64;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Générateurs de référence sélectionnables pour produire les seeds à tester.
+///
+/// # Remarques
+/// * Les générateurs de 31 ou 32 bits produisent chaque seed de 64 bits à partir de deux sorties
+///   (poids faible d'abord) ; pour RANDU, minstd et glibc les bits 31 et 63 sont donc toujours nuls.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceGenerator {
+    /// `rand::thread_rng()` (ChaCha12 réensemencé par le système), la graine est ignorée.
+    #[default]
+    ThreadRng,
+    /// RANDU d'IBM.
+    Randu,
+    /// `minstd_rand0` de Park et Miller.
+    Minstd,
+    /// `rand()` de glibc initialisé par `srand`.
+    GlibcRand,
+    /// Mersenne Twister 32 bits.
+    Mt19937,
+    /// xorshift64 de Marsaglia.
+    Xorshift64,
+    /// xoshiro256** de Blackman et Vigna.
+    Xoshiro256StarStar,
+    /// PCG64 (XSL RR 128/64) de `rand_pcg`.
+    Pcg64,
+    /// SplitMix64.
+    SplitMix64,
+    /// ChaCha20 de `rand_chacha`.
+    ChaCha20,
+    /// Valeur constante égale à la graine.
+    Constant,
+    /// Compteur partant de la graine.
+    Counter,
+}
+
+impl ReferenceGenerator {
+    /// Tous les générateurs de référence.
+    pub const ALL: [ReferenceGenerator; 12] = [
+        ReferenceGenerator::ThreadRng,
+        ReferenceGenerator::Randu,
+        ReferenceGenerator::Minstd,
+        ReferenceGenerator::GlibcRand,
+        ReferenceGenerator::Mt19937,
+        ReferenceGenerator::Xorshift64,
+        ReferenceGenerator::Xoshiro256StarStar,
+        ReferenceGenerator::Pcg64,
+        ReferenceGenerator::SplitMix64,
+        ReferenceGenerator::ChaCha20,
+        ReferenceGenerator::Constant,
+        ReferenceGenerator::Counter,
+    ];
+
+    /// Identifiant utilisé dans les formulaires et l'API (`thread_rng`, `randu`, ...).
+    pub fn id(&self) -> String {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    /// Nom lisible du générateur.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReferenceGenerator::ThreadRng => "thread_rng (système)",
+            ReferenceGenerator::Randu => "RANDU",
+            ReferenceGenerator::Minstd => "minstd",
+            ReferenceGenerator::GlibcRand => "glibc rand()",
+            ReferenceGenerator::Mt19937 => "MT19937",
+            ReferenceGenerator::Xorshift64 => "xorshift64",
+            ReferenceGenerator::Xoshiro256StarStar => "xoshiro256**",
+            ReferenceGenerator::Pcg64 => "PCG64",
+            ReferenceGenerator::SplitMix64 => "SplitMix64",
+            ReferenceGenerator::ChaCha20 => "ChaCha20",
+            ReferenceGenerator::Constant => "Constante",
+            ReferenceGenerator::Counter => "Compteur",
+        }
+    }
+
+    /// Vrai si le générateur doit passer toute la batterie ; faux s'il doit être rejeté par au moins un test
+    /// (défauts statistiques, ou état reconstructible à partir des sorties).
+    pub fn expected_to_pass(&self) -> bool {
+        matches!(
+            self,
+            ReferenceGenerator::ThreadRng
+                | ReferenceGenerator::Pcg64
+                | ReferenceGenerator::SplitMix64
+                | ReferenceGenerator::ChaCha20
+        )
+    }
+
+    /// Construit le générateur initialisé avec `seed`.
+    pub fn build(&self, seed: u64) -> Box<dyn RngCore> {
+        match self {
+            ReferenceGenerator::ThreadRng => Box::new(rand::thread_rng()),
+            ReferenceGenerator::Randu => Box::new(Randu::new(seed)),
+            ReferenceGenerator::Minstd => Box::new(Minstd::new(seed)),
+            ReferenceGenerator::GlibcRand => Box::new(GlibcRand::new(seed as u32)),
+            ReferenceGenerator::Mt19937 => Box::new(Mt19937::new(seed as u32)),
+            ReferenceGenerator::Xorshift64 => Box::new(Xorshift64::new(seed)),
+            ReferenceGenerator::Xoshiro256StarStar => Box::new(Xoshiro256StarStar::new(seed)),
+            ReferenceGenerator::Pcg64 => Box::new(Pcg64::seed_from_u64(seed)),
+            ReferenceGenerator::SplitMix64 => Box::new(SplitMix64::new(seed)),
+            ReferenceGenerator::ChaCha20 => Box::new(ChaCha20Rng::seed_from_u64(seed)),
+            ReferenceGenerator::Constant => Box::new(ConstantRng::new(seed)),
+            ReferenceGenerator::Counter => Box::new(CounterRng::new(seed)),
+        }
+    }
+
+    /// Produit `count` seeds de 64 bits.
+    pub fn generate(&self, seed: u64, count: usize) -> Vec<u64> {
+        let mut rng = self.build(seed);
+        (0..count).map(|_| rng.next_u64()).collect()
+    }
+}
+
+impl FromStr for ReferenceGenerator {
+    type Err = String;
+
+    /// Lit l'identifiant d'un générateur (voir `id`).
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_string()))
+            .map_err(|_| format!("Générateur inconnu : {}", value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_compression, test_cycle_detection, test_ent, test_state_recovery};
+    use crate::types::TestResult;
+
+    type BatteryTest = fn(&[u64]) -> TestResult;
+
+    #[test]
+    fn test_generators_are_reproducible() {
+        for generator in ReferenceGenerator::ALL {
+            assert_eq!(generator.id().parse::<ReferenceGenerator>(), Ok(generator));
+            if generator != ReferenceGenerator::ThreadRng {
+                assert_eq!(generator.generate(42, 16), generator.generate(42, 16), "{}", generator.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_bad_generators_are_rejected() {
+        // Chaque générateur défectueux doit être rejeté par le test qui cible son défaut
+        let ent_chi_square = |seeds: &[u64]| -> TestResult {
+            test_ent(seeds).into_iter().find(|result| result.test_name == "ENT : Chi-square").unwrap()
+        };
+        let cases: Vec<(ReferenceGenerator, BatteryTest)> = vec![
+            (ReferenceGenerator::Randu, ent_chi_square),
+            (ReferenceGenerator::Minstd, ent_chi_square),
+            (ReferenceGenerator::GlibcRand, ent_chi_square),
+            (ReferenceGenerator::Mt19937, test_state_recovery),
+            (ReferenceGenerator::Xorshift64, test_state_recovery),
+            (ReferenceGenerator::Xoshiro256StarStar, test_state_recovery),
+            (ReferenceGenerator::Constant, test_cycle_detection),
+            (ReferenceGenerator::Counter, test_compression),
+        ];
+        for (generator, test) in cases {
+            assert!(!generator.expected_to_pass());
+            let result = test(&generator.generate(12_345, 1000));
+            assert!(!result.passed, "{} devrait échouer : {}", generator.name(), result.details);
+        }
+    }
+}
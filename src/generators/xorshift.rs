@@ -0,0 +1,268 @@
+use crate::generators::{LinearWord, SplitMix64};
+use rand_core::{impls, Error, RngCore};
+
+// Masque des familles à mots de 32 bits, stockés dans des mots de 64 bits
+const MASK32: u64 = 0xFFFF_FFFF;
+// Multiplicateur de sortie de xorshift64* (Vigna, 2016)
+const XORSHIFT64_STAR_MULTIPLIER: u64 = 0x2545_F491_4F6C_DD1D;
+
+// Les fonctions `*_step` ci-dessous font avancer l'état d'un pas et retournent la partie linéaire
+// de la sortie : la sortie elle-même, la valeur avant brouillage (variantes * et **), ou un mot dont
+// le bit de poids faible est celui de la sortie (variantes +, dont l'addition n'est pas linéaire).
+
+/// Pas de xorshift32 (Marsaglia, triplet 13, 17, 5). Retourne la sortie.
+pub fn xorshift32_step<W: LinearWord>(state: &mut [W]) -> W {
+    let mut x = state[0].clone();
+    x = x.clone() ^ x.shl(13).mask(MASK32);
+    x = x.clone() ^ x.shr(17);
+    x = x.clone() ^ x.shl(5).mask(MASK32);
+    state[0] = x.clone();
+    x
+}
+
+/// Pas de xorshift64 (Marsaglia, triplet 13, 7, 17). Retourne la sortie.
+pub fn xorshift64_step<W: LinearWord>(state: &mut [W]) -> W {
+    let mut x = state[0].clone();
+    x = x.clone() ^ x.shl(13);
+    x = x.clone() ^ x.shr(7);
+    x = x.clone() ^ x.shl(17);
+    state[0] = x.clone();
+    x
+}
+
+/// Pas de xorshift64* (triplet 12, 25, 27). Retourne l'état, avant multiplication.
+pub fn xorshift64_star_step<W: LinearWord>(state: &mut [W]) -> W {
+    let mut x = state[0].clone();
+    x = x.clone() ^ x.shr(12);
+    x = x.clone() ^ x.shl(25);
+    x = x.clone() ^ x.shr(27);
+    state[0] = x.clone();
+    x
+}
+
+/// Pas de xorshift128 (Marsaglia, quatre mots de 32 bits). Retourne la sortie.
+pub fn xorshift128_step<W: LinearWord>(state: &mut [W]) -> W {
+    let t = state[0].clone() ^ state[0].shl(11).mask(MASK32);
+    let w = state[3].clone();
+    state[0] = state[1].clone();
+    state[1] = state[2].clone();
+    state[2] = w.clone();
+    state[3] = w.clone() ^ w.shr(19) ^ t.clone() ^ t.shr(8);
+    state[3].clone()
+}
+
+/// Pas de xorshift128+ (Vigna, triplet 23, 18, 5). Retourne s0 ^ s1, de même bit de poids faible que la sortie.
+pub fn xorshift128_plus_step<W: LinearWord>(state: &mut [W]) -> W {
+    let mut s1 = state[0].clone();
+    let s0 = state[1].clone();
+    let linear = s0.clone() ^ s1.clone();
+    state[0] = s0.clone();
+    s1 = s1.clone() ^ s1.shl(23);
+    state[1] = s1.clone() ^ s0.clone() ^ s1.shr(18) ^ s0.shr(5);
+    linear
+}
+
+/// Pas de xoroshiro128 (rotations 24, 16, 37). Retourne s0, avant mise à jour.
+pub fn xoroshiro128_step<W: LinearWord>(state: &mut [W]) -> W {
+    let s0 = state[0].clone();
+    let s1 = state[1].clone() ^ s0.clone();
+    state[0] = s0.rotl(24) ^ s1.clone() ^ s1.shl(16);
+    state[1] = s1.rotl(37);
+    s0
+}
+
+/// Pas de xoshiro256 (décalage 17, rotation 45). Retourne l'état avant mise à jour (s0, s1, s3).
+pub fn xoshiro256_step<W: LinearWord>(state: &mut [W]) -> [W; 3] {
+    let before = [state[0].clone(), state[1].clone(), state[3].clone()];
+    let t = state[1].shl(17);
+    state[2] = state[2].clone() ^ state[0].clone();
+    state[3] = state[3].clone() ^ state[1].clone();
+    state[1] = state[1].clone() ^ state[2].clone();
+    state[0] = state[0].clone() ^ state[3].clone();
+    state[2] = state[2].clone() ^ t;
+    state[3] = state[3].rotl(45);
+    before
+}
+
+/// Brouillage ** commun à xoroshiro128** et xoshiro256** : rotl(x · 5, 7) · 9.
+pub fn star_star(x: u64) -> u64 {
+    x.wrapping_mul(5).rotate_left(7).wrapping_mul(9)
+}
+
+/// Inverse de `star_star`.
+pub fn star_star_inverse(y: u64) -> u64 {
+    // 5 et 9 sont impairs donc inversibles modulo 2^64
+    const INVERSE_9: u64 = 0x8E38_E38E_38E3_8E39;
+    const INVERSE_5: u64 = 0xCCCC_CCCC_CCCC_CCCD;
+    y.wrapping_mul(INVERSE_9).rotate_right(7).wrapping_mul(INVERSE_5)
+}
+
+/// Inverse de la multiplication de sortie de xorshift64*.
+pub fn xorshift64_star_inverse(y: u64) -> u64 {
+    const INVERSE: u64 = 0x5907_1D96_D81E_CD35; // XORSHIFT64_STAR_MULTIPLIER^-1 mod 2^64
+    y.wrapping_mul(INVERSE)
+}
+
+/// Remplit un état à partir d'une graine avec SplitMix64, en évitant l'état nul.
+fn seed_state<const N: usize>(seed: u64, mask: u64) -> [u64; N] {
+    let mut splitmix = SplitMix64::new(seed);
+    let mut state = [0u64; N];
+    for word in state.iter_mut() {
+        *word = splitmix.next_u64() & mask;
+    }
+    if state.iter().all(|&word| word == 0) {
+        state[0] = 1;
+    }
+    state
+}
+
+// Implémente `new`, `from_state` et `RngCore` pour un générateur d'état `[u64; N]`.
+// `$next` calcule la prochaine sortie de 64 bits (ou 32 bits si `$bits` vaut 32) à partir de `&mut [u64]`.
+macro_rules! linear_generator {
+    ($(#[$doc:meta])* $name:ident, $words:expr, $bits:tt, $next:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name {
+            state: [u64; $words],
+        }
+
+        impl $name {
+            /// Nombre de mots de l'état.
+            pub const STATE_WORDS: usize = $words;
+
+            /// Initialise l'état à partir d'une graine (remplissage par SplitMix64).
+            pub fn new(seed: u64) -> Self {
+                let mask = if $bits == 32 { MASK32 } else { u64::MAX };
+                $name { state: seed_state::<$words>(seed, mask) }
+            }
+
+            /// Reconstruit le générateur à partir d'un état brut (ne doit pas être entièrement nul).
+            pub fn from_state(state: [u64; $words]) -> Self {
+                $name { state }
+            }
+        }
+
+        linear_generator!(@rng $name, $bits, $next);
+    };
+    (@rng $name:ident, 64, $next:expr) => {
+        impl RngCore for $name {
+            fn next_u32(&mut self) -> u32 {
+                (self.next_u64() >> 32) as u32
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                let next: fn(&mut [u64]) -> u64 = $next;
+                next(&mut self.state)
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                impls::fill_bytes_via_next(self, dest)
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+    };
+    (@rng $name:ident, 32, $next:expr) => {
+        impl RngCore for $name {
+            fn next_u32(&mut self) -> u32 {
+                let next: fn(&mut [u64]) -> u64 = $next;
+                next(&mut self.state) as u32
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                impls::next_u64_via_u32(self)
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                impls::fill_bytes_via_next(self, dest)
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+    };
+}
+
+linear_generator!(
+    /// xorshift32 de Marsaglia (sorties de 32 bits).
+    Xorshift32, 1, 32, xorshift32_step
+);
+linear_generator!(
+    /// xorshift64 de Marsaglia.
+    Xorshift64, 1, 64, xorshift64_step
+);
+linear_generator!(
+    /// xorshift64* de Vigna.
+    Xorshift64Star, 1, 64, |state| xorshift64_star_step(state).wrapping_mul(XORSHIFT64_STAR_MULTIPLIER)
+);
+linear_generator!(
+    /// xorshift128 de Marsaglia (quatre mots de 32 bits, sorties de 32 bits).
+    Xorshift128, 4, 32, xorshift128_step
+);
+linear_generator!(
+    /// xorshift128+ de Vigna.
+    Xorshift128Plus, 2, 64, |state| {
+        let output = state[0].wrapping_add(state[1]);
+        xorshift128_plus_step(state);
+        output
+    }
+);
+linear_generator!(
+    /// xoroshiro128+ de Blackman et Vigna.
+    Xoroshiro128Plus, 2, 64, |state| {
+        let output = state[0].wrapping_add(state[1]);
+        xoroshiro128_step(state);
+        output
+    }
+);
+linear_generator!(
+    /// xoroshiro128** de Blackman et Vigna.
+    Xoroshiro128StarStar, 2, 64, |state| star_star(xoroshiro128_step(state))
+);
+linear_generator!(
+    /// xoshiro256+ de Blackman et Vigna.
+    Xoshiro256Plus, 4, 64, |state| {
+        let [s0, _, s3] = xoshiro256_step(state);
+        s0.wrapping_add(s3)
+    }
+);
+linear_generator!(
+    /// xoshiro256** de Blackman et Vigna.
+    Xoshiro256StarStar, 4, 64, |state| {
+        let [_, s1, _] = xoshiro256_step(state);
+        star_star(s1)
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_outputs() {
+        // Premières sorties des implémentations de référence pour un état fixé
+        let mut rng = Xoshiro256StarStar::from_state([1, 2, 3, 4]);
+        assert_eq!(rng.next_u64(), 11520);
+        assert_eq!(rng.next_u64(), 0);
+        assert_eq!(rng.next_u64(), 1_509_978_240);
+
+        let mut rng = Xorshift32::from_state([1]);
+        assert_eq!(rng.next_u32(), 270_369);
+
+        let mut rng = Xoroshiro128Plus::from_state([1, 2]);
+        assert_eq!(rng.next_u64(), 3);
+    }
+
+    #[test]
+    fn test_scrambler_inverses() {
+        for x in [0u64, 1, 0xDEAD_BEEF_CAFE_BABE, u64::MAX] {
+            assert_eq!(star_star_inverse(star_star(x)), x);
+            assert_eq!(xorshift64_star_inverse(x.wrapping_mul(XORSHIFT64_STAR_MULTIPLIER)), x);
+        }
+    }
+}
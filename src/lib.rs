@@ -1,4 +1,12 @@
 pub mod types;
 pub mod report;
+pub mod compare;
+pub mod store;
 pub mod seed_generator;
+pub mod generators;
+pub mod battery;
+pub mod runner;
 pub mod tests;
+pub mod testing;
+pub mod streaming;
+pub mod parallel;
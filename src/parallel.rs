@@ -0,0 +1,88 @@
+//! Exécution parallèle à ordre déterministe, sur des threads du système (`std::thread::scope`).
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Nombre de threads effectif : `threads`, ou le parallélisme disponible si `threads` vaut 0.
+pub fn resolve_threads(threads: usize) -> usize {
+    if threads == 0 {
+        thread::available_parallelism().map_or(1, |available| available.get())
+    } else {
+        threads
+    }
+}
+
+/// Répartit `threads` threads entre `items` travaux indépendants et le parallélisme interne de chacun.
+///
+/// Retourne : Le nombre de travaux exécutés simultanément et le nombre de threads de chaque travail.
+pub fn split_threads(threads: usize, items: usize) -> (usize, usize) {
+    let threads = resolve_threads(threads);
+    let outer = threads.min(items.max(1));
+    (outer, (threads / outer).max(1))
+}
+
+/// Applique `f` à chaque élément sur au plus `threads` threads (0 : automatique).
+///
+/// Retourne : Les résultats dans l'ordre des éléments, quel que soit l'ordre d'exécution.
+///
+/// # Remarques
+/// * Chaque thread prend l'élément suivant dès qu'il a fini le précédent : un travail long n'en retarde
+///   pas d'autres.
+/// * Une panique dans `f` est propagée à l'appelant.
+pub fn map_ordered<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = resolve_threads(threads).min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let (next, f) = (&next, &f);
+    let mut indexed: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(move || {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else { break };
+                        done.push((index, f(item)));
+                    }
+                    done
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+            .collect()
+    });
+    indexed.sort_by_key(|(index, _)| *index);
+    indexed.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_keep_input_order() {
+        let items: Vec<u64> = (0..200).collect();
+        // Durées inégales : les travaux se terminent dans le désordre
+        let slow_square = |&value: &u64| {
+            thread::sleep(std::time::Duration::from_micros((200 - value) * 10));
+            value * value
+        };
+        let expected: Vec<u64> = items.iter().map(|value| value * value).collect();
+        assert_eq!(map_ordered(&items, 8, slow_square), expected);
+        assert_eq!(map_ordered(&items, 1, slow_square), expected);
+        assert!(map_ordered(&Vec::<u64>::new(), 4, slow_square).is_empty());
+        assert_eq!(split_threads(8, 2), (2, 4));
+        assert_eq!(split_threads(4, 10), (4, 1));
+        assert!(resolve_threads(0) >= 1);
+    }
+}
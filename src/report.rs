@@ -1,4 +1,450 @@
-use crate::types::TestResult;
+//! Rapports d'exécution : schéma JSON versionné commun à la bibliothèque, à la ligne de commande et au
+//! serveur, et mises en forme texte.
+//!
+//! Un `Report` décrit une exécution complète : métadonnées (entrée, générateur, graine, paramètres,
+//! version de l'outil, horodatages), bilan, puis une entrée par suite analysée avec ses résultats. Son
+//! schéma JSON (draft 2020-12) est `REPORT_SCHEMA` ; toute modification incompatible incrémente
+//! `REPORT_SCHEMA_VERSION`.
+
+use crate::battery::{BatteryConfig, BatteryReport, BatteryTest};
+use crate::generators::ReferenceGenerator;
+use crate::runner::{RunConfig, CRATE_VERSION};
+use crate::tests::time_seed_test::TimeSeedSearch;
+use crate::tests::{InputFormat, LagRange, ParseReport, RawFormat};
+use crate::types::{TestResult, Verdict};
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+
+/// Version du schéma des rapports, enregistrée dans chaque rapport (`schema_version`).
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Schéma JSON des rapports (`schema/report.schema.json`).
+pub const REPORT_SCHEMA: &str = include_str!("../schema/report.schema.json");
+
+// Nom de l'outil enregistré dans les rapports
+const TOOL_NAME: &str = "seed_tester";
+
+/// Rapport versionné d'une exécution.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Report {
+    /// Version du schéma (`REPORT_SCHEMA_VERSION`).
+    pub schema_version: u32,
+    /// Outil ayant produit le rapport.
+    pub tool: ToolInfo,
+    /// Description de l'exécution.
+    pub metadata: RunMetadata,
+    /// Bilan de toutes les suites.
+    pub summary: ReportSummary,
+    /// Une entrée par suite analysée (une seule, ou une par groupe d'un fichier délimité).
+    pub sequences: Vec<SequenceReport>,
+}
+
+/// Outil et version ayant produit un rapport.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ToolInfo {
+    pub name: String,
+    pub version: String,
+}
+
+/// Origine des valeurs analysées.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InputKind {
+    /// Générateur de référence (`metadata.generator` et `metadata.seed`).
+    Generator,
+    /// Fichier lu par la ligne de commande.
+    File,
+    /// Entrée standard.
+    Stdin,
+    /// Fichier envoyé au serveur.
+    Upload,
+}
+
+/// Entrée analysée et format demandé pour la lire.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct InputDescription {
+    pub kind: InputKind,
+    /// Description lisible : chemin du fichier, générateur et graine...
+    pub description: String,
+    /// Format texte demandé ; absent pour un générateur ou du binaire brut.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<InputFormat>,
+    /// Lecture des mots binaires (binaire brut, octets hex / base64) ; absente pour un générateur.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<RawFormat>,
+    /// Empreinte SHA-256 (hexadécimal) des octets lus, ou des valeurs tirées d'un générateur (64 bits
+    /// little-endian) : deux exécutions de même empreinte ont analysé les mêmes données.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+impl InputDescription {
+    /// Entrée de type `kind`, sans format.
+    pub fn new(kind: InputKind, description: &str) -> Self {
+        InputDescription { kind, description: description.to_string(), format: None, raw: None, sha256: None }
+    }
+
+    /// Seeds produites par un générateur de référence.
+    pub fn generator(generator: ReferenceGenerator, seed: u64) -> Self {
+        Self::new(InputKind::Generator, &format!("générateur {}, graine {}", generator.id(), seed))
+    }
+}
+
+/// Empreinte SHA-256 d'une entrée, calculée au fil de la lecture (`InputDescription::sha256`).
+#[derive(Debug, Clone, Default)]
+pub struct InputFingerprint(Sha256);
+
+impl InputFingerprint {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empreinte des valeurs `values`, en octets little-endian (générateurs).
+    pub fn of_values(values: &[u64]) -> String {
+        let mut fingerprint = Self::new();
+        for value in values {
+            fingerprint.update(&value.to_le_bytes());
+        }
+        fingerprint.finish()
+    }
+
+    /// Ajoute les octets suivants de l'entrée.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    /// Retourne : L'empreinte en hexadécimal minuscule (64 caractères).
+    pub fn finish(self) -> String {
+        self.0.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+}
+
+/// Lecteur calculant l'empreinte des octets lus au passage.
+pub struct FingerprintReader<R> {
+    inner: R,
+    fingerprint: InputFingerprint,
+}
+
+impl<R: Read> FingerprintReader<R> {
+    pub fn new(inner: R) -> Self {
+        FingerprintReader { inner, fingerprint: InputFingerprint::new() }
+    }
+
+    /// Retourne : L'empreinte des octets lus jusqu'ici.
+    pub fn finish(self) -> String {
+        self.fingerprint.finish()
+    }
+}
+
+impl<R: Read> Read for FingerprintReader<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buffer)?;
+        self.fingerprint.update(&buffer[..read]);
+        Ok(read)
+    }
+}
+
+/// Paramètres de la batterie ayant produit un rapport.
+///
+/// # Remarques
+/// * Le nombre de threads n'est pas enregistré : il ne change pas les résultats.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct RunParameters {
+    /// Tests exécutés, dans l'ordre.
+    pub tests: Vec<BatteryTest>,
+    /// Taille des blocs du test de blocs.
+    pub block_size: usize,
+    /// Décalages du test d'autocorrélation.
+    #[serde(default)]
+    pub lags: LagRange,
+    /// Fenêtre de la recherche de graine temporelle ; absente pour la fenêtre par défaut.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_seed: Option<TimeSeedSearch>,
+    /// Nombre de valeurs lues par les tests sur fenêtre d'une analyse en flux ; absent hors flux.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<usize>,
+    /// Lecture stricte : le fichier est refusé si un enregistrement est rejeté.
+    #[serde(default)]
+    pub strict: bool,
+}
+
+impl RunParameters {
+    /// Paramètres de `config`.
+    pub fn new(config: &BatteryConfig) -> Self {
+        RunParameters {
+            tests: config.tests.clone(),
+            block_size: config.block_size,
+            lags: config.lags,
+            time_seed: config.time_seed.clone(),
+            window: None,
+            strict: false,
+        }
+    }
+}
+
+/// Métadonnées d'une exécution.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RunMetadata {
+    /// Identifiant de l'exécution dans l'historique (voir `store::RunStore`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Entrée analysée.
+    pub input: InputDescription,
+    /// Générateur de référence, pour une entrée `generator`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<ReferenceGenerator>,
+    /// Graine du générateur.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Nombre total de valeurs de 64 bits analysées, toutes suites confondues.
+    pub num_values: usize,
+    /// Paramètres de la batterie.
+    pub parameters: RunParameters,
+    /// Début de l'exécution (RFC 3339, UTC).
+    pub started_at: String,
+    /// Fin de l'exécution (RFC 3339, UTC).
+    pub finished_at: String,
+    /// Paramètres pour rejouer l'exécution à l'identique (exécutions du serveur sur un générateur).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay: Option<RunConfig>,
+}
+
+// Horodatage RFC 3339 de l'instant présent, à la milliseconde
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+impl RunMetadata {
+    /// Exécution commençant à l'instant présent.
+    pub fn new(input: InputDescription, parameters: RunParameters) -> Self {
+        let started_at = now();
+        RunMetadata {
+            id: None,
+            input,
+            generator: None,
+            seed: None,
+            num_values: 0,
+            parameters,
+            finished_at: started_at.clone(),
+            started_at,
+            replay: None,
+        }
+    }
+
+    /// Exécution d'un générateur de référence.
+    pub fn generator(generator: ReferenceGenerator, seed: u64, parameters: RunParameters) -> Self {
+        RunMetadata {
+            generator: Some(generator),
+            seed: Some(seed),
+            ..Self::new(InputDescription::generator(generator, seed), parameters)
+        }
+    }
+
+    /// Exécution rejouable décrite par `run`.
+    pub fn replay(run: &RunConfig, parameters: RunParameters) -> Self {
+        RunMetadata { replay: Some(run.clone()), ..Self::generator(run.generator, run.seed, parameters) }
+    }
+}
+
+/// Politique de verdict appliquée à un rapport (option `--fail-on` de la ligne de commande).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct VerdictPolicy {
+    /// Verdict à partir duquel l'exécution est refusée (`weak` ou `fail`).
+    pub fail_on: Verdict,
+    /// Tests affichés mais exclus du bilan.
+    pub ignore: Vec<BatteryTest>,
+    /// Vrai si le verdict atteint `fail_on`.
+    pub failing: bool,
+}
+
+/// Bilan d'un ensemble de résultats, hors tests ignorés et résultats non applicables.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReportSummary {
+    /// Pire verdict des résultats pris en compte.
+    pub verdict: Verdict,
+    /// Nombre de résultats, tests ignorés compris.
+    pub total: usize,
+    pub passed: usize,
+    pub weak: usize,
+    pub failed: usize,
+    /// Résultats des tests ignorés.
+    pub ignored: usize,
+    /// Résultats des tests qui n'ont pas pu s'appliquer aux données (`TestResult::not_applicable`).
+    #[serde(default)]
+    pub not_applicable: usize,
+    /// Politique appliquée ; absente du bilan des suites.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<VerdictPolicy>,
+}
+
+impl ReportSummary {
+    /// Bilan de `results`, les résultats des tests de `ignore` et les résultats non applicables étant comptés
+    /// à part.
+    pub fn new<'a, I: IntoIterator<Item = &'a ReportedResult>>(results: I, ignore: &[BatteryTest]) -> Self {
+        let mut summary = ReportSummary {
+            verdict: Verdict::Pass,
+            total: 0,
+            passed: 0,
+            weak: 0,
+            failed: 0,
+            ignored: 0,
+            not_applicable: 0,
+            policy: None,
+        };
+        for result in results {
+            summary.total += 1;
+            if result.test.is_some_and(|test| ignore.contains(&test)) {
+                summary.ignored += 1;
+                continue;
+            }
+            if result.result.not_applicable {
+                summary.not_applicable += 1;
+                continue;
+            }
+            match result.verdict {
+                Verdict::Pass => summary.passed += 1,
+                Verdict::Weak => summary.weak += 1,
+                Verdict::Fail => summary.failed += 1,
+            }
+            summary.verdict = summary.verdict.max(result.verdict);
+        }
+        summary
+    }
+}
+
+/// Résultat d'un test dans un rapport : le `TestResult`, le test qui l'a produit et son verdict.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReportedResult {
+    /// Test de la batterie ayant produit le résultat ; absent pour une erreur de lecture ou d'exécution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test: Option<BatteryTest>,
+    /// Verdict du résultat (voir `TestResult::verdict`).
+    pub verdict: Verdict,
+    #[serde(flatten)]
+    pub result: TestResult,
+}
+
+impl From<TestResult> for ReportedResult {
+    fn from(result: TestResult) -> Self {
+        ReportedResult {
+            test: BatteryTest::ALL.into_iter().find(|test| test.produced(&result)),
+            verdict: result.verdict(),
+            result,
+        }
+    }
+}
+
+/// Résultats d'une suite analysée.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SequenceReport {
+    /// Valeur de la colonne de regroupement (`group_by`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Rapport de lecture du fichier ; absent pour un générateur.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse: Option<ParseReport>,
+    /// Nombre de valeurs de 64 bits analysées par les tests sur les mots.
+    pub num_values: usize,
+    /// Nombre de bits du flux analysé.
+    pub num_bits: usize,
+    /// Largeur des échantillons du flux en bits.
+    pub sample_bits: u32,
+    /// Bilan de la suite.
+    pub summary: ReportSummary,
+    /// Résultats dans l'ordre de la batterie.
+    pub results: Vec<ReportedResult>,
+}
+
+impl SequenceReport {
+    /// Suite analysée par la batterie ; le groupe est celui du rapport de lecture.
+    pub fn new(parse: Option<ParseReport>, report: BatteryReport) -> Self {
+        let results: Vec<ReportedResult> = report.results.into_iter().map(ReportedResult::from).collect();
+        SequenceReport {
+            group: parse.as_ref().and_then(|parse| parse.group.clone()),
+            parse,
+            num_values: report.num_values,
+            num_bits: report.num_bits,
+            sample_bits: report.sample_bits,
+            summary: ReportSummary::new(&results, &[]),
+            results,
+        }
+    }
+
+    /// Suite qui n'a pas pu être analysée : un seul résultat d'erreur.
+    pub fn error(parse: Option<ParseReport>, result: TestResult) -> Self {
+        let results = vec![ReportedResult::from(result)];
+        SequenceReport {
+            group: parse.as_ref().and_then(|parse| parse.group.clone()),
+            parse,
+            num_values: 0,
+            num_bits: 0,
+            sample_bits: 64,
+            summary: ReportSummary::new(&results, &[]),
+            results,
+        }
+    }
+
+    /// Résultats de la suite, sans leur verdict (pour les formats JUnit, TAP et le tableau).
+    pub fn test_results(&self) -> Vec<TestResult> {
+        self.results.iter().map(|reported| reported.result.clone()).collect()
+    }
+}
+
+impl Report {
+    /// Rapport de l'exécution décrite par `metadata`, terminée à l'instant présent.
+    pub fn new(mut metadata: RunMetadata, sequences: Vec<SequenceReport>) -> Self {
+        metadata.num_values = sequences.iter().map(|sequence| sequence.num_values).sum();
+        metadata.finished_at = now();
+        Report {
+            schema_version: REPORT_SCHEMA_VERSION,
+            tool: ToolInfo { name: TOOL_NAME.to_string(), version: CRATE_VERSION.to_string() },
+            summary: ReportSummary::new(sequences.iter().flat_map(|sequence| &sequence.results), &[]),
+            metadata,
+            sequences,
+        }
+    }
+
+    /// Rapport d'une exécution qui n'a pas pu avoir lieu (configuration invalide, lecture impossible...).
+    pub fn error(metadata: RunMetadata, result: TestResult) -> Self {
+        Self::new(metadata, vec![SequenceReport::error(None, result)])
+    }
+
+    /// Applique une politique de verdict : les tests de `ignore` sont exclus des bilans, et l'exécution est
+    /// refusée si le verdict atteint `fail_on`.
+    pub fn with_policy(mut self, fail_on: Verdict, ignore: &[BatteryTest]) -> Self {
+        for sequence in &mut self.sequences {
+            sequence.summary = ReportSummary::new(&sequence.results, ignore);
+        }
+        let mut summary = ReportSummary::new(self.sequences.iter().flat_map(|sequence| &sequence.results), ignore);
+        summary.policy = Some(VerdictPolicy { fail_on, ignore: ignore.to_vec(), failing: summary.verdict >= fail_on });
+        self.summary = summary;
+        self
+    }
+
+    /// Vrai si la politique de verdict refuse l'exécution.
+    pub fn failing(&self) -> bool {
+        self.summary.policy.as_ref().is_some_and(|policy| policy.failing)
+    }
+
+    /// Vérifie que le rapport suit la version du schéma de cet outil (rapport relu depuis un fichier).
+    pub fn check_schema_version(&self) -> Result<(), String> {
+        if self.schema_version == REPORT_SCHEMA_VERSION {
+            Ok(())
+        } else {
+            Err(format!(
+                "version de schéma {} non prise en charge (version attendue : {}).",
+                self.schema_version, REPORT_SCHEMA_VERSION
+            ))
+        }
+    }
+
+    /// Résultats de toutes les suites, dans l'ordre.
+    pub fn results(&self) -> impl Iterator<Item = &TestResult> {
+        self.sequences.iter().flat_map(|sequence| sequence.results.iter().map(|reported| &reported.result))
+    }
+}
 
 pub fn generate_report(results: &[TestResult]) {
     for result in results {
@@ -6,3 +452,319 @@ pub fn generate_report(results: &[TestResult]) {
                  result.test_name, result.passed, result.score, result.details);
     }
 }
+
+/// Met en forme des résultats en tableau texte, pour la ligne de commande.
+/// `results` : Résultats dans l'ordre d'affichage.
+/// `details` : Affiche les détails de chaque résultat sous sa ligne (toujours affichés pour les échecs).
+///
+/// Retourne : Le tableau (test, statut, score, seuils) suivi du nombre de réussites et d'échecs ; le statut
+/// distingue les rejets limites (`FAIBLE`) des rejets francs (`ÉCHEC`), et les tests non applicables (`N/A`).
+pub fn format_table(results: &[TestResult], details: bool) -> String {
+    let rows: Vec<[String; 4]> = results
+        .iter()
+        .map(|result| {
+            [
+                result.test_name.clone(),
+                if result.not_applicable { "N/A" } else { result.verdict().label() }.to_string(),
+                format!("{:.4}", result.score),
+                result.thresholds.map_or("-".to_string(), |(low, high)| format!("[{}, {}]", low, high)),
+            ]
+        })
+        .collect();
+    let lines = align_columns(["Test", "Statut", "Score", "Seuils"], &rows);
+    let mut table = lines[..2].to_vec();
+    for (line, result) in lines[2..].iter().zip(results) {
+        table.push(line.clone());
+        if (details || !result.passed) && !result.details.is_empty() {
+            table.push(format!("    {}", result.details));
+        }
+    }
+
+    let passed = results.iter().filter(|result| result.passed).count();
+    let not_applicable = results.iter().filter(|result| result.not_applicable).count();
+    let mut footer = format!(
+        "{} résultat(s) : {} réussi(s), {} échec(s)",
+        results.len(),
+        passed,
+        results.len() - passed - not_applicable
+    );
+    if not_applicable > 0 {
+        footer.push_str(&format!(", {} non applicable(s)", not_applicable));
+    }
+    table.push(footer);
+    table.join("\n")
+}
+
+/// Aligne des cellules en colonnes séparées par deux espaces, pour les tableaux texte.
+///
+/// Retourne : L'en-tête, son soulignement, puis une ligne par élément de `rows`, sans espaces finaux.
+pub(crate) fn align_columns<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> Vec<String> {
+    let header = header.map(str::to_string);
+    let mut widths = header.clone().map(|title| title.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: &[String; N]| {
+        let line: Vec<String> =
+            cells.iter().zip(widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+        line.join("  ").trim_end().to_string()
+    };
+    let mut lines = vec![line(&header), widths.map(|width| "-".repeat(width)).join("  ")];
+    lines.extend(rows.iter().map(line));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::run_report;
+    use crate::tests::time_seed_test::TimeSeededGenerator;
+    use crate::tests::{parse_text_groups, DelimitedFormat};
+    use serde_json::{json, Value};
+
+    // Mots-clés de schéma pris en charge par `validate`
+    const KEYWORDS: [&str; 16] = [
+        "$schema", "title", "description", "type", "properties", "required", "additionalProperties", "items",
+        "$ref", "$defs", "enum", "minimum", "maximum", "minItems", "maxItems", "format",
+    ];
+
+    // Vérifie que `schema` n'utilise que les mots-clés de `KEYWORDS`
+    fn check_keywords(schema: &Value) {
+        let Value::Object(object) = schema else { return };
+        for (keyword, value) in object {
+            assert!(KEYWORDS.contains(&keyword.as_str()), "mot-clé non pris en charge : {}", keyword);
+            match keyword.as_str() {
+                "properties" | "$defs" => value.as_object().unwrap().values().for_each(check_keywords),
+                "items" => check_keywords(value),
+                _ => {}
+            }
+        }
+    }
+
+    // Valide `value` contre `schema` (références résolues dans `root`)
+    fn validate(value: &Value, schema: &Value, root: &Value, path: &str) -> Result<(), String> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.strip_prefix("#/$defs/").unwrap();
+            return validate(value, &root["$defs"][name], root, path);
+        }
+        if schema["enum"].as_array().is_some_and(|allowed| !allowed.contains(value)) {
+            return Err(format!("{} : {} hors de l'énumération", path, value));
+        }
+        let types: Vec<&str> = match &schema["type"] {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        let matches = |name: &str| match name {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            _ => value.is_null(),
+        };
+        if !types.is_empty() && !types.iter().any(|name| matches(name)) {
+            return Err(format!("{} : {:?} attendu, {} trouvé", path, types, value));
+        }
+        let number = value.as_f64();
+        if schema["minimum"].as_f64().zip(number).is_some_and(|(minimum, number)| number < minimum)
+            || schema["maximum"].as_f64().zip(number).is_some_and(|(maximum, number)| number > maximum)
+        {
+            return Err(format!("{} : {} hors des bornes", path, value));
+        }
+        if let Value::Object(object) = value {
+            for required in schema["required"].as_array().into_iter().flatten() {
+                if !object.contains_key(required.as_str().unwrap()) {
+                    return Err(format!("{} : champ {} manquant", path, required));
+                }
+            }
+            for (key, item) in object {
+                match schema["properties"].get(key) {
+                    Some(property) => validate(item, property, root, &format!("{}.{}", path, key))?,
+                    None if schema["additionalProperties"] == false => {
+                        return Err(format!("{} : champ {} inconnu", path, key))
+                    }
+                    None => {}
+                }
+            }
+        }
+        if let Value::Array(items) = value {
+            let len = items.len() as u64;
+            if schema["minItems"].as_u64().is_some_and(|min| len < min)
+                || schema["maxItems"].as_u64().is_some_and(|max| len > max)
+            {
+                return Err(format!("{} : {} élément(s)", path, len));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate(item, item_schema, root, &format!("{}[{}]", path, index))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn enum_of(schema: &Value, name: &str) -> Vec<Value> {
+        schema["$defs"][name]["enum"].as_array().unwrap().clone()
+    }
+
+    #[test]
+    fn test_reports_match_published_schema() {
+        let schema: Value = serde_json::from_str(REPORT_SCHEMA).unwrap();
+        check_keywords(&schema);
+        assert_eq!(schema["properties"]["schema_version"]["enum"], json!([REPORT_SCHEMA_VERSION]));
+        let ids = |ids: Vec<String>| ids.into_iter().map(Value::String).collect::<Vec<_>>();
+        assert_eq!(enum_of(&schema, "test"), ids(BatteryTest::ALL.iter().map(BatteryTest::id).collect()));
+        assert_eq!(enum_of(&schema, "generator"), ids(ReferenceGenerator::ALL.iter().map(|g| g.id()).collect()));
+        let time_seeded = serde_json::to_value(TimeSeededGenerator::ALL).unwrap();
+        assert_eq!(schema["$defs"]["time_seed_search"]["properties"]["generators"]["items"]["enum"], time_seeded);
+
+        // Exécution rejouable sur un générateur, avec une politique de verdict
+        let mut run = RunConfig::new(ReferenceGenerator::Mt19937, 5489, 300, "standard");
+        run.time_seed_window = 60;
+        let generated = run_report(&run, 0).with_policy(Verdict::Weak, &[BatteryTest::Poker]);
+
+        // Fichier délimité lu par groupes, dont un groupe rejeté, et une suite en erreur
+        let rows: String = (0..300u64).map(|i| format!("{},{}\n", ["a", "b"][i as usize % 2], i * 0x9E37_79B9)).collect();
+        let delimited = DelimitedFormat {
+            column: Some("value".parse().unwrap()),
+            group_by: Some("source".parse().unwrap()),
+            ..Default::default()
+        };
+        let content = format!("source,value\n{}a,x\n", rows);
+        let groups = parse_text_groups(&content, InputFormat::Csv, &RawFormat::default(), &delimited).unwrap();
+        let config = BatteryConfig::default()
+            .tests(&[BatteryTest::Frequency, BatteryTest::TimeSeed, BatteryTest::Ent])
+            .time_seed(TimeSeedSearch::ending_at(1_700_000_000, 60));
+        let mut parameters = RunParameters::new(&config);
+        (parameters.window, parameters.strict) = (Some(100), false);
+        let mut input = InputDescription::new(InputKind::Upload, "fichier envoyé (text/csv)");
+        (input.format, input.raw) = (Some(InputFormat::Csv), Some(RawFormat::default()));
+        let mut sequences: Vec<SequenceReport> = groups
+            .into_iter()
+            .map(|group| {
+                let report = crate::battery::test_stream(&group.stream, &config);
+                SequenceReport::new(Some(group), report)
+            })
+            .collect();
+        sequences.push(SequenceReport::error(
+            None,
+            TestResult { test_name: "Erreur".to_string(), details: "Erreur : test".to_string(), ..Default::default() },
+        ));
+        let mut uploaded = Report::new(RunMetadata::new(input, parameters), sequences);
+        uploaded.metadata.id = Some(crate::store::new_run_id());
+        uploaded.metadata.input.sha256 = Some(InputFingerprint::of_values(&[1, 2, 3]));
+        assert_eq!(uploaded.sequences[0].group.as_deref(), Some("a"));
+        assert_eq!(uploaded.sequences[0].parse.as_ref().unwrap().rejected, 1);
+        assert_eq!(uploaded.metadata.num_values, 300);
+
+        for report in [&generated, &uploaded] {
+            let value = serde_json::to_value(report).unwrap();
+            validate(&value, &schema, &schema, "$").unwrap();
+            // Un rapport relu se réécrit à l'identique
+            let json = serde_json::to_string(report).unwrap();
+            let reread: Report = serde_json::from_str(&json).unwrap();
+            assert_eq!(serde_json::to_string(&reread).unwrap(), json);
+        }
+
+        let mut value = serde_json::to_value(&generated).unwrap();
+        value["sequences"][0]["results"][0]["verdict"] = json!("maybe");
+        assert!(validate(&value, &schema, &schema, "$").unwrap_err().contains("énumération"));
+        value["metadata"].as_object_mut().unwrap().remove("started_at");
+        assert!(validate(&value, &schema, &schema, "$").is_err());
+        let mut value = serde_json::to_value(&generated).unwrap();
+        value["summary"]["extra"] = json!(1);
+        assert!(validate(&value, &schema, &schema, "$").unwrap_err().contains("inconnu"));
+    }
+
+    #[test]
+    fn test_policy_excludes_ignored_tests() {
+        let result = |test_name: &str, passed, p_value| TestResult {
+            test_name: test_name.to_string(),
+            passed,
+            p_value,
+            ..Default::default()
+        };
+        let sequence = |results: Vec<TestResult>| {
+            let report = BatteryReport {
+                version: String::new(),
+                num_values: 10,
+                num_bits: 640,
+                sample_bits: 64,
+                passed: 0,
+                failed: 0,
+                results,
+            };
+            SequenceReport::new(None, report)
+        };
+        let report = Report::new(
+            RunMetadata::new(InputDescription::new(InputKind::Stdin, "entrée standard"), RunParameters::default()),
+            vec![
+                sequence(vec![
+                    result("Test de fréquence des bits", true, None),
+                    result("Test de compression", false, Some(0.003)),
+                ]),
+                sequence(vec![result("Test de Poker", false, None), result("ENT : Chi-square", true, Some(0.5))]),
+            ],
+        );
+        assert_eq!(report.metadata.num_values, 20);
+        assert_eq!(report.sequences[1].results[1].test, Some(BatteryTest::Ent));
+        let summary = &report.summary;
+        assert_eq!((summary.verdict, summary.total, summary.passed, summary.weak, summary.failed), (Verdict::Fail, 4, 2, 1, 1));
+        assert!(!report.failing());
+
+        let ignored = report.clone().with_policy(Verdict::Fail, &[BatteryTest::Poker]);
+        assert_eq!((ignored.summary.verdict, ignored.summary.ignored, ignored.failing()), (Verdict::Weak, 1, false));
+        assert_eq!(ignored.sequences[1].summary.verdict, Verdict::Pass);
+        assert!(report.clone().with_policy(Verdict::Weak, &[BatteryTest::Poker]).failing());
+        // Un test qui ne peut pas s'appliquer n'est pas un rejet
+        let mut skipped = report.clone();
+        skipped.sequences[1].results[0].result.not_applicable = true;
+        let skipped = skipped.with_policy(Verdict::Fail, &[]);
+        let summary = &skipped.summary;
+        assert_eq!((summary.verdict, summary.failed, summary.not_applicable), (Verdict::Weak, 0, 1));
+        let lenient = report.with_policy(Verdict::Weak, &[BatteryTest::Poker, BatteryTest::Compression]);
+        assert_eq!((lenient.summary.verdict, lenient.failing()), (Verdict::Pass, false));
+    }
+
+    #[test]
+    fn test_table_aligns_columns_and_details_failures() {
+        let results = vec![
+            TestResult {
+                test_name: "Test de fréquence des bits".to_string(),
+                passed: true,
+                score: 0.5,
+                details: "Fréquence correcte".to_string(),
+                thresholds: Some((0.45, 0.55)),
+                ..Default::default()
+            },
+            TestResult {
+                test_name: "ENT".to_string(),
+                passed: false,
+                score: 0.0,
+                details: "Erreur : données vides".to_string(),
+                thresholds: None,
+                ..Default::default()
+            },
+        ];
+
+        let table = format_table(&results, false);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "Test                        Statut  Score   Seuils");
+        assert_eq!(lines[2], "Test de fréquence des bits  OK      0.5000  [0.45, 0.55]");
+        assert_eq!(lines[3], "ENT                         ÉCHEC   0.0000  -");
+        assert_eq!(lines[4], "    Erreur : données vides");
+        assert_eq!(lines[5], "2 résultat(s) : 1 réussi(s), 1 échec(s)");
+        assert!(format_table(&results, true).contains("    Fréquence correcte"));
+
+        let mut skipped = results.clone();
+        skipped[1].not_applicable = true;
+        let table = format_table(&skipped, false);
+        assert!(table.contains("ENT                         N/A     0.0000  -"), "{}", table);
+        assert!(table.ends_with("2 résultat(s) : 1 réussi(s), 0 échec(s), 1 non applicable(s)"));
+    }
+}
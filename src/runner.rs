@@ -0,0 +1,140 @@
+use crate::battery::{run_tests, BatteryConfig};
+use crate::generators::ReferenceGenerator;
+use crate::report::{InputFingerprint, Report, RunMetadata, RunParameters, SequenceReport};
+use crate::seed_generator::generate_seeds_with;
+use crate::tests::time_seed_test::{TimeSeedSearch, DEFAULT_WINDOW_SECONDS};
+use crate::tests::LagRange;
+use crate::types::TestResult;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Version du crate enregistrée dans chaque exécution.
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Paramètres complets d'une exécution de la batterie.
+///
+/// Deux exécutions avec la même configuration (et la même version du crate) produisent des résultats
+/// identiques octet pour octet, sauf avec `thread_rng` qui ignore la graine.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RunConfig {
+    /// Générateur de référence produisant les seeds.
+    pub generator: ReferenceGenerator,
+    /// Graine du générateur.
+    pub seed: u64,
+    /// Nombre de seeds générées.
+    pub num_seeds: usize,
+    /// Mode de test choisi dans l'interface.
+    pub test_mode: String,
+    /// Instant de référence (horodatage Unix en secondes) de la recherche de graine temporelle.
+    pub time_reference: i64,
+    /// Largeur en secondes de la fenêtre de recherche de graine temporelle.
+    #[serde(default = "default_time_seed_window")]
+    pub time_seed_window: i64,
+    /// Décalages du test d'autocorrélation.
+    #[serde(default)]
+    pub lags: LagRange,
+    /// Version du crate ayant produit les résultats.
+    pub version: String,
+}
+
+fn default_time_seed_window() -> i64 {
+    DEFAULT_WINDOW_SECONDS
+}
+
+impl RunConfig {
+    /// Nouvelle exécution ancrée à l'instant présent, avec la version courante du crate.
+    pub fn new(generator: ReferenceGenerator, seed: u64, num_seeds: usize, test_mode: &str) -> Self {
+        RunConfig {
+            generator,
+            seed,
+            num_seeds,
+            test_mode: test_mode.to_string(),
+            time_reference: Utc::now().timestamp(),
+            time_seed_window: DEFAULT_WINDOW_SECONDS,
+            lags: LagRange::default(),
+            version: CRATE_VERSION.to_string(),
+        }
+    }
+
+    /// Vrai si l'exécution peut être rejouée à l'identique.
+    pub fn is_reproducible(&self) -> bool {
+        self.generator != ReferenceGenerator::ThreadRng
+    }
+}
+
+/// Génère les seeds décrites par `config` et exécute toute la batterie.
+/// `config` : Paramètres de l'exécution, recopiés dans le champ `run` de chaque résultat.
+///
+/// Retourne : Les résultats dans l'ordre de la batterie.
+pub fn run_battery(config: &RunConfig) -> Vec<TestResult> {
+    run_battery_with_threads(config, 0)
+}
+
+/// Comme `run_battery`, sur `threads` threads (0 : parallélisme disponible).
+///
+/// # Remarques
+/// * Le nombre de threads n'est pas enregistré dans `RunConfig` : il ne change pas les résultats.
+pub fn run_battery_with_threads(config: &RunConfig, threads: usize) -> Vec<TestResult> {
+    let seeds = generate_seeds_with(config.generator, config.seed, config.num_seeds);
+    let mut results = run_tests(&seeds, &battery_config(config, threads)).results;
+    for result in &mut results {
+        result.run = Some(config.clone());
+    }
+    results
+}
+
+/// Génère les seeds décrites par `config` et exécute toute la batterie sur `threads` threads (0 :
+/// parallélisme disponible).
+///
+/// Retourne : Le rapport versionné, dont `metadata.replay` permet de rejouer l'exécution.
+pub fn run_report(config: &RunConfig, threads: usize) -> Report {
+    let battery = battery_config(config, threads);
+    let mut metadata = RunMetadata::replay(config, RunParameters::new(&battery));
+    let seeds = generate_seeds_with(config.generator, config.seed, config.num_seeds);
+    metadata.input.sha256 = Some(InputFingerprint::of_values(&seeds));
+    Report::new(metadata, vec![SequenceReport::new(None, run_tests(&seeds, &battery))])
+}
+
+// Batterie d'une exécution : tous les tests, décalages d'autocorrélation demandés, fenêtre de graine
+// temporelle ancrée à `time_reference`
+fn battery_config(config: &RunConfig, threads: usize) -> BatteryConfig {
+    BatteryConfig::default()
+        .num_values(config.num_seeds)
+        .threads(threads)
+        .lags(config.lags)
+        .time_seed(TimeSeedSearch::ending_at(config.time_reference, config.time_seed_window))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rerun_is_byte_identical() {
+        let mut config = RunConfig::new(ReferenceGenerator::ChaCha20, 2024, 300, "standard");
+        config.time_seed_window = 60;
+        config.lags = LagRange::new(1, 64).unwrap();
+        assert!(config.is_reproducible());
+
+        let first = serde_json::to_string(&run_battery(&config)).unwrap();
+        let stored: Vec<TestResult> = serde_json::from_str(&first).unwrap();
+        let replayed = stored[0].run.clone().unwrap();
+        assert_eq!(replayed, config);
+        assert_eq!(serde_json::to_string(&run_battery(&replayed)).unwrap(), first);
+        assert_eq!(serde_json::to_string(&run_battery_with_threads(&replayed, 1)).unwrap(), first);
+
+        let results = run_battery(&RunConfig { seed: 2025, ..config });
+        assert!(results.iter().all(|result| result.run.as_ref().map(|run| run.seed) == Some(2025)));
+        assert_ne!(serde_json::to_string(&results).unwrap(), first);
+
+        // Le rapport versionné contient les mêmes résultats, et de quoi rejouer l'exécution
+        let report = run_report(&replayed, 1);
+        assert_eq!(report.metadata.replay.as_ref(), Some(&replayed));
+        assert_eq!((report.metadata.seed, report.metadata.num_values), (Some(2024), 300));
+        assert_eq!(report.metadata.parameters.lags, config.lags);
+        let expected: Vec<String> = stored.iter().map(|result| result.test_name.clone()).collect();
+        let names: Vec<String> = report.results().map(|result| result.test_name.clone()).collect();
+        assert_eq!(names, expected);
+        assert!(report.results().all(|result| result.run.is_none()));
+    }
+}
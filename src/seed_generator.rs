@@ -1,12 +1,14 @@
-use rand::Rng;
+use crate::generators::ReferenceGenerator;
 
 pub fn generate_seeds(num_seeds: usize) -> Vec<u64> {
-    let mut rng = rand::thread_rng();
-    let mut seeds = Vec::new();
-
-    for _ in 0..num_seeds {
-        seeds.push(rng.gen::<u64>());  // Génère un seed de 64 bits
-    }
+    // Générateur par défaut (thread_rng) : la graine est ignorée
+    generate_seeds_with(ReferenceGenerator::default(), 0, num_seeds)
+}
 
-    seeds
+/// Génère des seeds de 64 bits avec un générateur de référence.
+/// `generator` : Générateur à utiliser (voir `ReferenceGenerator::ALL`).
+/// `seed` : Graine du générateur (ignorée par `thread_rng`).
+/// `num_seeds` : Nombre de seeds à produire.
+pub fn generate_seeds_with(generator: ReferenceGenerator, seed: u64, num_seeds: usize) -> Vec<u64> {
+    generator.generate(seed, num_seeds)
 }
@@ -0,0 +1,923 @@
+//! Serveur web (sous-commande `serve`) : pages de l'interface et routes d'analyse.
+
+use rocket_dyn_templates::{Template, tera::Tera};
+use rocket::serde::{Deserialize, Serialize};
+use rocket::serde::json::Json;
+use rocket::data::{Data, ToByteUnit};
+use rocket::http::ContentType;
+use rocket::fairing::AdHoc;
+use rocket::State;
+use rocket::http::{Header, Status};
+use rocket::request::Request;
+use rocket::response::{self, Responder};
+use serde_json::json;
+use chrono::{Local, Datelike};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// Importation des modules personnalisés
+use seed_tester::tests::{
+    empty_data_result, file_analysis_config, file_analysis_config_with_time_seed, parse_delimiter, parse_raw, parse_text_groups, DelimitedFormat,
+    InputFormat, LagRange, ParseReport, RawFormat,
+};
+
+use seed_tester::tests::time_seed_test::{TimeResolution, TimeSeedSearch, TimeSeededGenerator};
+
+// Importation conditionnelle pour la génération de PDF
+#[cfg(feature = "pdf_export")]
+use seed_tester::tests::pdf_generator::generate_pdf_report;
+use seed_tester::tests::generate_csv_report;
+use seed_tester::store::{is_run_id, RetentionPolicy, RunStore, RunSummary, DEFAULT_DATA_DIR};
+
+// Importation de la structure des résultats de test
+use seed_tester::types::TestResult;
+use seed_tester::generators::ReferenceGenerator;
+use seed_tester::runner::{run_report, RunConfig, CRATE_VERSION};
+use seed_tester::battery::{test_stream, BatteryConfig, BatteryTest};
+use seed_tester::report::{
+    InputDescription, InputFingerprint, InputKind, Report, RunMetadata, RunParameters, SequenceReport, REPORT_SCHEMA,
+};
+use seed_tester::compare::{compare_reports, ReportComparison};
+use seed_tester::parallel::{map_ordered, split_threads};
+use seed_tester::streaming::{StreamInput, StreamReader, StreamingBattery};
+use rocket::tokio::io::AsyncReadExt;
+
+// Taille maximale d'un fichier analysé en flux (`/upload_stream`), en gibioctets
+const STREAM_UPLOAD_LIMIT_GIB: u64 = 64;
+// Taille des lectures du corps d'une requête analysée en flux, en octets
+const STREAM_READ_BYTES: usize = 1 << 16;
+// Taille maximale des deux rapports envoyés à `/compare`, en mébioctets
+const COMPARE_LIMIT_MIB: u64 = 32;
+// Nombre maximal de graines essayées par la recherche de graine temporelle d'une requête
+// (instants de la fenêtre multipliés par le nombre de générateurs)
+const MAX_TIME_SEED_CANDIDATES: i64 = 2_000_000;
+
+// Configuration du serveur, lue par Rocket (Rocket.toml ou variables d'environnement `ROCKET_*`)
+// `battery_threads` : threads d'exécution de la batterie par requête (`ROCKET_BATTERY_THREADS`) ;
+// 0 ou absent pour le parallélisme disponible
+// `data_dir` : répertoire de l'historique des exécutions (`ROCKET_DATA_DIR`, `data` par défaut), partagé
+// avec la sous-commande `history`
+#[derive(Deserialize, Debug, Default)]
+struct AppConfig {
+    #[serde(default)]
+    battery_threads: usize,
+    #[serde(default = "default_data_dir")]
+    data_dir: PathBuf,
+}
+
+fn default_data_dir() -> PathBuf {
+    PathBuf::from(DEFAULT_DATA_DIR)
+}
+
+// Rapport d'une requête d'analyse : une exécution est enregistrée dans l'historique à l'envoi de la réponse
+// sous un nouvel identifiant (`metadata.id`) ; en cas d'échec de l'enregistrement, le rapport est renvoyé sans
+// identifiant. Une requête refusée avant l'analyse (paramètres invalides, données illisibles) n'est pas
+// conservée.
+enum RunResponse {
+    Stored(Report),
+    Rejected(Report),
+}
+
+impl<'r> Responder<'r, 'static> for RunResponse {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let report = match (self, request.rocket().state::<RunStore>()) {
+            (RunResponse::Stored(report), Some(store)) => match store.insert(report.clone()) {
+                Ok(report) => report,
+                Err(err) => {
+                    eprintln!("Erreur lors de l'enregistrement de l'exécution : {}", err);
+                    report
+                }
+            },
+            (RunResponse::Stored(report) | RunResponse::Rejected(report), _) => report,
+        };
+        Json(report).respond_to(request)
+    }
+}
+
+// Fichier exporté, proposé au téléchargement sous le nom `filename`
+#[derive(Responder)]
+struct Export {
+    data: Vec<u8>,
+    content_type: ContentType,
+    disposition: Header<'static>,
+}
+
+impl Export {
+    fn new(data: Vec<u8>, content_type: ContentType, report: &Report, extension: &str) -> Self {
+        let id = report.metadata.id.as_deref().unwrap_or("run");
+        let disposition = format!("attachment; filename=\"seed_tester_{}.{}\"", id, extension);
+        Export { data, content_type, disposition: Header::new("Content-Disposition", disposition) }
+    }
+}
+
+// Structure pour représenter les configurations de test envoyées par le client
+#[derive(Serialize, Deserialize, Debug)]
+struct ConfigForm {
+    num_seeds: usize,
+    test_mode: String,
+    // Générateur de référence produisant les seeds (ChaCha20 par défaut, reproductible)
+    #[serde(default = "default_run_generator")]
+    generator: ReferenceGenerator,
+    // Graine du générateur ; tirée au hasard et enregistrée dans les résultats si absente
+    #[serde(default)]
+    seed: Option<u64>,
+    // Décalages du test d'autocorrélation ("DEBUT-FIN" ou "FIN") ; 1-4096 si absent
+    #[serde(default)]
+    lags: Option<String>,
+}
+
+fn default_run_generator() -> ReferenceGenerator {
+    ReferenceGenerator::ChaCha20
+}
+
+// Route pour afficher la page d'accueil
+#[get("/")]
+fn index_page() -> Template {
+    let mut context = HashMap::new();
+    context.insert("current_year", json!(Local::now().year()));
+    context.insert("app_name", json!("Seed Tester"));
+    context.insert("pdf_enabled", json!(cfg!(feature = "pdf_export"))); // Vérification dynamique
+
+    Template::render("index", &context)
+}
+
+// Route pour afficher la page de configuration
+#[get("/config")]
+fn config_page() -> Template {
+    let mut context = HashMap::new();
+    context.insert("current_year", json!(Local::now().year()));
+    context.insert("default_seeds", json!(1000));
+    context.insert("default_mode", json!("standard"));
+    context.insert("generators", reference_generators_context());
+    context.insert("default_generator", json!(default_run_generator().id()));
+
+    Template::render("config", &context)
+}
+
+// Route pour afficher la page d'exécution des tests RNG
+#[get("/run_tests")]
+fn run_tests_page() -> Template {
+    let mut context = HashMap::new();
+    context.insert("current_year", json!(Local::now().year()));
+    context.insert("default_seeds", json!(1000));
+    context.insert("default_mode", json!("standard"));
+    context.insert("generators", reference_generators_context());
+    context.insert("default_generator", json!(default_run_generator().id()));
+    context.insert("pdf_enabled", json!(cfg!(feature = "pdf_export"))); // Vérification dynamique
+
+    Template::render("run_tests", &context)
+}
+
+// Route pour afficher la page de comparaison de deux rapports
+// `base` / `head` : identifiants d'exécutions de l'historique, comparées dès l'ouverture de la page
+#[get("/compare?<base>&<head>")]
+fn compare_page(base: Option<&str>, head: Option<&str>) -> Template {
+    let mut context = HashMap::new();
+    context.insert("current_year", json!(Local::now().year()));
+    context.insert("base", json!(base.unwrap_or_default()));
+    context.insert("head", json!(head.unwrap_or_default()));
+
+    Template::render("compare", &context)
+}
+
+// Deux rapports enregistrés (`run --output json`, réponses des routes d'analyse) à comparer
+#[derive(Deserialize, Debug)]
+struct CompareForm {
+    base: Report,
+    head: Report,
+}
+
+// Route comparant un nouveau rapport (`head`) à un rapport de référence (`base`)
+#[post("/compare", data = "<input>")]
+async fn compare_route(input: Data<'_>) -> Result<Json<ReportComparison>, (Status, String)> {
+    let data = input
+        .open(COMPARE_LIMIT_MIB.mebibytes())
+        .into_string()
+        .await
+        .map_err(|err| (Status::BadRequest, format!("Erreur de lecture : {}", err)))?;
+    if !data.is_complete() {
+        return Err((Status::PayloadTooLarge, format!("Rapports limités à {} Mio.", COMPARE_LIMIT_MIB)));
+    }
+    let form: CompareForm = serde_json::from_str(&data)
+        .map_err(|err| (Status::BadRequest, format!("Rapports invalides : {}", err)))?;
+    for (label, report) in [("référence", &form.base), ("nouveau", &form.head)] {
+        report.check_schema_version().map_err(|err| (Status::BadRequest, format!("Rapport {} : {}", label, err)))?;
+    }
+    Ok(Json(compare_reports(&form.base, &form.head)))
+}
+
+// Route comparant deux exécutions conservées : `head` (la dernière si absent) à la référence `base`
+#[get("/compare/runs?<base>&<head>")]
+fn compare_runs(
+    base: &str,
+    head: Option<&str>,
+    store: &State<RunStore>,
+) -> Result<Json<ReportComparison>, (Status, String)> {
+    let (base, head) = (stored_run(store, Some(base))?, stored_run(store, head)?);
+    for (label, report) in [("référence", &base), ("nouveau", &head)] {
+        report.check_schema_version().map_err(|err| (Status::BadRequest, format!("Rapport {} : {}", label, err)))?;
+    }
+    Ok(Json(compare_reports(&base, &head)))
+}
+
+// Route pour exécuter les tests RNG
+#[post("/run_tests", data = "<input>")]
+async fn run_tests_route(
+    content_type: &ContentType,
+    input: Data<'_>,
+    app: &State<AppConfig>,
+) -> RunResponse {
+    // Rapport d'erreur d'une configuration illisible ou invalide
+    let config_error = |result: TestResult| {
+        let input = InputDescription::new(InputKind::Generator, "configuration envoyée");
+        let parameters = RunParameters::new(&BatteryConfig::default());
+        RunResponse::Rejected(Report::error(RunMetadata::new(input, parameters), result))
+    };
+
+    let data = match input.open(128.kibibytes()).into_string().await {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("Erreur lors de la lecture des données : {:?}", err);
+            return config_error(TestResult {
+                test_name: "Erreur de lecture".to_string(),
+                passed: false,
+                score: 0.0,
+                details: format!("Erreur : {:?}", err),
+                thresholds: None,
+                ..Default::default()
+            });
+        }
+    };
+
+    let config: Option<ConfigForm> = if content_type.is_json() {
+        serde_json::from_str(&data).ok()
+    } else {
+        None
+    };
+
+    if let Some(config) = config {
+        if config.num_seeds == 0 {
+            return config_error(TestResult {
+                test_name: "Configuration invalide".to_string(),
+                passed: false,
+                score: 0.0,
+                details: "Le nombre de seeds doit être supérieur à 0.".to_string(),
+                thresholds: None,
+                ..Default::default()
+            });
+        }
+
+        let lags = match config.lags.as_deref().filter(|lags| !lags.trim().is_empty()).map(str::parse) {
+            Some(Ok(lags)) => lags,
+            None => LagRange::default(),
+            Some(Err(message)) => {
+                return config_error(TestResult {
+                    test_name: "Configuration invalide".to_string(),
+                    passed: false,
+                    score: 0.0,
+                    details: message,
+                    thresholds: None,
+                    ..Default::default()
+                })
+            }
+        };
+
+        // Génération des seeds et exécution des tests, paramètres enregistrés dans le rapport (`replay`)
+        // Graine tirée sur 53 bits : le navigateur la relit sans perte (nombres JavaScript)
+        let seed = config.seed.unwrap_or_else(|| rand::random::<u64>() >> 11);
+        let mut run = RunConfig::new(config.generator, seed, config.num_seeds, &config.test_mode);
+        run.lags = lags;
+        let threads = app.battery_threads;
+        let metadata = RunMetadata::replay(&run, RunParameters::default());
+        RunResponse::Stored(run_blocking(metadata, move |_| run_report(&run, threads)).await)
+    } else {
+        config_error(TestResult {
+            test_name: "Erreur de configuration".to_string(),
+            passed: false,
+            score: 0.0,
+            details: "Erreur dans les données envoyées.".to_string(),
+            thresholds: None,
+            ..Default::default()
+        })
+    }
+}
+
+// Paramètres de lecture des fichiers envoyés
+// `format` : "auto", "decimal", "json", "hex", "base64" ou "bits" pour les fichiers texte (auto par défaut)
+// `word_bits` : 8, 16, 32 ou 64 ; `endian` : "little" ou "big" ; `bit_order` : "msb_first" ou "lsb_first"
+// (fichiers binaires bruts `application/octet-stream`, et octets décodés des formats hex et base64)
+// `strict` : refuse le fichier si une ligne est rejetée, au lieu de l'ignorer et de le signaler
+// Fichiers CSV / TSV : `delimiter` (un caractère ou "tab"), `header` (true / false), `column` et `group_by`
+// (nom ou index à partir de 0), `encoding` ("unsigned", "signed" ou "hex") ; chaque valeur distincte de
+// `group_by` est analysée comme un flux séparé
+// `lags` : décalages du test d'autocorrélation ("DEBUT-FIN" ou "FIN", 1-4096 par défaut)
+// `time_seed` : ajoute la recherche de graine temporelle sur les dernières 24 heures (false par défaut)
+#[derive(FromForm, Debug, Default)]
+struct InputParams {
+    format: Option<String>,
+    word_bits: Option<u32>,
+    endian: Option<String>,
+    bit_order: Option<String>,
+    strict: Option<bool>,
+    delimiter: Option<String>,
+    header: Option<bool>,
+    column: Option<String>,
+    encoding: Option<String>,
+    group_by: Option<String>,
+    lags: Option<String>,
+    time_seed: Option<bool>,
+}
+
+impl InputParams {
+    // Format texte demandé (détection automatique par défaut, CSV si une colonne est choisie)
+    fn input_format(&self) -> Result<InputFormat, String> {
+        match self.format.as_deref() {
+            Some(format) => format.parse(),
+            None if self.column.is_some() || self.group_by.is_some() => Ok(InputFormat::Csv),
+            None => Ok(InputFormat::Auto),
+        }
+    }
+
+    // Lecture des fichiers délimités, les paramètres absents étant détectés
+    fn delimited_format(&self) -> Result<DelimitedFormat, String> {
+        Ok(DelimitedFormat {
+            delimiter: self.delimiter.as_deref().map(parse_delimiter).transpose()?,
+            header: self.header,
+            column: self.column.as_deref().map(str::parse).transpose()?,
+            encoding: self.encoding.as_deref().map_or(Ok(Default::default()), str::parse)?,
+            group_by: self.group_by.as_deref().map(str::parse).transpose()?,
+        })
+    }
+
+    // Décalages du test d'autocorrélation demandés
+    fn lag_range(&self) -> Result<LagRange, String> {
+        self.lags.as_deref().filter(|lags| !lags.trim().is_empty()).map_or(Ok(LagRange::default()), str::parse)
+    }
+
+    // Recherche de graine temporelle demandée, sur la fenêtre par défaut fixée à la réception
+    fn time_seed(&self) -> Option<TimeSeedSearch> {
+        self.time_seed.unwrap_or(false).then(TimeSeedSearch::default)
+    }
+
+    // Format binaire demandé, les valeurs absentes prenant celles de `RawFormat::default()`
+    fn raw_format(&self) -> Result<RawFormat, String> {
+        let default = RawFormat::default();
+        RawFormat::new(
+            self.word_bits.unwrap_or(default.word_bits),
+            self.endian.as_deref().map_or(Ok(default.endianness), str::parse)?,
+            self.bit_order.as_deref().map_or(Ok(default.bit_order), str::parse)?,
+        )
+    }
+}
+
+// Route pour rejouer une exécution enregistrée (`metadata.replay` d'un rapport) à l'identique
+#[post("/rerun", format = "json", data = "<run>")]
+async fn rerun_route(run: Json<RunConfig>, app: &State<AppConfig>) -> RunResponse {
+    let run = run.into_inner();
+    let error = if !run.is_reproducible() {
+        Some("thread_rng ignore la graine : cette exécution ne peut pas être rejouée.".to_string())
+    } else if run.version != CRATE_VERSION {
+        Some(format!(
+            "Exécution produite par la version {} ; cette version ({}) ne garantit pas des résultats identiques.",
+            run.version, CRATE_VERSION
+        ))
+    } else if run.num_seeds == 0 {
+        Some("Le nombre de seeds doit être supérieur à 0.".to_string())
+    } else {
+        None
+    };
+
+    let metadata = RunMetadata::replay(&run, RunParameters::default());
+    match error {
+        Some(message) => RunResponse::Rejected(Report::error(
+            metadata,
+            TestResult {
+                test_name: "Relance impossible".to_string(),
+                passed: false,
+                score: 0.0,
+                details: format!("Erreur : {}", message),
+                thresholds: None,
+                ..Default::default()
+            },
+        )),
+        None => {
+            let threads = app.battery_threads;
+            RunResponse::Stored(run_blocking(metadata, move |_| run_report(&run, threads)).await)
+        }
+    }
+}
+
+// Route pour analyser un fichier RNG
+#[post("/upload_file?<input..>", data = "<file>")]
+// Les groupes (`group_by`) sont analysés en parallèle, chacun avec sa part des threads configurés
+async fn upload_file(
+    content_type: &ContentType,
+    file: Data<'_>,
+    input: InputParams,
+    app: &State<AppConfig>,
+) -> RunResponse {
+    let config = match input.time_seed() {
+        Some(search) => file_analysis_config_with_time_seed(search),
+        None => file_analysis_config(),
+    };
+    let config = match input.lag_range() {
+        Ok(lags) => config.lags(lags),
+        Err(message) => {
+            let metadata = upload_metadata(content_type, &input, RunParameters::new(&config));
+            return RunResponse::Rejected(Report::error(metadata, parameter_error(message)));
+        }
+    };
+    let mut metadata = upload_metadata(content_type, &input, RunParameters::new(&config));
+    let reports = match read_uploaded_seeds(content_type, file, &input, &mut metadata).await {
+        Ok(reports) => reports,
+        Err(error) => return RunResponse::Rejected(Report::error(metadata, error)),
+    };
+    let (groups, threads) = split_threads(app.battery_threads, reports.len());
+    let config = config.threads(threads);
+    RunResponse::Stored(
+        run_blocking(metadata, move |metadata| {
+            let sequences = map_ordered(&reports, groups, |report| {
+                if report.stream.is_empty() {
+                    SequenceReport::error(Some(report.clone()), empty_data_result())
+                } else {
+                    SequenceReport::new(Some(report.clone()), test_stream(&report.stream, &config))
+                }
+            });
+            Report::new(metadata, sequences)
+        })
+        .await,
+    )
+}
+
+// Route pour analyser en flux un fichier trop volumineux pour être chargé en mémoire
+// (binaire brut `application/octet-stream` ou décimal, une valeur par ligne) ; les tests qui ne lisent
+// qu'une fenêtre le signalent dans leurs détails
+#[post("/upload_stream?<input..>", data = "<file>")]
+async fn upload_stream(
+    content_type: &ContentType,
+    file: Data<'_>,
+    input: InputParams,
+    app: &State<AppConfig>,
+) -> RunResponse {
+    let config = match input.time_seed() {
+        Some(search) => BatteryConfig::default().time_seed(search),
+        None => BatteryConfig::default().without(BatteryTest::TimeSeed),
+    };
+    let config = config.threads(app.battery_threads);
+    let config = match input.lag_range() {
+        Ok(lags) => config.lags(lags),
+        Err(message) => {
+            let metadata = upload_metadata(content_type, &input, RunParameters::new(&config));
+            return RunResponse::Rejected(Report::error(metadata, parameter_error(message)));
+        }
+    };
+    let battery = StreamingBattery::new(config.clone());
+    let mut parameters = RunParameters::new(&config);
+    parameters.window = Some(battery.window_len());
+    let mut metadata = upload_metadata(content_type, &input, parameters);
+    let stream_error = |metadata: RunMetadata, details: String| {
+        RunResponse::Rejected(Report::error(
+            metadata,
+            TestResult {
+                test_name: "Erreur".to_string(),
+                passed: false,
+                score: 0.0,
+                details: format!("Erreur : {}", details),
+                thresholds: None,
+                ..Default::default()
+            },
+        ))
+    };
+
+    let stream_input = match (input.input_format(), input.raw_format()) {
+        (Ok(InputFormat::Auto), Ok(raw)) if *content_type == ContentType::Binary => StreamInput::Raw(raw),
+        (Ok(InputFormat::Auto | InputFormat::Decimal), Ok(_)) => StreamInput::Decimal,
+        (Ok(_), Ok(_)) => {
+            return stream_error(
+                metadata,
+                "l'analyse en flux accepte le binaire brut et le décimal (une valeur par ligne).".into(),
+            )
+        }
+        (Err(message), _) | (_, Err(message)) => return stream_error(metadata, message),
+    };
+
+    let mut reader = StreamReader::new(stream_input, battery);
+    let mut stream = file.open(STREAM_UPLOAD_LIMIT_GIB.gibibytes());
+    let mut buffer = vec![0u8; STREAM_READ_BYTES];
+    let mut fingerprint = InputFingerprint::new();
+    loop {
+        match stream.read(&mut buffer).await {
+            Ok(0) => break,
+            Ok(read) => {
+                fingerprint.update(&buffer[..read]);
+                if let Err(message) = reader.feed(&buffer[..read]) {
+                    return stream_error(metadata, message);
+                }
+            }
+            Err(err) => return stream_error(metadata, format!("lecture du fichier impossible : {}", err)),
+        }
+    }
+    metadata.input.sha256 = Some(fingerprint.finish());
+
+    let analysis = match rocket::tokio::task::spawn_blocking(move || reader.finish()).await {
+        Ok(Ok(analysis)) => analysis,
+        Ok(Err(message)) => return stream_error(metadata, message),
+        Err(err) => return stream_error(metadata, format!("l'analyse a été interrompue ({})", err)),
+    };
+    if input.strict.unwrap_or(false) && !analysis.parse.is_clean() {
+        return stream_error(metadata, format!("Lecture stricte refusée : {}", analysis.parse.summary()));
+    }
+    RunResponse::Stored(Report::new(metadata, vec![SequenceReport::new(Some(analysis.parse), analysis.report)]))
+}
+
+// Route pour rechercher une graine temporelle dans un fichier RNG
+// `start` / `end` : bornes de la fenêtre (horodatages Unix dans l'unité `resolution`, "s" ou "ms")
+// `generators` : identifiants séparés par des virgules (ex. "glibc_rand,java_random_int")
+// Les fichiers binaires bruts acceptent les mêmes paramètres que `/upload_file`
+#[post("/time_seed?<start>&<end>&<resolution>&<generators>&<input..>", data = "<file>")]
+#[allow(clippy::too_many_arguments)] // Un argument par paramètre de la route
+async fn time_seed_route(
+    content_type: &ContentType,
+    file: Data<'_>,
+    start: Option<i64>,
+    end: Option<i64>,
+    resolution: Option<String>,
+    generators: Option<String>,
+    input: InputParams,
+    app: &State<AppConfig>,
+) -> RunResponse {
+    let mut search = TimeSeedSearch::default();
+    let mut window = Ok(());
+    if resolution.as_deref() == Some("ms") {
+        // La fenêtre par défaut de 25 heures compterait 90 millions d'instants à la milliseconde
+        search.resolution = TimeResolution::Milliseconds;
+        match (start, end) {
+            (Some(start), Some(end)) => {
+                search.start = start;
+                search.end = end;
+            }
+            _ => {
+                window = Err("la résolution à la milliseconde nécessite une fenêtre explicite (start et end).".into())
+            }
+        }
+    } else {
+        search.start = start.unwrap_or(search.start);
+        search.end = end.unwrap_or(search.end);
+    }
+    let generators = generators
+        .filter(|list| !list.trim().is_empty())
+        .map(|list| list.split(',').map(str::parse).collect::<Result<Vec<TimeSeededGenerator>, _>>())
+        .transpose();
+    if let Ok(Some(list)) = &generators {
+        search.generators = list.clone();
+    }
+    let candidates = search.candidates();
+    if window.is_ok() && candidates > MAX_TIME_SEED_CANDIDATES {
+        window = Err(format!(
+            "la recherche essaierait {} graines, au-delà de la limite de {} par requête ; \
+             réduisez la fenêtre ou le nombre de générateurs.",
+            candidates, MAX_TIME_SEED_CANDIDATES
+        ));
+    }
+
+    let config = BatteryConfig::default().tests(&[BatteryTest::TimeSeed]).time_seed(search);
+    let mut metadata = upload_metadata(content_type, &input, RunParameters::new(&config));
+    if let Err(message) = generators.and(window) {
+        return RunResponse::Rejected(Report::error(
+            metadata,
+            TestResult {
+                test_name: "Test de graine temporelle".to_string(),
+                passed: false,
+                score: 0.0,
+                details: format!("Erreur : {}", message),
+                thresholds: None,
+                ..Default::default()
+            },
+        ));
+    }
+    let reports = match read_uploaded_seeds(content_type, file, &input, &mut metadata).await {
+        Ok(reports) => reports,
+        Err(error) => return RunResponse::Rejected(Report::error(metadata, error)),
+    };
+
+    let threads = app.battery_threads;
+    RunResponse::Stored(
+        run_blocking(metadata, move |metadata| {
+            let sequences = map_ordered(&reports, threads, |report| {
+                SequenceReport::new(Some(report.clone()), test_stream(&report.stream, &config))
+            });
+            Report::new(metadata, sequences)
+        })
+        .await,
+    )
+}
+
+// Exécute un calcul de la batterie hors des threads asynchrones du serveur, qui restent disponibles
+// pour les autres requêtes pendant l'analyse ; `metadata` décrit l'exécution, y compris en cas d'échec
+async fn run_blocking<F>(metadata: RunMetadata, work: F) -> Report
+where
+    F: FnOnce(RunMetadata) -> Report + Send + 'static,
+{
+    let interrupted = metadata.clone();
+    rocket::tokio::task::spawn_blocking(move || work(metadata)).await.unwrap_or_else(|err| {
+        Report::error(
+            interrupted,
+            TestResult {
+                test_name: "Erreur".to_string(),
+                passed: false,
+                score: 0.0,
+                details: format!("Erreur : l'analyse a été interrompue ({})", err),
+                thresholds: None,
+                ..Default::default()
+            },
+        )
+    })
+}
+
+// Résultat d'erreur d'un paramètre de la requête invalide
+fn parameter_error(message: String) -> TestResult {
+    TestResult {
+        test_name: "Paramètre invalide".to_string(),
+        passed: false,
+        score: 0.0,
+        details: format!("Erreur : {}", message),
+        thresholds: None,
+        ..Default::default()
+    }
+}
+
+// Métadonnées de l'analyse d'un fichier envoyé : type du contenu, format de lecture et paramètres
+fn upload_metadata(content_type: &ContentType, input: &InputParams, mut parameters: RunParameters) -> RunMetadata {
+    let mut description = InputDescription::new(InputKind::Upload, &format!("fichier envoyé ({})", content_type));
+    // Sans format explicite, un contenu binaire est lu en binaire brut (voir `read_uploaded_seeds`)
+    if input.format.is_some() || *content_type != ContentType::Binary {
+        description.format = input.input_format().ok();
+    }
+    description.raw = input.raw_format().ok();
+    parameters.strict = input.strict.unwrap_or(false);
+    RunMetadata::new(description, parameters)
+}
+
+// Lit un fichier RNG envoyé au serveur (JSON, CSV, texte ou binaire brut) et en extrait les seeds,
+// avec le détail des lignes rejetées (refusées en mode strict) ; un flux par groupe pour `group_by`
+// L'empreinte du fichier lu est enregistrée dans `metadata`, y compris si sa lecture échoue ensuite
+async fn read_uploaded_seeds(
+    content_type: &ContentType,
+    file: Data<'_>,
+    input: &InputParams,
+    metadata: &mut RunMetadata,
+) -> Result<Vec<ParseReport>, TestResult> {
+    let read_error = |details: String| TestResult {
+        test_name: "Erreur".to_string(),
+        passed: false,
+        score: 0.0,
+        details,
+        thresholds: None,
+        ..Default::default()
+    };
+
+    let bytes = match file.open(2.mebibytes()).into_bytes().await {
+        Ok(capped) => capped.into_inner(),
+        Err(err) => {
+            eprintln!("Erreur lors de la lecture du fichier : {:?}", err);
+            return Err(read_error(format!("Erreur lors de la lecture du fichier : {:?}", err)));
+        }
+    };
+    let mut fingerprint = InputFingerprint::new();
+    fingerprint.update(&bytes);
+    metadata.input.sha256 = Some(fingerprint.finish());
+
+    let raw = input.raw_format().map_err(|message| read_error(format!("Erreur : {}", message)))?;
+    // Un format texte explicite l'emporte sur le type binaire (dump hexadécimal envoyé en octet-stream)
+    if *content_type == ContentType::Binary && input.format.is_none() {
+        return parse_raw(&bytes, &raw)
+            .map(|stream| vec![ParseReport::accepted(InputFormat::Auto, stream)])
+            .map_err(|message| read_error(format!("Erreur : {}", message)));
+    }
+
+    let format = input.input_format().map_err(|message| read_error(format!("Erreur : {}", message)))?;
+    let format = match format {
+        InputFormat::Auto if content_type.is_json() => InputFormat::Json,
+        InputFormat::Auto if !(content_type.is_plain() || content_type.is_csv()) => {
+            return Err(read_error("Type de fichier non supporté.".to_string()))
+        }
+        format => format,
+    };
+
+    let file_content = match String::from_utf8(bytes) {
+        Ok(content) => content,
+        Err(_) => {
+            return Err(read_error(
+                "Erreur : le fichier n'est pas du texte UTF-8 ; envoyez les données binaires en application/octet-stream."
+                    .to_string(),
+            ))
+        }
+    };
+
+    let delimited = input.delimited_format().map_err(|message| read_error(format!("Erreur : {}", message)))?;
+    let reports = parse_text_groups(&file_content, format, &raw, &delimited)
+        .map_err(|message| read_error(format!("Erreur : {}", message)))?;
+    if input.strict.unwrap_or(false) {
+        return reports
+            .into_iter()
+            .map(ParseReport::into_strict)
+            .collect::<Result<_, _>>()
+            .map_err(|message| read_error(format!("Erreur : {}", message)));
+    }
+    Ok(reports)
+}
+
+// Route publiant le schéma JSON des rapports renvoyés par les routes d'analyse
+#[get("/schema/report.json")]
+fn report_schema() -> (ContentType, &'static str) {
+    (ContentType::JSON, REPORT_SCHEMA)
+}
+
+// Erreur d'accès à l'historique des exécutions
+fn store_error(err: std::io::Error) -> (Status, String) {
+    eprintln!("Erreur d'accès à l'historique des exécutions : {}", err);
+    (Status::InternalServerError, format!("Erreur d'accès à l'historique des exécutions : {}", err))
+}
+
+// Exécution conservée : celle d'identifiant `run`, ou la dernière si `run` est absent
+fn stored_run(store: &RunStore, run: Option<&str>) -> Result<Report, (Status, String)> {
+    let report = match run {
+        Some(id) if !is_run_id(id) => return Err((Status::BadRequest, format!("Identifiant d'exécution invalide : {}", id))),
+        Some(id) => store.get(id),
+        None => store.latest(),
+    };
+    report.map_err(store_error)?.ok_or_else(|| {
+        let run = run.map_or("Aucune exécution".to_string(), |id| format!("Exécution {} inconnue", id));
+        (Status::NotFound, format!("{} dans l'historique.", run))
+    })
+}
+
+// Route pour afficher l'historique des exécutions
+#[get("/history")]
+fn history_page() -> Template {
+    let mut context = HashMap::new();
+    context.insert("current_year", json!(Local::now().year()));
+    context.insert("pdf_export_enabled", json!(cfg!(feature = "pdf_export")));
+
+    Template::render("history", &context)
+}
+
+// Route listant les exécutions conservées, de la plus récente à la plus ancienne
+#[get("/runs")]
+fn list_runs(store: &State<RunStore>) -> Result<Json<Vec<RunSummary>>, (Status, String)> {
+    store.list().map(Json).map_err(store_error)
+}
+
+// Route renvoyant le rapport d'une exécution conservée
+#[get("/runs/<id>")]
+fn get_run(id: &str, store: &State<RunStore>) -> Result<Json<Report>, (Status, String)> {
+    stored_run(store, Some(id)).map(Json)
+}
+
+// Route supprimant une exécution conservée
+#[delete("/runs/<id>")]
+fn delete_run(id: &str, store: &State<RunStore>) -> Result<Status, (Status, String)> {
+    if !is_run_id(id) {
+        return Err((Status::BadRequest, format!("Identifiant d'exécution invalide : {}", id)));
+    }
+    match store.delete(id).map_err(store_error)? {
+        true => Ok(Status::NoContent),
+        false => Err((Status::NotFound, format!("Exécution {} inconnue dans l'historique.", id))),
+    }
+}
+
+// Route renvoyant la politique de rétention de l'historique
+#[get("/runs/retention")]
+fn get_retention(store: &State<RunStore>) -> Result<Json<RetentionPolicy>, (Status, String)> {
+    store.retention().map(Json).map_err(store_error)
+}
+
+// Route enregistrant et appliquant une nouvelle politique de rétention ; renvoie les exécutions supprimées
+#[put("/runs/retention", data = "<policy>")]
+fn set_retention(policy: Json<RetentionPolicy>, store: &State<RunStore>) -> Result<Json<Vec<String>>, (Status, String)> {
+    store.set_retention(policy.into_inner()).map(Json).map_err(store_error)
+}
+
+// Route appliquant la politique de rétention enregistrée ; renvoie les exécutions supprimées
+#[post("/runs/prune")]
+fn prune_runs(store: &State<RunStore>) -> Result<Json<Vec<String>>, (Status, String)> {
+    let policy = store.retention().map_err(store_error)?;
+    store.prune(&policy).map(Json).map_err(store_error)
+}
+
+// Route pour exporter les résultats d'une exécution en PDF
+#[cfg(feature = "pdf_export")]
+#[get("/export_pdf?<run>")]
+fn export_pdf(run: Option<&str>, store: &State<RunStore>) -> Result<Export, (Status, String)> {
+    let report = stored_run(store, run)?;
+    match generate_pdf_report(&report) {
+        Ok(pdf_data) => Ok(Export::new(pdf_data, ContentType::PDF, &report, "pdf")),
+        Err(err) => {
+            eprintln!("Erreur lors de la génération du PDF : {:?}", err);
+            Err((Status::InternalServerError, "Impossible de générer le fichier PDF.".to_string()))
+        }
+    }
+}
+
+// Route pour exporter les résultats d'une exécution en CSV
+#[get("/export_csv?<run>")]
+fn export_csv(run: Option<&str>, store: &State<RunStore>) -> Result<Export, (Status, String)> {
+    let report = stored_run(store, run)?;
+    match generate_csv_report(&report) {
+        Ok(data) => Ok(Export::new(data, ContentType::CSV, &report, "csv")),
+        Err(err) => {
+            eprintln!("Erreur lors de la génération du CSV : {:?}", err);
+            Err((Status::InternalServerError, "Impossible de générer le fichier CSV.".to_string()))
+        }
+    }
+}
+
+// Route pour exporter le rapport JSON d'une exécution (schéma : /schema/report.json)
+#[get("/export_json?<run>")]
+fn export_json(run: Option<&str>, store: &State<RunStore>) -> Result<Export, (Status, String)> {
+    let report = stored_run(store, run)?;
+    let data = serde_json::to_vec_pretty(&report).map_err(|err| (Status::InternalServerError, err.to_string()))?;
+    Ok(Export::new(data, ContentType::JSON, &report, "json"))
+}
+
+// Liste des générateurs de référence pour les formulaires
+fn reference_generators_context() -> serde_json::Value {
+    ReferenceGenerator::ALL
+        .iter()
+        .map(|generator| {
+            json!({
+                "id": generator.id(),
+                "name": generator.name(),
+                "expected_to_pass": generator.expected_to_pass(),
+            })
+        })
+        .collect()
+}
+
+// Chargement des templates
+fn force_load_templates() -> Tera {
+    let mut tera = Tera::default();
+    tera.add_template_files(vec![
+        ("templates/layout_base.html.tera", Some("layout_base")),
+        ("templates/index.html.tera", Some("index")),
+        ("templates/config.html.tera", Some("config")),
+        ("templates/run_tests.html.tera", Some("run_tests")),
+        ("templates/compare.html.tera", Some("compare")),
+        ("templates/history.html.tera", Some("history")),
+    ])
+    .expect("Erreur de chargement des templates.");
+
+    tera.autoescape_on(vec!["html.tera"]);
+    tera
+}
+
+// Application Rocket, lancée par la sous-commande `serve`
+pub fn rocket() -> rocket::Rocket<rocket::Build> {
+    let app = rocket::build()
+        .attach(AdHoc::try_on_ignite("Historique des exécutions", |rocket| async {
+            let data_dir = rocket.figment().extract::<AppConfig>().map_or_else(|_| default_data_dir(), |config| config.data_dir);
+            match RunStore::open(&data_dir) {
+                Ok(store) => Ok(rocket.manage(store)),
+                Err(err) => {
+                    eprintln!("Impossible d'ouvrir l'historique des exécutions ({}) : {}", data_dir.display(), err);
+                    Err(rocket)
+                }
+            }
+        }))
+        .attach(Template::custom(|engines| {
+            engines.tera = force_load_templates();
+        }))
+        .attach(AdHoc::config::<AppConfig>())
+        .mount(
+            "/",
+            routes![
+                index_page,
+                config_page,
+                run_tests_page,
+                run_tests_route,
+                rerun_route,
+                upload_file,
+                upload_stream,
+                time_seed_route,
+                report_schema,
+                compare_page,
+                compare_route,
+                compare_runs,
+                export_csv,
+                export_json,
+                history_page,
+                list_runs,
+                get_run,
+                delete_run,
+                get_retention,
+                set_retention,
+                prune_runs,
+            ],
+        )
+        .mount("/static", rocket::fs::FileServer::from("./static"));
+
+    #[cfg(feature = "pdf_export")]
+    let app = app.mount("/", routes![export_pdf]);
+
+    app
+}
@@ -0,0 +1,373 @@
+//! Historique des exécutions : chaque rapport enregistré reçoit un identifiant (`metadata.id`) et est
+//! conservé dans un répertoire de données, partagé par le serveur et la ligne de commande.
+//!
+//! Disposition du répertoire : `runs/<id>.json` (un rapport par exécution, voir `report::Report`) et
+//! `retention.json` (politique de rétention, appliquée à chaque enregistrement).
+
+use crate::battery::BatteryTest;
+use crate::report::{align_columns, InputKind, Report};
+use crate::types::Verdict;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Répertoire de données par défaut, relatif au répertoire courant.
+pub const DEFAULT_DATA_DIR: &str = "data";
+
+/// Nombre d'exécutions conservées par défaut.
+pub const DEFAULT_RETAINED_RUNS: usize = 100;
+
+// Sous-répertoire des rapports
+const RUNS_DIR: &str = "runs";
+// Fichier de la politique de rétention
+const RETENTION_FILE: &str = "retention.json";
+// Longueur d'un identifiant d'exécution (chiffres hexadécimaux)
+const RUN_ID_LEN: usize = 16;
+
+/// Nouvel identifiant d'exécution : 16 chiffres hexadécimaux aléatoires.
+pub fn new_run_id() -> String {
+    format!("{:0width$x}", rand::random::<u64>(), width = RUN_ID_LEN)
+}
+
+/// Vrai si `id` a la forme d'un identifiant d'exécution (16 chiffres hexadécimaux minuscules).
+pub fn is_run_id(id: &str) -> bool {
+    id.len() == RUN_ID_LEN && id.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Politique de rétention : les exécutions au-delà de `max_runs` (les plus anciennes d'abord) ou plus
+/// vieilles que `max_age_days` jours sont supprimées ; 0 lève la limite correspondante.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub max_runs: usize,
+    pub max_age_days: u64,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy { max_runs: DEFAULT_RETAINED_RUNS, max_age_days: 0 }
+    }
+}
+
+impl RetentionPolicy {
+    /// Description lisible (ex. « 100 exécution(s) au plus, sans limite d'âge »).
+    pub fn describe(&self) -> String {
+        let runs = match self.max_runs {
+            0 => "nombre d'exécutions illimité".to_string(),
+            max => format!("{} exécution(s) au plus", max),
+        };
+        let age = match self.max_age_days {
+            0 => "sans limite d'âge".to_string(),
+            days => format!("{} jour(s) au plus", days),
+        };
+        format!("{}, {}", runs, age)
+    }
+}
+
+/// Résumé d'une exécution conservée, pour les listes.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RunSummary {
+    pub id: String,
+    pub started_at: String,
+    pub kind: InputKind,
+    /// Description de l'entrée analysée.
+    pub description: String,
+    /// Empreinte SHA-256 des données analysées.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    pub tool_version: String,
+    pub num_values: usize,
+    /// Tests demandés.
+    pub tests: Vec<BatteryTest>,
+    pub verdict: Verdict,
+    pub passed: usize,
+    pub weak: usize,
+    pub failed: usize,
+}
+
+impl RunSummary {
+    fn new(id: &str, report: &Report) -> Self {
+        let metadata = &report.metadata;
+        RunSummary {
+            id: id.to_string(),
+            started_at: metadata.started_at.clone(),
+            kind: metadata.input.kind,
+            description: metadata.input.description.clone(),
+            sha256: metadata.input.sha256.clone(),
+            tool_version: report.tool.version.clone(),
+            num_values: metadata.num_values,
+            tests: metadata.parameters.tests.clone(),
+            verdict: report.summary.verdict,
+            passed: report.summary.passed,
+            weak: report.summary.weak,
+            failed: report.summary.failed,
+        }
+    }
+}
+
+/// Historique des exécutions dans un répertoire de données.
+///
+/// # Remarques
+/// * Les écritures d'un même processus sont sérialisées ; un rapport est écrit dans un fichier temporaire
+///   puis renommé, si bien qu'un autre processus (serveur et ligne de commande) ne lit jamais un rapport
+///   partiel.
+/// * Les fichiers illisibles du répertoire `runs` sont ignorés par `list`.
+#[derive(Debug)]
+pub struct RunStore {
+    dir: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl RunStore {
+    /// Ouvre l'historique du répertoire `dir`, créé s'il n'existe pas.
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(dir.join(RUNS_DIR))?;
+        Ok(RunStore { dir, lock: Mutex::new(()) })
+    }
+
+    /// Répertoire de données.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Politique de rétention enregistrée (`RetentionPolicy::default()` si aucune).
+    pub fn retention(&self) -> io::Result<RetentionPolicy> {
+        match fs::read_to_string(self.dir.join(RETENTION_FILE)) {
+            Ok(json) => serde_json::from_str(&json).map_err(|err| io::Error::new(ErrorKind::InvalidData, err)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(RetentionPolicy::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Enregistre la politique de rétention et l'applique.
+    ///
+    /// Retourne : Les identifiants des exécutions supprimées.
+    pub fn set_retention(&self, policy: RetentionPolicy) -> io::Result<Vec<String>> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let json = serde_json::to_vec_pretty(&policy).map_err(io::Error::other)?;
+        self.write_atomic(&self.dir.join(RETENTION_FILE), &json)?;
+        self.prune_locked(&policy)
+    }
+
+    /// Enregistre une exécution sous un nouvel identifiant, puis applique la politique de rétention.
+    ///
+    /// Retourne : Le rapport, avec son identifiant dans `metadata.id`.
+    pub fn insert(&self, mut report: Report) -> io::Result<Report> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        // Lue sous le verrou, pour ne pas appliquer une politique remplacée entre-temps par `set_retention`
+        let policy = self.retention()?;
+        let id = new_run_id();
+        report.metadata.id = Some(id.clone());
+        let json = serde_json::to_vec_pretty(&report).map_err(io::Error::other)?;
+        self.write_atomic(&self.run_path(&id), &json)?;
+        self.prune_locked(&policy)?;
+        Ok(report)
+    }
+
+    /// Exécution d'identifiant `id`, si elle est conservée.
+    pub fn get(&self, id: &str) -> io::Result<Option<Report>> {
+        if !is_run_id(id) {
+            return Ok(None);
+        }
+        match fs::read(self.run_path(id)) {
+            Ok(json) => serde_json::from_slice(&json).map(Some).map_err(|err| io::Error::new(ErrorKind::InvalidData, err)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Dernière exécution enregistrée.
+    pub fn latest(&self) -> io::Result<Option<Report>> {
+        match self.list()?.first() {
+            Some(summary) => self.get(&summary.id),
+            None => Ok(None),
+        }
+    }
+
+    /// Exécutions conservées, de la plus récente à la plus ancienne.
+    pub fn list(&self) -> io::Result<Vec<RunSummary>> {
+        let mut runs = Vec::new();
+        for entry in fs::read_dir(self.dir.join(RUNS_DIR))? {
+            let path = entry?.path();
+            let id = match path.file_name().and_then(|name| name.to_str()).and_then(|name| name.strip_suffix(".json")) {
+                Some(id) if is_run_id(id) => id.to_string(),
+                _ => continue,
+            };
+            if let Ok(Some(report)) = self.get(&id) {
+                runs.push(RunSummary::new(&id, &report));
+            }
+        }
+        runs.sort_by(|a, b| b.started_at.cmp(&a.started_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(runs)
+    }
+
+    /// Supprime l'exécution `id`.
+    ///
+    /// Retourne : Vrai si elle était conservée.
+    pub fn delete(&self, id: &str) -> io::Result<bool> {
+        if !is_run_id(id) {
+            return Ok(false);
+        }
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match fs::remove_file(self.run_path(id)) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Applique `policy` sans l'enregistrer.
+    ///
+    /// Retourne : Les identifiants des exécutions supprimées.
+    pub fn prune(&self, policy: &RetentionPolicy) -> io::Result<Vec<String>> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.prune_locked(policy)
+    }
+
+    // Applique `policy`, le verrou étant tenu
+    fn prune_locked(&self, policy: &RetentionPolicy) -> io::Result<Vec<String>> {
+        let oldest = (policy.max_age_days > 0).then(|| Utc::now() - Duration::days(policy.max_age_days as i64));
+        let mut removed = Vec::new();
+        for (rank, run) in self.list()?.into_iter().enumerate() {
+            let too_many = policy.max_runs > 0 && rank >= policy.max_runs;
+            let too_old = oldest.is_some_and(|oldest| {
+                DateTime::parse_from_rfc3339(&run.started_at).is_ok_and(|started| started < oldest)
+            });
+            if too_many || too_old {
+                // Un autre processus (serveur ou ligne de commande) a pu la supprimer entre-temps
+                match fs::remove_file(self.run_path(&run.id)) {
+                    Ok(()) => removed.push(run.id),
+                    Err(err) if err.kind() == ErrorKind::NotFound => {}
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    fn run_path(&self, id: &str) -> PathBuf {
+        self.dir.join(RUNS_DIR).join(format!("{}.json", id))
+    }
+
+    // Écrit `data` dans un fichier temporaire renommé ensuite en `path`
+    fn write_atomic(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let temporary = path.with_extension("tmp");
+        fs::write(&temporary, data)?;
+        fs::rename(&temporary, path)
+    }
+}
+
+// Caractères de l'empreinte affichés dans la liste
+const FINGERPRINT_PREFIX_LEN: usize = 12;
+
+/// Met en forme la liste des exécutions conservées : une ligne par exécution, puis la politique de
+/// rétention.
+pub fn format_history(runs: &[RunSummary], policy: &RetentionPolicy) -> String {
+    let rows: Vec<[String; 6]> = runs
+        .iter()
+        .map(|run| {
+            [
+                run.id.clone(),
+                run.started_at.clone(),
+                run.verdict.label().to_string(),
+                format!("{}/{}/{}", run.passed, run.weak, run.failed),
+                run.sha256.as_deref().map_or("-".to_string(), |sha256| {
+                    sha256.chars().take(FINGERPRINT_PREFIX_LEN).collect()
+                }),
+                run.description.clone(),
+            ]
+        })
+        .collect();
+    let mut lines = if runs.is_empty() {
+        vec!["Aucune exécution dans l'historique.".to_string()]
+    } else {
+        align_columns(["Exécution", "Début", "Verdict", "OK/FAIBLE/ÉCHEC", "Empreinte", "Entrée"], &rows)
+    };
+    lines.push(String::new());
+    lines.push(format!("{} exécution(s) conservée(s) ; rétention : {}.", runs.len(), policy.describe()));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{InputDescription, RunMetadata, RunParameters};
+    use crate::types::TestResult;
+
+    fn report(description: &str, started_at: &str) -> Report {
+        let mut metadata =
+            RunMetadata::new(InputDescription::new(InputKind::Upload, description), RunParameters::default());
+        metadata.input.sha256 = Some(crate::report::InputFingerprint::of_values(&[1, 2, 3]));
+        let mut report = Report::error(metadata, TestResult { test_name: "Erreur".to_string(), ..Default::default() });
+        report.metadata.started_at = started_at.to_string();
+        report
+    }
+
+    fn temporary_store(name: &str) -> RunStore {
+        let dir = std::env::temp_dir().join(format!("seed_tester_store_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        RunStore::open(dir).unwrap()
+    }
+
+    #[test]
+    fn test_store_saves_lists_and_deletes_runs() {
+        let store = temporary_store("runs");
+        assert!(store.latest().unwrap().is_none());
+        let first = store.insert(report("a", "2026-01-01T00:00:00.000Z")).unwrap();
+        let id = first.metadata.id.clone().unwrap();
+        assert!(is_run_id(&id), "{}", id);
+        let stored = store.get(&id).unwrap().unwrap();
+        assert_eq!(serde_json::to_string(&stored).unwrap(), serde_json::to_string(&first).unwrap());
+
+        let second = store.insert(report("b", "2026-01-02T00:00:00.000Z")).unwrap();
+        let second_id = second.metadata.id.clone().unwrap();
+        assert_ne!(second_id, id);
+        let runs = store.list().unwrap();
+        let descriptions: Vec<&str> = runs.iter().map(|run| run.description.as_str()).collect();
+        assert_eq!(descriptions, ["b", "a"]);
+        assert_eq!(runs[0].sha256.as_ref().map(String::len), Some(64));
+        assert_eq!(store.latest().unwrap().unwrap().metadata.id, Some(second_id.clone()));
+        let table = format_history(&runs, &RetentionPolicy::default());
+        assert!(table.lines().nth(2).unwrap().starts_with(&second_id), "{}", table);
+        assert!(table.ends_with("2 exécution(s) conservée(s) ; rétention : 100 exécution(s) au plus, sans limite d'âge."));
+
+        // Un autre processus (même répertoire) voit les mêmes exécutions
+        assert_eq!(RunStore::open(store.dir()).unwrap().list().unwrap(), runs);
+
+        assert!(store.delete(&second_id).unwrap());
+        assert!(!store.delete(&second_id).unwrap());
+        assert!(store.get(&second_id).unwrap().is_none());
+        assert!(store.get("../retention").unwrap().is_none());
+        assert!(!is_run_id("0123456789ABCDEF"));
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_retention_policy_prunes_oldest_runs() {
+        let store = temporary_store("retention");
+        assert_eq!(store.retention().unwrap(), RetentionPolicy::default());
+        let now = Utc::now();
+        let days_ago = |days| (now - Duration::days(days)).to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        for (description, age) in [("ancienne", 40), ("récente", 2), ("moyenne", 10)] {
+            store.insert(report(description, &days_ago(age))).unwrap();
+        }
+
+        let removed = store.prune(&RetentionPolicy { max_runs: 0, max_age_days: 30 }).unwrap();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(store.list().unwrap().len(), 2);
+
+        let policy = RetentionPolicy { max_runs: 1, max_age_days: 0 };
+        assert_eq!(store.set_retention(policy).unwrap().len(), 1);
+        assert_eq!(store.retention().unwrap(), policy);
+        assert_eq!(store.list().unwrap()[0].description, "récente");
+        // La politique enregistrée s'applique à chaque nouvelle exécution
+        store.insert(report("nouvelle", &days_ago(0))).unwrap();
+        let runs = store.list().unwrap();
+        assert_eq!((runs.len(), runs[0].description.as_str()), (1, "nouvelle"));
+        assert_eq!(policy.describe(), "1 exécution(s) au plus, sans limite d'âge");
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+}
@@ -0,0 +1,364 @@
+//! Analyse en flux d'entrées plus grandes que la mémoire (captures de plusieurs gigaoctets lues sur
+//! disque, sur l'entrée standard ou dans le corps d'une requête).
+//!
+//! ```no_run
+//! use seed_tester::battery::BatteryConfig;
+//! use seed_tester::streaming::{StreamInput, StreamReader, StreamingBattery};
+//! use seed_tester::tests::RawFormat;
+//!
+//! let file = std::fs::File::open("capture.bin").unwrap();
+//! let battery = StreamingBattery::new(BatteryConfig::default());
+//! let analysis = StreamReader::new(StreamInput::Raw(RawFormat::default()), battery).read_from(file).unwrap();
+//! println!("{} valeurs, {} échec(s)", analysis.report.num_values, analysis.report.failed);
+//! ```
+
+use crate::battery::{BatteryConfig, BatteryReport, BatteryTest, StreamSupport};
+use crate::parallel::map_ordered;
+use crate::runner::CRATE_VERSION;
+use crate::tests::{parse_raw, BitStream, InputFormat, ParseReport, RawFormat, StreamAccumulator, ValueEncoding};
+use crate::types::TestResult;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+
+// Nombre de valeurs conservées par défaut pour les tests sur fenêtre (8 Mio)
+const DEFAULT_WINDOW_VALUES: usize = 1 << 20;
+// Taille des lectures d'un `Read`, en octets
+const READ_BUFFER_BYTES: usize = 1 << 16;
+// Longueur maximale d'une ligne décimale, en octets (une valeur de 64 bits s'écrit en 20 chiffres)
+const MAX_LINE_BYTES: usize = 4096;
+
+/// Batterie alimentée morceau par morceau, en mémoire bornée.
+///
+/// # Remarques
+/// * Les tests `StreamSupport::Streaming` voient tout le flux et donnent le même résultat que la batterie
+///   sur l'entrée complète ; les tests `StreamSupport::Window` ne voient que les `window` premières valeurs,
+///   ce que précisent leurs détails quand le flux est plus long.
+/// * La mémoire utilisée est celle de la fenêtre et des compteurs, quelle que soit la taille du flux.
+pub struct StreamingBattery {
+    config: BatteryConfig,
+    window: usize,
+    accumulators: Vec<Option<Box<dyn StreamAccumulator>>>,
+    kept: BitStream,
+    num_bits: usize,
+    sample_bits: u32,
+}
+
+impl StreamingBattery {
+    /// Batterie sur les tests de `config`, avec une fenêtre de 2^20 valeurs.
+    pub fn new(config: BatteryConfig) -> Self {
+        let accumulators = config.tests.iter().map(|test| test.accumulator(&config)).collect();
+        StreamingBattery {
+            config,
+            window: DEFAULT_WINDOW_VALUES,
+            accumulators,
+            kept: BitStream::default(),
+            num_bits: 0,
+            sample_bits: 64,
+        }
+    }
+
+    /// Fixe le nombre de valeurs de 64 bits conservées pour les tests sur fenêtre.
+    pub fn window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Nombre de valeurs conservées pour les tests sur fenêtre.
+    pub fn window_len(&self) -> usize {
+        self.window
+    }
+
+    /// Ajoute le morceau suivant du flux, fait d'échantillons complets de la largeur du flux (64 bits, ou
+    /// la taille des mots du format binaire lu par `StreamReader`).
+    pub fn update(&mut self, chunk: &BitStream) {
+        if chunk.is_empty() {
+            return;
+        }
+        for accumulator in self.accumulators.iter_mut().flatten() {
+            accumulator.update(chunk);
+        }
+        if self.config.tests.iter().any(|test| test.stream_support() == StreamSupport::Window) {
+            if self.kept.is_empty() {
+                self.kept = BitStream::new(self.sample_bits);
+            }
+            let room = self.window.saturating_mul(64).saturating_sub(self.kept.len_bits());
+            if room > 0 {
+                self.kept.append(&chunk.prefix(room));
+            }
+        }
+        self.num_bits += chunk.len_bits();
+    }
+
+    /// Nombre de valeurs de 64 bits reçues.
+    pub fn num_values(&self) -> usize {
+        self.num_bits / 64
+    }
+
+    /// Résultats de la batterie sur tout le flux reçu ; les tests sont terminés sur `config.threads` threads.
+    pub fn finish(&self) -> BatteryReport {
+        let truncated = self.num_bits > self.kept.len_bits();
+        let tests: Vec<(&BatteryTest, &Option<Box<dyn StreamAccumulator>>)> =
+            self.config.tests.iter().zip(&self.accumulators).collect();
+        let results: Vec<TestResult> = map_ordered(&tests, self.config.threads, |&(test, accumulator)| {
+            match accumulator {
+                Some(accumulator) => vec![accumulator.finish()],
+                None => self.run_window(test, truncated),
+            }
+        })
+        .concat();
+        let passed = results.iter().filter(|result| result.passed).count();
+        BatteryReport {
+            version: CRATE_VERSION.to_string(),
+            num_values: self.num_values(),
+            num_bits: self.num_bits,
+            sample_bits: self.sample_bits,
+            passed,
+            failed: results.len() - passed,
+            results,
+        }
+    }
+
+    /// Exécute un test sur fenêtre, en signalant la fenêtre si le flux est plus long.
+    fn run_window(&self, test: &BatteryTest, truncated: bool) -> Vec<TestResult> {
+        let mut results = test.run_stream(&self.kept, &self.config);
+        if truncated {
+            for result in &mut results {
+                result.details.push_str(&format!(
+                    " (fenêtre : {} premières valeurs sur {})",
+                    self.kept.len_bits() / 64,
+                    self.num_values()
+                ));
+            }
+        }
+        results
+    }
+}
+
+/// Format d'une entrée lue en flux.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamInput {
+    /// Binaire brut (voir `parse_raw`).
+    Raw(RawFormat),
+    /// Entiers décimaux, un par ligne (voir `parse_decimal`).
+    Decimal,
+}
+
+/// Résultat d'une analyse en flux.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StreamAnalysis {
+    /// Rapport de la batterie.
+    pub report: BatteryReport,
+    /// Rapport de lecture (lignes rejetées du format décimal) ; les valeurs n'y sont pas conservées.
+    pub parse: ParseReport,
+}
+
+/// Décode une entrée reçue par morceaux d'octets quelconques et alimente une `StreamingBattery`.
+///
+/// # Remarques
+/// * Une valeur binaire ou une ligne décimale peut chevaucher deux morceaux : seuls les octets d'une
+///   valeur ou d'une ligne incomplète sont conservés entre deux appels à `feed`.
+/// * Une ligne décimale de plus de `MAX_LINE_BYTES` octets est refusée plutôt que conservée sans limite.
+pub struct StreamReader {
+    input: StreamInput,
+    battery: StreamingBattery,
+    pending: Vec<u8>,
+    parse: ParseReport,
+    line: usize,
+}
+
+impl StreamReader {
+    /// Lecteur d'une entrée au format `input` ; les échantillons du flux ont la taille des mots du format
+    /// binaire, ou 64 bits pour le décimal.
+    pub fn new(input: StreamInput, mut battery: StreamingBattery) -> Self {
+        let format = match input {
+            StreamInput::Raw(_) => InputFormat::Auto,
+            StreamInput::Decimal => InputFormat::Decimal,
+        };
+        if let StreamInput::Raw(raw) = input {
+            // Un format invalide est refusé par `parse_raw` à la première lecture
+            if let Ok(raw) = RawFormat::new(raw.word_bits, raw.endianness, raw.bit_order) {
+                battery.sample_bits = raw.word_bits;
+            }
+        }
+        StreamReader {
+            input,
+            battery,
+            pending: Vec::new(),
+            parse: ParseReport { format, ..Default::default() },
+            line: 0,
+        }
+    }
+
+    /// Ajoute les octets suivants de l'entrée.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<(), String> {
+        self.pending.extend_from_slice(bytes);
+        match self.input {
+            StreamInput::Raw(format) => {
+                let word_bytes = (format.word_bits / 8).max(1) as usize;
+                let aligned = self.pending.len() / word_bytes * word_bytes;
+                let chunk = parse_raw(&self.pending[..aligned], &format)?;
+                self.pending.drain(..aligned);
+                self.parse.accepted += chunk.len_samples();
+                self.battery.update(&chunk);
+            }
+            StreamInput::Decimal => {
+                if let Some(end) = self.pending.iter().rposition(|&byte| byte == b'\n') {
+                    let lines: Vec<u8> = self.pending.drain(..=end).collect();
+                    self.read_lines(&lines[..end]);
+                }
+                // Une entrée sans fin de ligne serait sinon conservée en entier
+                if self.pending.len() > MAX_LINE_BYTES {
+                    return Err(format!(
+                        "Ligne {} : plus de {} octets sans fin de ligne (une valeur décimale par ligne).",
+                        self.line + 1,
+                        MAX_LINE_BYTES
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Lit des lignes décimales complètes.
+    fn read_lines(&mut self, bytes: &[u8]) {
+        let mut values = Vec::new();
+        for line in bytes.split(|&byte| byte == b'\n') {
+            self.line += 1;
+            let Ok(line) = std::str::from_utf8(line) else {
+                self.parse.reject(self.line, "", "n'est pas du texte UTF-8".to_string());
+                continue;
+            };
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            match ValueEncoding::Unsigned.parse(trimmed) {
+                Ok(value) => values.push(value),
+                Err(reason) => self.parse.reject(self.line, trimmed, reason),
+            }
+        }
+        self.parse.accepted += values.len();
+        self.battery.update(&BitStream::from_u64s(&values));
+    }
+
+    /// Termine la lecture et exécute la batterie.
+    ///
+    /// Retourne : L'analyse, ou une erreur si l'entrée binaire se termine par une valeur incomplète.
+    pub fn finish(mut self) -> Result<StreamAnalysis, String> {
+        let pending = std::mem::take(&mut self.pending);
+        match self.input {
+            StreamInput::Raw(format) if !pending.is_empty() => {
+                return Err(format!(
+                    "{} octet(s) en fin de flux ne forment pas un mot de {} bits complet.",
+                    pending.len(),
+                    format.word_bits
+                ))
+            }
+            StreamInput::Raw(_) => {}
+            StreamInput::Decimal => self.read_lines(&pending),
+        }
+        Ok(StreamAnalysis { report: self.battery.finish(), parse: self.parse })
+    }
+
+    /// Lit toute l'entrée de `reader` (fichier, entrée standard) puis exécute la batterie.
+    pub fn read_from<R: Read>(mut self, mut reader: R) -> Result<StreamAnalysis, String> {
+        let mut buffer = vec![0u8; READ_BUFFER_BYTES];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => self.feed(&buffer[..read])?,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(format!("Erreur de lecture : {}", err)),
+            }
+        }
+        self.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battery::{run_tests, test_stream};
+    use crate::tests::{BitOrder, Endianness};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::RngCore;
+
+    #[test]
+    fn test_streaming_matches_batch() {
+        let mut rng = ChaCha20Rng::seed_from_u64(11);
+        let values: Vec<u64> = (0..3000).map(|_| rng.next_u64()).collect();
+        let config = BatteryConfig::default().without(BatteryTest::TimeSeed);
+        let expected = run_tests(&values, &config);
+
+        // Morceaux d'octets de taille quelconque : les valeurs chevauchent les morceaux
+        let bytes: Vec<u8> = values.iter().flat_map(|value| value.to_le_bytes()).collect();
+        let battery = StreamingBattery::new(config.clone());
+        let analysis = StreamReader::new(StreamInput::Raw(RawFormat::default()), battery)
+            .read_from(&bytes[..])
+            .unwrap();
+        let expected = serde_json::to_string(&expected.results).unwrap();
+        assert_eq!(serde_json::to_string(&analysis.report.results).unwrap(), expected);
+        assert_eq!((analysis.report.num_values, analysis.parse.accepted), (3000, 3000));
+
+        let raw = StreamInput::Raw(RawFormat::default());
+        let mut reader = StreamReader::new(raw, StreamingBattery::new(config.clone()));
+        for chunk in bytes.chunks(13) {
+            reader.feed(chunk).unwrap();
+        }
+        let report = reader.finish().unwrap().report;
+        assert_eq!(serde_json::to_string(&report.results).unwrap(), expected);
+
+        let mut reader = StreamReader::new(raw, StreamingBattery::new(config.clone()));
+        reader.feed(&bytes[..12]).unwrap();
+        assert!(reader.finish().unwrap_err().contains("4 octet(s)"));
+
+        // Octets lus comme des échantillons de 8 bits, sans former de valeurs de 64 bits
+        let bytes8 = StreamInput::Raw(RawFormat::new(8, Endianness::Little, BitOrder::MsbFirst).unwrap());
+        let mut reader = StreamReader::new(bytes8, StreamingBattery::new(config.clone()));
+        for chunk in bytes[..1003].chunks(13) {
+            reader.feed(chunk).unwrap();
+        }
+        let analysis = reader.finish().unwrap();
+        let expected = test_stream(&BitStream::from_bytes(&bytes[..1003]), &config);
+        assert_eq!((analysis.report.num_bits, analysis.report.sample_bits, analysis.parse.accepted), (8024, 8, 1003));
+        assert_eq!(
+            serde_json::to_string(&analysis.report.results).unwrap(),
+            serde_json::to_string(&expected.results).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_window_tests_see_bounded_prefix() {
+        let mut rng = ChaCha20Rng::seed_from_u64(5);
+        let values: Vec<u64> = (0..2000).map(|_| rng.next_u64()).collect();
+        let config = BatteryConfig::default().tests(&[BatteryTest::Frequency, BatteryTest::StateRecovery]);
+        assert_eq!(BatteryTest::Frequency.stream_support(), StreamSupport::Streaming);
+        assert_eq!(BatteryTest::StateRecovery.stream_support(), StreamSupport::Window);
+
+        let text: String = values.iter().map(|value| format!("{}\n", value)).collect::<String>() + "x\n12";
+        let mut reader = StreamReader::new(StreamInput::Decimal, StreamingBattery::new(config.clone()).window(500));
+        for chunk in text.as_bytes().chunks(7) {
+            reader.feed(chunk).unwrap();
+        }
+        let analysis = reader.finish().unwrap();
+        assert_eq!((analysis.parse.accepted, analysis.parse.rejected), (2001, 1));
+        assert_eq!(analysis.parse.rejections[0].line, 2001);
+
+        let mut all = values.clone();
+        all.push(12);
+        let frequency = &run_tests(&all, &config).results[0];
+        assert_eq!(analysis.report.results[0].score, frequency.score);
+        let window = &analysis.report.results[1];
+        assert_eq!(window.score, run_tests(&values[..500], &config).results[1].score);
+        assert!(window.details.contains("500 premières valeurs sur 2001"), "{}", window.details);
+        assert_eq!(analysis.report.sample_bits, 64);
+
+        // Une ligne sans fin n'est pas conservée au-delà de la limite
+        let mut reader = StreamReader::new(StreamInput::Decimal, StreamingBattery::new(config));
+        reader.feed(b"12\n").unwrap();
+        let error = (0..10).find_map(|_| reader.feed(&[b'1'; 1000]).err()).unwrap();
+        assert!(error.starts_with("Ligne 2 : plus de 4096 octets"), "{}", error);
+    }
+}
@@ -0,0 +1,226 @@
+//! Assertions de qualité pour les suites `cargo test` des crates utilisateurs.
+//!
+//! ```no_run
+//! use seed_tester::assert_random;
+//! use seed_tester::battery::BatteryTest;
+//! use rand::SeedableRng;
+//!
+//! let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(1);
+//! assert_random!(rng);
+//! assert_random!(rng, alpha(0.001), tests(&[BatteryTest::Autocorrelation, BatteryTest::StateRecovery]));
+//! ```
+
+use crate::battery::{BatteryConfig, BatteryTest};
+use crate::types::TestResult;
+use rand_core::RngCore;
+use std::fmt;
+
+// Seuil de rejet par défaut des résultats munis d'une p-value
+const DEFAULT_ALPHA: f64 = 0.01;
+// Nombre de tentatives par défaut : un test doit échouer à chacune pour que l'assertion échoue
+const DEFAULT_ATTEMPTS: usize = 5;
+
+/// Assertion « ce générateur passe la batterie », construite par méthodes chaînées.
+///
+/// # Remarques
+/// * Un résultat muni d'une p-value est rejeté si elle est inférieure à `alpha` (ou supérieure à
+///   `1 - alpha` quand le test échoue, pour les tests bilatéraux comme le chi-square ENT) ; les autres
+///   résultats gardent leurs propres seuils.
+/// * Chaque test rejeté est rejoué sur de nouvelles valeurs tirées du même générateur : l'assertion
+///   n'échoue que si le même test est rejeté à chacune des `attempts` tentatives. Pour un générateur sain,
+///   la probabilité de rejet d'un test (de l'ordre de `alpha`) est ainsi élevée à la puissance `attempts`,
+///   alors qu'un défaut structurel (état reconstruit, cycle) est rejeté à chaque tentative.
+/// * Par défaut, toute la batterie sur 1000 valeurs, sauf la recherche de graine temporelle (dépendante
+///   de l'heure), le test de blocs, dont le seuil fixe rejette toujours des blocs de 8 bits sains, et le
+///   test de poker, dont les seuils fixes rejettent environ un tiers des échantillons sains.
+#[derive(Debug, Clone)]
+pub struct RandomnessAssertion {
+    config: BatteryConfig,
+    alpha: f64,
+    attempts: usize,
+}
+
+impl Default for RandomnessAssertion {
+    fn default() -> Self {
+        RandomnessAssertion {
+            config: BatteryConfig::default()
+                .without(BatteryTest::TimeSeed)
+                .without(BatteryTest::Blocks)
+                .without(BatteryTest::Poker),
+            alpha: DEFAULT_ALPHA,
+            attempts: DEFAULT_ATTEMPTS,
+        }
+    }
+}
+
+impl RandomnessAssertion {
+    /// Assertion par défaut (voir les remarques du type).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remplace la configuration de la batterie.
+    pub fn config(mut self, config: BatteryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Restreint la batterie aux tests donnés.
+    pub fn tests(mut self, tests: &[BatteryTest]) -> Self {
+        self.config = self.config.tests(tests);
+        self
+    }
+
+    /// Nombre de valeurs de 64 bits tirées à chaque tentative.
+    pub fn num_values(mut self, num_values: usize) -> Self {
+        self.config = self.config.num_values(num_values);
+        self
+    }
+
+    /// Seuil de rejet des p-values.
+    pub fn alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Nombre de tentatives (au moins une).
+    pub fn attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    /// Vrai si `result` est rejeté au seuil `alpha`.
+    pub fn rejects(&self, result: &TestResult) -> bool {
+        match result.p_value {
+            Some(p) => p < self.alpha || (!result.passed && p > 1.0 - self.alpha),
+            None => !result.passed,
+        }
+    }
+
+    /// Exécute l'assertion sur `rng`.
+    ///
+    /// Retourne : `Ok(())` si chaque test est accepté à au moins une tentative, sinon les résultats de la
+    /// dernière tentative des tests rejetés à chaque fois.
+    pub fn check_rng<R: RngCore + ?Sized>(&self, rng: &mut R) -> Result<(), RandomnessFailure> {
+        let mut pending = self.config.tests.clone();
+        let mut failures = Vec::new();
+        for _ in 0..self.attempts {
+            let data: Vec<u64> = (0..self.config.num_values).map(|_| rng.next_u64()).collect();
+            failures.clear();
+            pending.retain(|test| {
+                let rejected: Vec<TestResult> = test
+                    .run(&data, &self.config)
+                    .into_iter()
+                    .filter(|result| self.rejects(result))
+                    .collect();
+                let keep = !rejected.is_empty();
+                failures.extend(rejected);
+                keep
+            });
+            if pending.is_empty() {
+                return Ok(());
+            }
+        }
+        Err(RandomnessFailure { alpha: self.alpha, attempts: self.attempts, failures })
+    }
+
+    /// Exécute l'assertion sur `rng` et panique avec le détail des tests rejetés en cas d'échec.
+    #[track_caller]
+    pub fn assert_rng<R: RngCore + ?Sized>(&self, rng: &mut R) {
+        if let Err(failure) = self.check_rng(rng) {
+            panic!("{}", failure);
+        }
+    }
+}
+
+/// Échec d'une `RandomnessAssertion`.
+#[derive(Debug, Clone)]
+pub struct RandomnessFailure {
+    /// Seuil de rejet utilisé.
+    pub alpha: f64,
+    /// Nombre de tentatives effectuées.
+    pub attempts: usize,
+    /// Résultats de la dernière tentative pour les tests rejetés à chaque tentative.
+    pub failures: Vec<TestResult>,
+}
+
+impl fmt::Display for RandomnessFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Le générateur est rejeté par {} test(s) à chacune des {} tentative(s) (alpha = {}) :",
+            self.failures.len(),
+            self.attempts,
+            self.alpha
+        )?;
+        for result in &self.failures {
+            let p_value = result.p_value.map_or("-".to_string(), |p| format!("{:.3e}", p));
+            writeln!(f, "  - {} (p = {}) : {}", result.test_name, p_value, result.details)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RandomnessFailure {}
+
+/// Vérifie qu'un générateur passe la batterie, dans un `#[test]`.
+///
+/// `assert_random!(rng)` utilise `RandomnessAssertion::new()` ; les arguments suivants sont des appels
+/// de méthodes du constructeur, par exemple `assert_random!(rng, alpha(0.001), attempts(5))`.
+#[macro_export]
+macro_rules! assert_random {
+    ($rng:expr $(,)?) => {
+        $crate::testing::RandomnessAssertion::new().assert_rng(&mut $rng)
+    };
+    ($rng:expr, $($method:ident ( $($arg:expr),* $(,)? )),+ $(,)?) => {
+        $crate::testing::RandomnessAssertion::new()$(.$method($($arg),*))+.assert_rng(&mut $rng)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::{Randu, Xorshift64};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn test_assert_random_accepts_sound_generators() {
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        assert_random!(rng);
+        assert_random!(rng, alpha(0.001), attempts(2), tests(&[BatteryTest::Frequency, BatteryTest::Ent]));
+        assert_random!(rand::thread_rng(), tests(&[BatteryTest::Autocorrelation]));
+    }
+
+    #[test]
+    fn test_structural_defects_fail_every_attempt() {
+        let assertion = RandomnessAssertion::new().tests(&[BatteryTest::StateRecovery]).attempts(4);
+        assert!(!RandomnessAssertion::new().config.tests.contains(&BatteryTest::Blocks));
+        assert!(!RandomnessAssertion::new().config.tests.contains(&BatteryTest::Poker));
+        let failure = assertion.check_rng(&mut Xorshift64::new(9)).unwrap_err();
+        assert_eq!(failure.attempts, 4);
+        assert_eq!(failure.failures.len(), 1);
+        assert!(failure.to_string().contains("Test de récupération d'état"));
+
+        let failure = RandomnessAssertion::new().tests(&[BatteryTest::Ent]).check_rng(&mut Randu::new(1));
+        assert!(failure.is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "rejeté")]
+    fn test_assert_random_panics_on_failure() {
+        let mut rng = Randu::new(1);
+        assert_random!(rng, tests(&[BatteryTest::Ent]));
+    }
+
+    #[test]
+    fn test_alpha_applies_to_p_values() {
+        let assertion = RandomnessAssertion::new().alpha(0.05);
+        let result = |passed, p_value| TestResult { passed, p_value, ..Default::default() };
+        assert!(assertion.rejects(&result(true, Some(0.02))));
+        assert!(!assertion.rejects(&result(false, Some(0.5))));
+        assert!(assertion.rejects(&result(false, Some(0.999))));
+        assert!(assertion.rejects(&result(false, None)));
+        assert!(!assertion.rejects(&result(true, None)));
+    }
+}
@@ -23,5 +23,6 @@ pub fn analyze_seed_patterns(seeds: &[u64]) -> TestResult {
             unique_patterns, bias_score
         ),
         thresholds: Some((0.0, 0.2)),
+        ..Default::default()
     }
 }
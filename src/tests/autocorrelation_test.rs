@@ -0,0 +1,277 @@
+use crate::tests::bit_stream::BitStream;
+use crate::types::TestResult;
+use crate::tests::stats::normal_two_sided_p_value;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// Seuil de signification de la p-value corrigée
+const AUTOCORRELATION_ALPHA: f64 = 0.01;
+// Décalage maximal par défaut
+const DEFAULT_MAX_LAG: usize = 4096;
+// Décalage maximal accepté pour une plage configurée (le coût du test croît avec le nombre de décalages)
+pub const MAX_LAG: usize = 16384;
+// Nombre minimal de paires comparées pour qu'un décalage soit évalué
+const MIN_PAIRS: usize = 32;
+
+/// Corrélogramme complet produit par le test d'autocorrélation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Correlogram {
+    /// Premier décalage évalué (les tableaux `bit_z` et `word_z` commencent à ce décalage).
+    pub lag_start: usize,
+    /// Scores z de l'autocorrélation du flux de bits, un par décalage.
+    pub bit_z: Vec<f64>,
+    /// Scores z de l'autocorrélation des valeurs des échantillons (flux « mots »), un par décalage.
+    pub word_z: Vec<f64>,
+    /// Décalage ayant le plus grand |z|.
+    pub worst_lag: usize,
+    /// Flux du décalage le plus suspect ("bits" ou "mots").
+    pub worst_stream: String,
+    /// Plus grand |z| observé sur tous les décalages.
+    pub max_abs_z: f64,
+}
+
+/// Plage de décalages du test d'autocorrélation, bornes incluses.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LagRange {
+    /// Premier décalage (au moins 1).
+    pub start: usize,
+    /// Dernier décalage (au plus `MAX_LAG`).
+    pub end: usize,
+}
+
+impl Default for LagRange {
+    /// Décalages 1 à 4096.
+    fn default() -> Self {
+        LagRange { start: 1, end: DEFAULT_MAX_LAG }
+    }
+}
+
+impl LagRange {
+    /// Construit une plage en vérifiant ses bornes.
+    pub fn new(start: usize, end: usize) -> Result<Self, String> {
+        if start == 0 || start > end || end > MAX_LAG {
+            return Err(format!(
+                "Plage de décalages invalide : {}-{} (attendu : 1 <= début <= fin <= {})",
+                start, end, MAX_LAG
+            ));
+        }
+        Ok(LagRange { start, end })
+    }
+
+    /// Décalages de la plage.
+    pub fn range(&self) -> RangeInclusive<usize> {
+        self.start..=self.end
+    }
+}
+
+impl FromStr for LagRange {
+    type Err = String;
+
+    /// Accepte `DEBUT-FIN` (ex. `1-512`) ou `FIN` seul pour les décalages 1 à `FIN`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let bound = |text: &str| {
+            text.trim().parse::<usize>().map_err(|_| format!("Plage de décalages invalide : {} (attendu : 1-512)", value))
+        };
+        match value.split_once('-') {
+            Some((start, end)) => LagRange::new(bound(start)?, bound(end)?),
+            None => LagRange::new(1, bound(value)?),
+        }
+    }
+}
+
+/// Test d'autocorrélation sur les décalages par défaut (1..=4096).
+/// `seeds` : Tableau de seeds.
+/// Retourne : Une structure TestResult avec le corrélogramme dans le champ `data`.
+pub fn test_autocorrelation(seeds: &[u64]) -> TestResult {
+    test_autocorrelation_with_lags(seeds, 1..=DEFAULT_MAX_LAG)
+}
+
+/// Test d'autocorrélation sur une plage de décalages configurable.
+/// `seeds` : Tableau de seeds, lus comme un flux de bits (bit de poids fort en premier) et comme des mots.
+/// `lags` : Plage de décalages à évaluer, limitée automatiquement à la taille des données.
+/// Retourne : Une structure TestResult ; la p-value est corrigée (Šidák) pour le nombre de décalages testés.
+pub fn test_autocorrelation_with_lags(seeds: &[u64], lags: RangeInclusive<usize>) -> TestResult {
+    test_autocorrelation_stream(&BitStream::from_u64s(seeds), lags)
+}
+
+/// Test d'autocorrélation d'un flux : les bits sont lus dans l'ordre du flux, et les valeurs comparées
+/// sont les échantillons à leur largeur réelle (sorties de 32 bits, octets...).
+/// `stream` : Flux analysé ; un échantillon incomplet en fin de flux n'entre que dans le flux de bits.
+/// `lags` : Plage de décalages à évaluer, limitée automatiquement à la taille des données.
+/// Retourne : Une structure TestResult ; la p-value est corrigée (Šidák) pour le nombre de décalages testés.
+pub fn test_autocorrelation_stream(stream: &BitStream, lags: RangeInclusive<usize>) -> TestResult {
+    let lag_start = (*lags.start()).max(1);
+    let bit_z = bit_autocorrelation(stream, lag_start..=*lags.end());
+    let word_z = word_autocorrelation(stream, lag_start..=*lags.end());
+
+    let tests_count = bit_z.len() + word_z.len();
+    if tests_count == 0 {
+        return TestResult {
+            test_name: "Test d'autocorrélation".to_string(),
+            passed: false,
+            score: 0.0,
+            details: "Erreur : Pas assez de données pour la plage de décalages demandée.".to_string(),
+            thresholds: None,
+            not_applicable: true,
+            ..Default::default()
+        };
+    }
+
+    let worst = |values: &[f64]| {
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, z)| (index, z.abs()))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    };
+    let (worst_lag, worst_stream, max_abs_z) = match (worst(&bit_z), worst(&word_z)) {
+        (Some((bit_index, bit_max)), Some((_, word_max))) if bit_max >= word_max => (bit_index, "bits", bit_max),
+        (_, Some((word_index, word_max))) => (word_index, "mots", word_max),
+        (Some((bit_index, bit_max)), None) => (bit_index, "bits", bit_max),
+        (None, None) => unreachable!("Au moins un décalage a été évalué"),
+    };
+    let worst_lag = worst_lag + lag_start;
+
+    // Correction de Šidák : probabilité qu'au moins un des décalages atteigne ce |z|
+    let raw_p_value = normal_two_sided_p_value(max_abs_z);
+    let p_value = -((tests_count as f64) * (-raw_p_value).ln_1p()).exp_m1();
+    let passed = p_value >= AUTOCORRELATION_ALPHA;
+
+    let correlogram = Correlogram {
+        lag_start,
+        bit_z,
+        word_z,
+        worst_lag,
+        worst_stream: worst_stream.to_string(),
+        max_abs_z,
+    };
+
+    TestResult {
+        test_name: "Test d'autocorrélation".to_string(),
+        passed,
+        score: max_abs_z,
+        details: format!(
+            "|z| maximal : {:.3} au décalage {} ({}), {} décalages testés, p-value corrigée : {:.4}",
+            max_abs_z, worst_lag, worst_stream, tests_count, p_value
+        ),
+        thresholds: None,
+        p_value: Some(p_value),
+        data: serde_json::to_value(&correlogram).ok(),
+        ..Default::default()
+    }
+}
+
+/// Calcule le score z de l'autocorrélation du flux de bits pour chaque décalage.
+/// Pour un décalage `d`, compte les bits différents entre b_i et b_(i+d) ; sous H0 ce nombre suit B(n - d, 1/2).
+fn bit_autocorrelation(stream: &BitStream, lags: RangeInclusive<usize>) -> Vec<f64> {
+    let total_bits = stream.len_bits();
+    lags.take_while(|&lag| lag + MIN_PAIRS <= total_bits)
+        .map(|lag| {
+            let pairs = total_bits - lag;
+            let mut differences = 0u64;
+            let mut position = 0;
+            while position < pairs {
+                let width = (pairs - position).min(64);
+                let xor = stream.bits_at(position, width as u32) ^ stream.bits_at(position + lag, width as u32);
+                differences += xor.count_ones() as u64;
+                position += width;
+            }
+            (2.0 * differences as f64 - pairs as f64) / (pairs as f64).sqrt()
+        })
+        .collect()
+}
+
+/// Calcule le score z de l'autocorrélation des valeurs des échantillons pour chaque décalage.
+/// Le coefficient r_k (Pearson) est ramené à z = r_k * sqrt(n - k).
+fn word_autocorrelation(stream: &BitStream, lags: RangeInclusive<usize>) -> Vec<f64> {
+    let n = stream.len_samples();
+    if n < MIN_PAIRS + 1 {
+        return Vec::new();
+    }
+
+    let max = (u64::MAX >> (64 - stream.sample_bits())) as f64;
+    let values: Vec<f64> = stream.samples().map(|sample| sample as f64 / max).collect();
+    let mean = values.iter().sum::<f64>() / n as f64;
+    let centered: Vec<f64> = values.iter().map(|value| value - mean).collect();
+    let variance = centered.iter().map(|value| value * value).sum::<f64>() / n as f64;
+    if variance == 0.0 {
+        return Vec::new();
+    }
+
+    lags.take_while(|&lag| lag + MIN_PAIRS <= n)
+        .map(|lag| {
+            let pairs = n - lag;
+            let covariance = centered
+                .iter()
+                .zip(&centered[lag..])
+                .map(|(a, b)| a * b)
+                .sum::<f64>()
+                / pairs as f64;
+            covariance / variance * (pairs as f64).sqrt()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn generate_random_seeds() -> Vec<u64> {
+        let mut rng = rand::thread_rng();
+        (0..1000).map(|_| rng.gen::<u64>()).collect()
+    }
+
+    #[test]
+    fn test_autocorrelation_with_random_seeds() {
+        let seeds = generate_random_seeds();
+        let result = test_autocorrelation_with_lags(&seeds, 1..=256);
+        println!("{}", result.details);
+        assert!(result.passed, "Échec du test d'autocorrélation : score = {:?}", result.score);
+        let correlogram: Correlogram = serde_json::from_value(result.data.unwrap()).unwrap();
+        assert_eq!(correlogram.bit_z.len(), 256);
+        assert_eq!(correlogram.word_z.len(), 256);
+    }
+
+    #[test]
+    fn test_autocorrelation_detects_repeated_block() {
+        // Un bloc aléatoire de 37 mots répété : corrélation parfaite au décalage 37
+        let mut rng = rand::thread_rng();
+        let block: Vec<u64> = (0..37).map(|_| rng.gen::<u64>()).collect();
+        let seeds: Vec<u64> = block.iter().cycle().take(1000).copied().collect();
+        let result = test_autocorrelation_with_lags(&seeds, 1..=64);
+        assert!(!result.passed);
+        let correlogram: Correlogram = serde_json::from_value(result.data.unwrap()).unwrap();
+        assert_eq!(correlogram.worst_lag, 37);
+    }
+
+    #[test]
+    fn test_autocorrelation_reads_samples_in_stream_order() {
+        // 37 sorties de 32 bits répétées : période de 37 × 32 = 1184 bits dans l'ordre du flux
+        let mut rng = rand::thread_rng();
+        let block: Vec<u32> = (0..37).map(|_| rng.gen::<u32>()).collect();
+        let outputs: Vec<u32> = block.iter().cycle().take(1000).copied().collect();
+        let stream = BitStream::from_u32s(&outputs);
+        let result = test_autocorrelation_stream(&stream, 1100..=1300);
+        assert!(!result.passed);
+        let correlogram: Correlogram = serde_json::from_value(result.data.unwrap()).unwrap();
+        assert_eq!((correlogram.worst_lag, correlogram.worst_stream.as_str()), (1184, "bits"));
+        // Les valeurs comparées sont les sorties elles-mêmes : même période de 37 échantillons
+        let correlogram: Correlogram =
+            serde_json::from_value(test_autocorrelation_stream(&stream, 30..=40).data.unwrap()).unwrap();
+        let word_z = &correlogram.word_z;
+        let worst_word = (0..word_z.len()).max_by(|&a, &b| word_z[a].total_cmp(&word_z[b]));
+        assert_eq!(worst_word.map(|index| index + 30), Some(37));
+    }
+
+    #[test]
+    fn test_lag_range_parsing() {
+        assert_eq!("8-64".parse(), Ok(LagRange { start: 8, end: 64 }));
+        assert_eq!(" 512 ".parse::<LagRange>().unwrap().range(), 1..=512);
+        assert_eq!(LagRange::default().range(), 1..=DEFAULT_MAX_LAG);
+        for invalid in ["0-10", "10-5", "x", "1-", &format!("{}", MAX_LAG + 1)] {
+            assert!(invalid.parse::<LagRange>().is_err(), "{}", invalid);
+        }
+    }
+}
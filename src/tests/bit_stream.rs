@@ -0,0 +1,326 @@
+use crate::types::TestResult;
+
+/// Flux de bits de longueur connue, découpé en échantillons de largeur connue.
+///
+/// Les bits sont stockés dans l'ordre du flux, regroupés par mots de 64 bits dont le premier bit est le
+/// poids fort ; chaque échantillon est lu poids fort d'abord. Les tests sur les bits (fréquence, séquences,
+/// poker, blocs) parcourent ainsi le flux sans frontière artificielle entre mots, et les tests sur les
+/// échantillons (corrélation, périodicité) comparent des sorties de la largeur réelle du générateur.
+///
+/// # Remarques
+/// * Les bits inutilisés du dernier mot sont toujours nuls.
+/// * Si la longueur n'est pas un multiple de la largeur, le dernier échantillon incomplet n'est pas
+///   renvoyé par `samples` mais ses bits restent dans le flux.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitStream {
+    words: Vec<u64>,
+    bit_len: usize,
+    sample_bits: u32,
+}
+
+impl Default for BitStream {
+    /// Flux vide d'échantillons de 64 bits.
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+impl BitStream {
+    /// Flux vide d'échantillons de `sample_bits` bits (de 1 à 64), à remplir avec `push`.
+    pub fn new(sample_bits: u32) -> Self {
+        assert!((1..=64).contains(&sample_bits), "Largeur d'échantillon invalide : {}", sample_bits);
+        BitStream { words: Vec::new(), bit_len: 0, sample_bits }
+    }
+
+    /// Flux de valeurs de 64 bits, chacune lue poids fort d'abord.
+    pub fn from_u64s(values: &[u64]) -> Self {
+        BitStream { words: values.to_vec(), bit_len: values.len() * 64, sample_bits: 64 }
+    }
+
+    /// Flux de sorties de 32 bits, dans l'ordre de production.
+    pub fn from_u32s(values: &[u32]) -> Self {
+        Self::from_samples(values.iter().map(|&value| value as u64), 32)
+    }
+
+    /// Flux d'octets, dans l'ordre du fichier.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self::from_samples(bytes.iter().map(|&byte| byte as u64), 8)
+    }
+
+    /// Flux de bits isolés (échantillons d'un bit), de longueur quelconque.
+    pub fn from_bits<I: IntoIterator<Item = bool>>(bits: I) -> Self {
+        Self::from_samples(bits.into_iter().map(u64::from), 1)
+    }
+
+    /// Flux d'échantillons de `sample_bits` bits (de 1 à 64), pris dans les bits de poids faible de
+    /// chaque valeur.
+    pub fn from_samples<I: IntoIterator<Item = u64>>(samples: I, sample_bits: u32) -> Self {
+        let mut stream = Self::new(sample_bits);
+        for sample in samples {
+            stream.push(sample);
+        }
+        stream
+    }
+
+    /// Ajoute un échantillon, pris dans les `sample_bits` bits de poids faible de `sample`.
+    pub fn push(&mut self, sample: u64) {
+        self.push_bits(sample, self.sample_bits);
+    }
+
+    /// Ajoute tous les bits de `other` à la suite du flux ; la largeur d'échantillon reste celle du flux.
+    pub fn append(&mut self, other: &BitStream) {
+        let complete = other.bit_len / 64;
+        for &word in &other.words[..complete] {
+            self.push_bits(word, 64);
+        }
+        let rest = (other.bit_len % 64) as u32;
+        if rest > 0 {
+            self.push_bits(other.bits_at(complete * 64, rest), rest);
+        }
+    }
+
+    /// Les `bits` premiers bits du flux (tout le flux s'il est plus court), de même largeur d'échantillon.
+    pub fn prefix(&self, bits: usize) -> BitStream {
+        let bit_len = bits.min(self.bit_len);
+        let mut words = self.words[..bit_len.div_ceil(64)].to_vec();
+        if let Some(last) = words.last_mut().filter(|_| !bit_len.is_multiple_of(64)) {
+            *last &= u64::MAX << (64 - bit_len % 64);
+        }
+        BitStream { words, bit_len, sample_bits: self.sample_bits }
+    }
+
+    /// Ajoute les `count` bits de poids faible de `value`, poids fort d'abord.
+    fn push_bits(&mut self, value: u64, count: u32) {
+        let value = if count == 64 { value } else { value & ((1u64 << count) - 1) };
+        let offset = (self.bit_len % 64) as u32;
+        if offset == 0 {
+            self.words.push(value << (64 - count));
+        } else {
+            let free = 64 - offset;
+            let last = self.words.last_mut().unwrap();
+            if count <= free {
+                *last |= value << (free - count);
+            } else {
+                *last |= value >> (count - free);
+                self.words.push(value << (64 - (count - free)));
+            }
+        }
+        self.bit_len += count as usize;
+    }
+
+    /// Nombre total de bits.
+    pub fn len_bits(&self) -> usize {
+        self.bit_len
+    }
+
+    /// Vrai si le flux ne contient aucun bit.
+    pub fn is_empty(&self) -> bool {
+        self.bit_len == 0
+    }
+
+    /// Largeur des échantillons en bits.
+    pub fn sample_bits(&self) -> u32 {
+        self.sample_bits
+    }
+
+    /// Nombre d'échantillons complets.
+    pub fn len_samples(&self) -> usize {
+        self.bit_len / self.sample_bits as usize
+    }
+
+    /// Nombre de bits à 1.
+    pub fn count_ones(&self) -> u64 {
+        self.words.iter().map(|word| word.count_ones() as u64).sum()
+    }
+
+    /// Lit `count` bits (au plus 64) à partir de la position `start`, premier bit en poids fort.
+    pub fn bits_at(&self, start: usize, count: u32) -> u64 {
+        debug_assert!(count <= 64 && start + count as usize <= self.bit_len);
+        if count == 0 {
+            return 0;
+        }
+        let (index, offset) = (start / 64, (start % 64) as u32);
+        let high = self.words[index] << offset;
+        let value = if offset + count > 64 { high | (self.words[index + 1] >> (64 - offset)) } else { high };
+        value >> (64 - count)
+    }
+
+    /// Bits du flux, dans l'ordre.
+    pub fn bits(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.bit_len).map(move |i| (self.words[i / 64] >> (63 - i % 64)) & 1 == 1)
+    }
+
+    /// Groupes consécutifs et disjoints de `width` bits (de 1 à 64) ; les derniers bits d'un groupe
+    /// incomplet sont ignorés.
+    pub fn chunks(&self, width: u32) -> impl Iterator<Item = u64> + '_ {
+        let width = width.clamp(1, 64);
+        (0..self.bit_len / width as usize).map(move |i| self.bits_at(i * width as usize, width))
+    }
+
+    /// Échantillons complets, dans l'ordre.
+    pub fn samples(&self) -> impl Iterator<Item = u64> + '_ {
+        self.chunks(self.sample_bits)
+    }
+
+    /// Octets du flux pour les tests qui lisent des octets (ENT, compression).
+    ///
+    /// # Remarques
+    /// * Les échantillons dont la largeur est un multiple de 8 bits donnent leurs octets poids faible
+    ///   d'abord, comme `seeds_to_bytes` (un flux d'octets est donc rendu dans l'ordre du fichier) ; les
+    ///   autres largeurs (bits isolés notamment) sont lues par groupes de 8 bits, premier bit en poids fort.
+    /// * Un échantillon ou un octet incomplet en fin de flux n'est pas renvoyé (voir `note_dropped_bits`).
+    pub fn bytes(&self) -> Vec<u8> {
+        if self.sample_bits.is_multiple_of(8) {
+            let width = (self.sample_bits / 8) as usize;
+            self.samples().flat_map(|sample| sample.to_le_bytes().into_iter().take(width)).collect()
+        } else {
+            self.chunks(8).map(|byte| byte as u8).collect()
+        }
+    }
+
+    /// Valeurs de 64 bits pour les tests qui travaillent sur des mots.
+    ///
+    /// # Remarques
+    /// * Les échantillons de 8, 16 ou 32 bits sont regroupés premier échantillon en poids faible, comme
+    ///   `next_u64` des générateurs 32 bits et `fill_bytes` ; les autres largeurs (bits isolés notamment)
+    ///   sont lues par 64 bits, premier bit en poids fort.
+    /// * Seuls les mots complets sont renvoyés.
+    pub fn words64(&self) -> Vec<u64> {
+        let complete = self.bit_len / 64;
+        let w = self.sample_bits;
+        if (8..64).contains(&w) && 64u32.is_multiple_of(w) {
+            (0..complete)
+                .map(|index| {
+                    (0..64 / w).fold(0u64, |value, k| {
+                        value | (self.bits_at(index * 64 + (k * w) as usize, w) << (k * w))
+                    })
+                })
+                .collect()
+        } else {
+            self.words[..complete].to_vec()
+        }
+    }
+}
+
+/// Signale dans les détails d'un résultat les `dropped` bits de fin de flux laissés de côté parce qu'ils ne
+/// forment pas un `unit` complet (ex. « mot de 64 bits ») ; ne fait rien si `dropped` est nul.
+pub fn note_dropped_bits(result: &mut TestResult, dropped: usize, unit: &str) {
+    if dropped > 0 {
+        result.details.push_str(&format!(" ({} bit(s) final(s) ignoré(s) : {} incomplet)", dropped, unit));
+    }
+}
+
+/// Statistique d'un test calculée morceau par morceau, pour analyser un flux sans le garder en mémoire.
+///
+/// # Remarques
+/// * Les morceaux passés à `update` se suivent dans le flux et contiennent des échantillons complets de
+///   même largeur ; le résultat de `finish` est celui du test sur leur concaténation.
+/// * `Send + Sync` : la batterie en flux tourne dans une tâche du serveur et termine ses tests en parallèle.
+pub trait StreamAccumulator: Send + Sync {
+    /// Ajoute le morceau suivant du flux.
+    fn update(&mut self, chunk: &BitStream);
+    /// Résultat du test sur tous les morceaux reçus.
+    fn finish(&self) -> TestResult;
+}
+
+/// Découpe en groupes consécutifs de `width` bits un flux reçu en plusieurs morceaux, un groupe pouvant
+/// chevaucher deux morceaux.
+#[derive(Debug, Clone, Default)]
+pub struct GroupCarry {
+    width: u32,
+    pending: u64,
+    pending_bits: u32,
+}
+
+impl GroupCarry {
+    /// Groupes de `width` bits (de 1 à 64).
+    pub fn new(width: u32) -> Self {
+        GroupCarry { width: width.clamp(1, 64), ..Default::default() }
+    }
+
+    /// Appelle `on_group` pour chaque groupe complété par `chunk` ; les bits restants sont conservés.
+    pub fn feed(&mut self, chunk: &BitStream, mut on_group: impl FnMut(u64)) {
+        let (width, len) = (self.width as usize, chunk.len_bits());
+        let mut start = 0;
+        if self.pending_bits > 0 {
+            let take = (width - self.pending_bits as usize).min(len);
+            self.pending = (self.pending << take) | chunk.bits_at(0, take as u32);
+            self.pending_bits += take as u32;
+            start = take;
+            if (self.pending_bits as usize) < width {
+                return;
+            }
+            on_group(self.pending);
+            self.pending_bits = 0;
+        }
+        while start + width <= len {
+            on_group(chunk.bits_at(start, width as u32));
+            start += width;
+        }
+        self.pending_bits = (len - start) as u32;
+        self.pending = chunk.bits_at(start, self.pending_bits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_layout() {
+        let stream = BitStream::from_u32s(&[0x8000_0001, 0xFFFF_0000, 0x1234_5678]);
+        assert_eq!((stream.len_bits(), stream.len_samples(), stream.sample_bits()), (96, 3, 32));
+        assert_eq!(stream.samples().collect::<Vec<_>>(), vec![0x8000_0001, 0xFFFF_0000, 0x1234_5678]);
+        assert_eq!(stream.count_ones(), 2 + 16 + 13);
+        assert_eq!(stream.bits_at(31, 2), 0b11);
+        // Premier échantillon en poids faible, comme `next_u64` ; le dernier échantillon isolé est exclu
+        assert_eq!(stream.words64(), vec![0xFFFF_0000_8000_0001]);
+
+        let bytes = BitStream::from_bytes(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(bytes.words64(), vec![0x0807_0605_0403_0201]);
+        assert_eq!(bytes.chunks(4).take(4).collect::<Vec<_>>(), vec![0, 1, 0, 2]);
+
+        assert_eq!(bytes.bytes(), vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(BitStream::from_u32s(&[0x0403_0201, 0x0807_0605]).bytes(), bytes.bytes());
+
+        let values = [0xDEAD_BEEF_0123_4567u64, u64::MAX];
+        assert_eq!(BitStream::from_u64s(&values).words64(), values.to_vec());
+        assert_eq!(BitStream::from_samples(values, 64), BitStream::from_u64s(&values));
+    }
+
+    #[test]
+    fn test_unaligned_bits_are_kept() {
+        let stream = BitStream::from_bits((0..70).map(|i| i % 3 == 0));
+        assert_eq!((stream.len_bits(), stream.len_samples()), (70, 70));
+        assert_eq!(stream.count_ones(), 24);
+        assert_eq!(stream.bits().filter(|&bit| bit).count(), 24);
+        assert_eq!(stream.words64().len(), 1);
+        assert_eq!(stream.chunks(7).count(), 10);
+        assert_eq!(stream.bytes(), vec![0b1001_0010, 0b0100_1001, 0b0010_0100, 0b1001_0010, 0b0100_1001,
+                                        0b0010_0100, 0b1001_0010, 0b0100_1001]);
+
+        let odd = BitStream::from_samples([0b101, 0b011, 0b1], 3);
+        assert_eq!(odd.bits().map(u8::from).collect::<Vec<_>>(), vec![1, 0, 1, 0, 1, 1, 0, 0, 1]);
+
+        // Concaténation et préfixe gardent les bits exacts, hors des frontières de mots
+        let mut joined = BitStream::from_bits((0..70).map(|i| i % 3 == 0));
+        joined.append(&odd);
+        let expected: Vec<bool> = stream.bits().chain(odd.bits()).collect();
+        assert_eq!(joined.bits().collect::<Vec<_>>(), expected);
+        assert_eq!(joined.prefix(75), BitStream::from_bits(expected[..75].iter().copied()));
+        assert_eq!(joined.prefix(1000), joined);
+        assert_eq!(BitStream::default().sample_bits(), 64);
+    }
+
+    #[test]
+    fn test_groups_cross_chunks() {
+        let stream = BitStream::from_bits((0..200).map(|i| i % 7 < 3));
+        let expected: Vec<u64> = stream.chunks(5).collect();
+        let mut carry = GroupCarry::new(5);
+        let mut groups = Vec::new();
+        for chunk in stream.bits().collect::<Vec<_>>().chunks(13) {
+            carry.feed(&BitStream::from_bits(chunk.iter().copied()), |group| groups.push(group));
+        }
+        assert_eq!(groups, expected);
+    }
+}
@@ -1,7 +1,9 @@
 use crate::types::TestResult;
+use crate::tests::bit_stream::{BitStream, GroupCarry, StreamAccumulator};
 
 // Seuils pour le test de blocs
 const CHI_SQUARE_THRESHOLD: f64 = 25.0; // Seuil ajusté pour tolérer plus de variations
+#[cfg(test)]
 const BLOCK_SIZE: usize = 4; // Taille des blocs pour l'analyse
 
 /// Test de blocs pour vérifier la répartition des combinaisons.
@@ -9,7 +11,46 @@ const BLOCK_SIZE: usize = 4; // Taille des blocs pour l'analyse
 /// `block_size` : Taille des blocs.
 /// Retourne : Une structure TestResult avec les résultats du test.
 pub fn test_blocs(seeds: &[u64], block_size: usize) -> TestResult {
-    let chi_square_score = calculate_chi_square(seeds, block_size);
+    test_blocs_stream(&BitStream::from_u64s(seeds), block_size)
+}
+
+/// Test de blocs sur tous les blocs consécutifs d'un flux de bits.
+/// `stream` : Flux de bits.
+/// `block_size` : Taille des blocs (de 1 à 64 bits).
+/// Retourne : Une structure TestResult avec les résultats du test.
+pub fn test_blocs_stream(stream: &BitStream, block_size: usize) -> TestResult {
+    let mut accumulator = BlockAccumulator::new(block_size);
+    accumulator.update(stream);
+    accumulator.finish()
+}
+
+/// Fréquences des blocs, mises à jour morceau par morceau.
+#[derive(Debug, Clone)]
+pub struct BlockAccumulator {
+    counts: Vec<u64>,
+    carry: GroupCarry,
+}
+
+impl BlockAccumulator {
+    /// Compteurs pour des blocs de `block_size` bits.
+    pub fn new(block_size: usize) -> Self {
+        BlockAccumulator { counts: vec![0; 2_usize.pow(block_size as u32)], carry: GroupCarry::new(block_size as u32) }
+    }
+}
+
+impl StreamAccumulator for BlockAccumulator {
+    fn update(&mut self, chunk: &BitStream) {
+        let counts = &mut self.counts;
+        self.carry.feed(chunk, |block| counts[block as usize] += 1);
+    }
+
+    fn finish(&self) -> TestResult {
+        blocks_result(calculate_chi_square(&self.counts))
+    }
+}
+
+/// Construit le résultat du test à partir du score Chi-square.
+fn blocks_result(chi_square_score: f64) -> TestResult {
     let passed = chi_square_score <= CHI_SQUARE_THRESHOLD;
 
     TestResult {
@@ -21,26 +62,15 @@ pub fn test_blocs(seeds: &[u64], block_size: usize) -> TestResult {
             chi_square_score, CHI_SQUARE_THRESHOLD
         ),
         thresholds: Some((0.0, CHI_SQUARE_THRESHOLD)),
+        ..Default::default()
     }
 }
 
 /// Calcule le score Chi-square pour les blocs.
-/// `seeds` : Tableau de seeds.
-/// `block_size` : Taille des blocs.
+/// `counts` : Nombre d'occurrences de chaque bloc (un bloc incomplet final est ignoré).
 /// Retourne : Le score Chi-square.
-fn calculate_chi_square(seeds: &[u64], block_size: usize) -> f64 {
-    let mut counts = vec![0; 2_usize.pow(block_size as u32)];
-
-    for &seed in seeds {
-        let mut value = seed;
-        for _ in 0..(64 / block_size) {
-            let block = (value & ((1 << block_size) - 1)) as usize;
-            counts[block] += 1;
-            value >>= block_size;
-        }
-    }
-
-    let total_blocks = counts.iter().sum::<usize>() as f64;
+fn calculate_chi_square(counts: &[u64]) -> f64 {
+    let total_blocks = counts.iter().sum::<u64>() as f64;
     let expected_count = total_blocks / counts.len() as f64;
 
     counts
@@ -24,6 +24,8 @@ pub fn test_collisions(seeds: &[u64]) -> TestResult {
             score: 100.0, // 100 % de collision dans le cas d'une entrée vide
             details: "Erreur : Aucun seed fourni pour le test.".to_string(),
             thresholds: Some((0.0, COLLISION_THRESHOLD)),
+            not_applicable: true,
+            ..Default::default()
         };
     }
 
@@ -44,6 +46,7 @@ pub fn test_collisions(seeds: &[u64]) -> TestResult {
             collision_rate, num_total, num_unique, COLLISION_THRESHOLD
         ),
         thresholds: Some((0.0, COLLISION_THRESHOLD)),
+        ..Default::default()
     }
 }
 
@@ -0,0 +1,183 @@
+use crate::tests::bit_stream::{note_dropped_bits, BitStream};
+use crate::types::TestResult;
+use crate::tests::stats::seeds_to_bytes;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+// Seuil de signification : un gain de compression aussi improbable que 1 % fait échouer le test
+const COMPRESSION_ALPHA: f64 = 0.01;
+// Ordre par défaut du modèle de contexte (nombre d'octets précédents utilisés comme contexte)
+const CONTEXT_ORDER: usize = 2;
+// Taille (en bits) de la table de hachage des contextes du modèle
+const CONTEXT_TABLE_BITS: u32 = 20;
+
+/// Résultat d'un compresseur appliqué aux données.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CompressorOutcome {
+    /// Nom du compresseur.
+    pub name: String,
+    /// Taille compressée en octets (arrondie au supérieur pour le modèle de contexte).
+    pub compressed_bytes: usize,
+    /// Rapport taille compressée / taille d'origine (>= 1.0 attendu pour des données aléatoires).
+    pub ratio: f64,
+    /// Borne supérieure de la probabilité d'un tel gain pour des données uniformes.
+    pub p_value: f64,
+}
+
+/// Test de compression : des données aléatoires ne doivent pas être compressibles.
+/// `seeds` : Tableau de seeds, lus octet par octet en little-endian.
+/// Retourne : Une structure `TestResult` avec le meilleur rapport obtenu et les détails par compresseur.
+///
+/// # Remarques
+/// * La signification repose sur l'inégalité de Kraft : pour des octets uniformes, la probabilité
+///   qu'un code sans préfixe économise `s` bits est au plus 2^-s.
+/// * La p-value globale est corrigée (Bonferroni) pour le nombre de compresseurs.
+pub fn test_compression(seeds: &[u64]) -> TestResult {
+    compression_result(&seeds_to_bytes(seeds))
+}
+
+/// Test de compression sur les octets d'un flux (voir `BitStream::bytes`).
+/// `stream` : Flux analysé ; les bits d'un octet ou d'un échantillon incomplet en fin de flux sont ignorés
+/// et signalés dans les détails.
+/// Retourne : Une structure `TestResult` avec le meilleur rapport obtenu et les détails par compresseur.
+pub fn test_compression_stream(stream: &BitStream) -> TestResult {
+    let bytes = stream.bytes();
+    let mut result = compression_result(&bytes);
+    let unit = if stream.sample_bits().is_multiple_of(8) { "échantillon" } else { "octet" };
+    note_dropped_bits(&mut result, stream.len_bits() - bytes.len() * 8, unit);
+    result
+}
+
+// Résultat du test de compression d'un flux d'octets
+fn compression_result(bytes: &[u8]) -> TestResult {
+    if bytes.is_empty() {
+        return TestResult {
+            test_name: "Test de compression".to_string(),
+            passed: false,
+            score: 0.0,
+            details: "Erreur : Aucun seed fourni pour le test.".to_string(),
+            thresholds: None,
+            not_applicable: true,
+            ..Default::default()
+        };
+    }
+
+    let outcomes = compress_all(bytes, CONTEXT_ORDER);
+    let best = outcomes
+        .iter()
+        .min_by(|a, b| a.ratio.total_cmp(&b.ratio))
+        .expect("Au moins un compresseur est toujours exécuté");
+    let p_value = (best.p_value * outcomes.len() as f64).min(1.0);
+    let passed = p_value >= COMPRESSION_ALPHA;
+
+    let summary = outcomes
+        .iter()
+        .map(|outcome| format!("{} : {:.4}", outcome.name, outcome.ratio))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    TestResult {
+        test_name: "Test de compression".to_string(),
+        passed,
+        score: best.ratio,
+        details: format!(
+            "Rapports de compression : {}, Meilleur : {}, p-value : {:.4}",
+            summary, best.name, p_value
+        ),
+        thresholds: None,
+        p_value: Some(p_value),
+        data: serde_json::to_value(&outcomes).ok(),
+        ..Default::default()
+    }
+}
+
+/// Exécute chaque compresseur sur les octets.
+/// `bytes` : Données à compresser.
+/// `context_order` : Ordre du modèle de contexte (0 à 8 octets).
+/// Retourne : Un `CompressorOutcome` par compresseur (DEFLATE, LZ4, modèle de contexte).
+pub fn compress_all(bytes: &[u8], context_order: usize) -> Vec<CompressorOutcome> {
+    let original_bits = bytes.len() as f64 * 8.0;
+    let outcome = |name: String, compressed_bits: f64| CompressorOutcome {
+        name,
+        compressed_bytes: (compressed_bits / 8.0).ceil() as usize,
+        ratio: compressed_bits / original_bits,
+        p_value: 2f64.powf(compressed_bits - original_bits).min(1.0),
+    };
+
+    vec![
+        outcome("DEFLATE".to_string(), deflate_size(bytes) as f64 * 8.0),
+        outcome("LZ4".to_string(), lz4_flex::block::compress(bytes).len() as f64 * 8.0),
+        outcome(format!("Contexte d'ordre {}", context_order), context_model_bits(bytes, context_order)),
+    ]
+}
+
+/// Taille de la sortie DEFLATE au niveau de compression maximal.
+fn deflate_size(bytes: &[u8]) -> usize {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(bytes).expect("Écriture en mémoire infaillible");
+    encoder.finish().expect("Écriture en mémoire infaillible").len()
+}
+
+/// Longueur de code idéale (en bits) d'un modèle de contexte binaire adaptatif d'ordre `order`.
+/// Chaque bit est prédit à partir des `order` octets précédents et des bits déjà lus de l'octet courant,
+/// avec l'estimateur de Krichevsky–Trofimov ; c'est la taille qu'atteindrait un codeur arithmétique.
+fn context_model_bits(bytes: &[u8], order: usize) -> f64 {
+    let order = order.min(8);
+    let history_mask = if order == 8 { u64::MAX } else { (1u64 << (8 * order)) - 1 };
+    let mut table = vec![[0u16; 2]; 1 << CONTEXT_TABLE_BITS];
+
+    let mut history = 0u64;
+    let mut total_bits = 0.0;
+    for &byte in bytes {
+        let context_hash = (history & history_mask).wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let mut node = 1usize;
+        for shift in (0..8).rev() {
+            let bit = ((byte >> shift) & 1) as usize;
+            let index = ((context_hash ^ (node as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)) >> (64 - CONTEXT_TABLE_BITS)) as usize;
+            let counts = &mut table[index];
+            let probability = (counts[bit] as f64 + 0.5) / (counts[0] as f64 + counts[1] as f64 + 1.0);
+            total_bits -= probability.log2();
+
+            counts[bit] += 1;
+            if counts[bit] == u16::MAX {
+                counts[0] /= 2;
+                counts[1] /= 2;
+            }
+            node = (node << 1) | bit;
+        }
+        history = (history << 8) | byte as u64;
+    }
+    total_bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn generate_random_seeds() -> Vec<u64> {
+        let mut rng = rand::thread_rng();
+        (0..1000).map(|_| rng.gen::<u64>()).collect()
+    }
+
+    #[test]
+    fn test_compression_with_random_seeds() {
+        let seeds = generate_random_seeds();
+        let result = test_compression(&seeds);
+        println!("{}", result.details);
+        assert!(result.passed, "Échec du test de compression : score = {:?}", result.score);
+        assert!(result.score >= 0.99);
+    }
+
+    #[test]
+    fn test_compression_with_counter() {
+        let seeds: Vec<u64> = (0..1000).collect();
+        let result = test_compression(&seeds);
+        println!("{}", result.details);
+        assert!(!result.passed);
+        assert!(result.score < 0.5);
+        assert_eq!(result.data.unwrap().as_array().unwrap().len(), 3);
+    }
+}
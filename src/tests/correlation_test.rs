@@ -1,4 +1,5 @@
 use crate::types::TestResult; // Importation de TestResult pour structurer les résultats des tests
+use crate::tests::bit_stream::{BitStream, StreamAccumulator};
 
 // Constantes de seuil pour le Test de Corrélation
 const CORRELATION_TEST_MIN: f64 = 0.45; // Valeur minimale acceptée pour le passage du test
@@ -8,8 +9,47 @@ const CORRELATION_TEST_MAX: f64 = 0.55; // Valeur maximale acceptée pour le pas
 /// `seeds` : Un tableau de nombres de seeds pour le calcul.
 /// Retourne : Une structure TestResult contenant les résultats du test.
 pub fn test_correlation(seeds: &[u64]) -> TestResult {
-    let correlation = calculate_correlation(seeds); // Calcul de la corrélation
-    let passed = correlation >= CORRELATION_TEST_MIN && correlation <= CORRELATION_TEST_MAX;
+    test_correlation_stream(&BitStream::from_u64s(seeds))
+}
+
+/// Exécute le test de corrélation entre échantillons consécutifs d'un flux, à leur largeur réelle.
+/// `stream` : Flux d'échantillons (par exemple des sorties de 32 bits).
+/// Retourne : Une structure TestResult contenant les résultats du test.
+pub fn test_correlation_stream(stream: &BitStream) -> TestResult {
+    let mut accumulator = CorrelationAccumulator::default();
+    accumulator.update(stream);
+    accumulator.finish()
+}
+
+/// Somme des corrélations entre échantillons successifs, mise à jour morceau par morceau.
+#[derive(Debug, Clone, Default)]
+pub struct CorrelationAccumulator {
+    previous: Option<u64>,
+    correlation_sum: f64,
+    count: u64,
+}
+
+impl StreamAccumulator for CorrelationAccumulator {
+    fn update(&mut self, chunk: &BitStream) {
+        let width = chunk.sample_bits() as f64;
+        for sample in chunk.samples() {
+            if let Some(previous) = self.previous {
+                // Différence binaire normalisée entre deux échantillons consécutifs
+                self.correlation_sum += (previous ^ sample).count_ones() as f64 / width;
+                self.count += 1;
+            }
+            self.previous = Some(sample);
+        }
+    }
+
+    fn finish(&self) -> TestResult {
+        correlation_result(self.correlation_sum / self.count as f64) // Moyenne de la corrélation
+    }
+}
+
+/// Construit le résultat du test à partir du taux de corrélation.
+fn correlation_result(correlation: f64) -> TestResult {
+    let passed = (CORRELATION_TEST_MIN..=CORRELATION_TEST_MAX).contains(&correlation);
 
     TestResult {
         test_name: "Test de corrélation des bits".to_string(),
@@ -17,26 +57,10 @@ pub fn test_correlation(seeds: &[u64]) -> TestResult {
         score: correlation,
         details: format!("Taux de corrélation : {:.2}%", correlation * 100.0),
         thresholds: Some((CORRELATION_TEST_MIN, CORRELATION_TEST_MAX)),
+        ..Default::default()
     }
 }
 
-/// Calcule le taux de corrélation entre les seeds successifs.
-/// `seeds` : Tableau de seeds.
-/// Retourne : La valeur moyenne de corrélation entre les bits des seeds adjacents.
-fn calculate_correlation(seeds: &[u64]) -> f64 {
-    let mut pairs = seeds.windows(2); // Génère des paires de seeds consécutifs
-    let mut correlation_sum = 0.0;
-    let mut count = 0;
-
-    while let Some(&[seed1, seed2]) = pairs.next() {
-        let correlation = (seed1 ^ seed2).count_ones() as f64 / 64.0; // Calcul de la différence binaire normalisée
-        correlation_sum += correlation;
-        count += 1;
-    }
-
-    correlation_sum / count as f64 // Moyenne de la corrélation
-}
-
 #[cfg(test)]
 mod tests {
     use super::{test_correlation, CORRELATION_TEST_MIN, CORRELATION_TEST_MAX}; // Importation des fonctions et constantes du module parent
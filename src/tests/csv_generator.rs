@@ -0,0 +1,98 @@
+use crate::report::Report;
+
+/// Génère l'export CSV d'une exécution : ses métadonnées en commentaires (`# clé : valeur`), puis une ligne
+/// par résultat de chaque suite.
+/// `report` : Rapport de l'exécution.
+/// Retourne : Le document CSV (séparateur `,`), ou l'erreur d'écriture.
+///
+/// # Remarques
+/// * Les lignes de commentaire commencent par `#` ; les lecteurs CSV les ignorent avec l'option
+///   correspondante (`comment`).
+/// * Les colonnes `Seuils Bas` et `Seuils Haut` sont vides pour un test sans seuils, `p-value` pour un
+///   test sans p-value.
+pub fn generate_csv_report(report: &Report) -> Result<Vec<u8>, csv::Error> {
+    let metadata = &report.metadata;
+    let mut comments = vec![
+        ("Exécution", metadata.id.clone().unwrap_or_else(|| "-".to_string())),
+        ("Entrée", metadata.input.description.clone()),
+        ("Valeurs", metadata.num_values.to_string()),
+        ("Outil", format!("{} {}", report.tool.name, report.tool.version)),
+        ("Début", metadata.started_at.clone()),
+        ("Fin", metadata.finished_at.clone()),
+        ("Verdict", report.summary.verdict.label().to_string()),
+    ];
+    if let Some(run) = &metadata.replay {
+        comments.push(("Relance", format!("{} seeds, mode {}", run.num_seeds, run.test_mode)));
+    }
+    let mut data: Vec<u8> = comments
+        .iter()
+        .flat_map(|(key, value)| format!("# {} : {}\n", key, value.replace(['\r', '\n'], " ")).into_bytes())
+        .collect();
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record([
+        "Groupe",
+        "Test",
+        "Nom du test",
+        "Verdict",
+        "Résultat",
+        "Score",
+        "p-value",
+        "Détails",
+        "Seuils Bas",
+        "Seuils Haut",
+    ])?;
+    for sequence in &report.sequences {
+        for reported in &sequence.results {
+            let result = &reported.result;
+            wtr.write_record(&[
+                sequence.group.clone().unwrap_or_default(),
+                reported.test.map(|test| test.id()).unwrap_or_default(),
+                result.test_name.clone(),
+                reported.verdict.label().to_string(),
+                result.passed.to_string(),
+                format!("{:.4}", result.score),
+                result.p_value.map_or(String::new(), |p| format!("{:e}", p)),
+                result.details.clone(),
+                result.thresholds.map_or(String::new(), |(low, _)| format!("{:.2}", low)),
+                result.thresholds.map_or(String::new(), |(_, high)| format!("{:.2}", high)),
+            ])?;
+        }
+    }
+    data.extend(wtr.into_inner().map_err(|err| err.into_error())?);
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battery::{run_tests, BatteryConfig, BatteryTest};
+    use crate::report::{InputDescription, InputKind, RunMetadata, RunParameters, SequenceReport};
+
+    #[test]
+    fn test_csv_report_lists_results_with_metadata() {
+        let data: Vec<u64> = (0..2000u64).map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ (i << 7)).collect();
+        let config = BatteryConfig::default().tests(&[BatteryTest::Frequency, BatteryTest::Compression]);
+        let mut metadata =
+            RunMetadata::new(InputDescription::new(InputKind::Upload, "fichier\nenvoyé"), RunParameters::new(&config));
+        metadata.id = Some("0123456789abcdef".to_string());
+        let mut sequence = SequenceReport::new(None, run_tests(&data, &config));
+        sequence.group = Some("a".to_string());
+        let report = Report::new(metadata, vec![sequence]);
+
+        let csv = generate_csv_report(&report).unwrap();
+        let text = String::from_utf8(csv.clone()).unwrap();
+        assert!(text.starts_with("# Exécution : 0123456789abcdef\n# Entrée : fichier envoyé\n"), "{}", text);
+
+        let mut reader = csv::ReaderBuilder::new().comment(Some(b'#')).from_reader(csv.as_slice());
+        assert_eq!(&reader.headers().unwrap()[2], "Nom du test");
+        let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), report.results().count());
+        let compression = &report.sequences[0].results[1];
+        assert_eq!(&rows[0][0], "a");
+        assert_eq!(&rows[1][1], "compression");
+        assert_eq!(&rows[1][2], compression.result.test_name);
+        assert_eq!(&rows[1][3], compression.verdict.label());
+        assert_eq!(rows[1][6].parse::<f64>().unwrap(), compression.result.p_value.unwrap());
+    }
+}
@@ -0,0 +1,381 @@
+use crate::types::TestResult;
+use serde::{Deserialize, Serialize};
+
+// Seuil de signification pour la longueur de la plus longue répétition
+const CYCLE_ALPHA: f64 = 0.01;
+// Marqueur de case vide dans le tableau des suffixes
+const EMPTY: u32 = u32::MAX;
+
+/// Résultat de la recherche de cycle et de la plus longue sous-séquence répétée.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct CycleReport {
+    /// Période détectée (en nombre de valeurs), si la fin de la séquence est périodique.
+    pub period: Option<usize>,
+    /// Indice à partir duquel la séquence devient périodique.
+    pub cycle_start: Option<usize>,
+    /// Méthode ayant établi la période ("brent" ou "tableau des suffixes").
+    pub method: Option<String>,
+    /// Longueur (en valeurs) de la plus longue sous-séquence apparaissant deux fois.
+    pub longest_repeat_length: usize,
+    /// Positions des deux occurrences de la plus longue répétition.
+    pub longest_repeat_offsets: Option<(usize, usize)>,
+    /// Probabilité (borne de Bonferroni) d'une répétition aussi longue pour des valeurs indépendantes.
+    pub p_value: f64,
+}
+
+/// Test de détection de cycle : recherche une période exacte dans la séquence de seeds.
+/// `seeds` : Tableau de seeds, considéré comme la sortie successive d'un générateur.
+/// Retourne : Une structure TestResult avec le `CycleReport` dans le champ `data`.
+pub fn test_cycle_detection(seeds: &[u64]) -> TestResult {
+    if seeds.len() < 2 {
+        return TestResult {
+            test_name: "Test de détection de cycle".to_string(),
+            passed: false,
+            score: 0.0,
+            details: "Erreur : Pas assez de seeds pour rechercher un cycle.".to_string(),
+            thresholds: None,
+            not_applicable: true,
+            ..Default::default()
+        };
+    }
+
+    let report = detect_cycle(seeds);
+    let passed = report.period.is_none() && report.p_value >= CYCLE_ALPHA;
+    let details = match (report.period, report.cycle_start) {
+        (Some(period), Some(start)) => format!(
+            "Période détectée : {} (à partir de l'indice {}, méthode : {}), Plus longue répétition : {}",
+            period,
+            start,
+            report.method.as_deref().unwrap_or("inconnue"),
+            report.longest_repeat_length
+        ),
+        _ => match report.longest_repeat_offsets {
+            Some((first, second)) => format!(
+                "Aucune période détectée, Plus longue répétition : {} valeurs aux positions {} et {}, p-value : {:.4}",
+                report.longest_repeat_length, first, second, report.p_value
+            ),
+            None => "Aucune période détectée, aucune valeur répétée".to_string(),
+        },
+    };
+
+    TestResult {
+        test_name: "Test de détection de cycle".to_string(),
+        passed,
+        score: report.period.unwrap_or(report.longest_repeat_length) as f64,
+        details,
+        thresholds: None,
+        p_value: Some(report.p_value),
+        data: serde_json::to_value(&report).ok(),
+        ..Default::default()
+    }
+}
+
+/// Recherche la période de la séquence puis sa plus longue sous-séquence répétée.
+/// `values` : La séquence à analyser.
+/// Retourne : Un `CycleReport`.
+///
+/// # Remarques
+/// * L'algorithme de Brent suppose que chaque valeur détermine la suivante (état = sortie) ;
+///   la période trouvée est vérifiée sur au moins deux cycles complets.
+/// * Sinon, le tableau des suffixes (SA-IS, temps linéaire) donne la plus longue répétition ;
+///   si sa seconde occurrence se prolonge jusqu'à la fin, la séquence est périodique.
+pub fn detect_cycle(values: &[u64]) -> CycleReport {
+    let n = values.len();
+    let mut report = CycleReport { p_value: 1.0, ..Default::default() };
+
+    if let Some((start, period)) = brent_period(values) {
+        report.period = Some(period);
+        report.cycle_start = Some(start);
+        report.method = Some("brent".to_string());
+        report.longest_repeat_length = n - start - period;
+        report.longest_repeat_offsets = Some((start, start + period));
+        report.p_value = 0.0;
+        return report;
+    }
+
+    // Rang de chaque valeur ; si toutes sont distinctes, aucune répétition n'est possible
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let collision_probability = collision_probability(&sorted);
+    sorted.dedup();
+    if sorted.len() == n {
+        return report;
+    }
+    let text: Vec<u32> = values
+        .iter()
+        .map(|value| sorted.binary_search(value).expect("Valeur présente") as u32)
+        .collect();
+    let upper = sorted.len() as u32 - 1;
+    drop(sorted);
+
+    let suffix_array = sa_is(&text, upper);
+    let (length, first, second) = longest_repeat(&text, &suffix_array);
+    if length == 0 {
+        return report;
+    }
+
+    report.longest_repeat_length = length;
+    report.longest_repeat_offsets = Some((first, second));
+    // P(répétition de longueur >= k) <= (nombre de paires de positions) * q^k
+    let pairs = n as f64 * (n as f64 - 1.0) / 2.0;
+    report.p_value = (pairs.ln() + length as f64 * collision_probability.ln()).exp().min(1.0);
+
+    if second + length == n && length >= second - first {
+        report.period = Some(second - first);
+        report.cycle_start = Some(first);
+        report.method = Some("tableau des suffixes".to_string());
+    }
+    report
+}
+
+/// Algorithme de Brent sur une séquence enregistrée.
+/// Retourne : `(début du cycle, période)` si la fin de la séquence répète au moins deux fois la période.
+fn brent_period(values: &[u64]) -> Option<(usize, usize)> {
+    let n = values.len();
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = values[0];
+    let mut hare = 1;
+    while hare < n && values[hare] != tortoise {
+        if power == lambda {
+            tortoise = values[hare];
+            power *= 2;
+            lambda = 0;
+        }
+        hare += 1;
+        lambda += 1;
+    }
+    if hare >= n {
+        return None;
+    }
+
+    // La relation x[i] = x[i + λ] doit tenir jusqu'à la fin ; on remonte pour trouver le début du cycle
+    if (hare..n).any(|i| values[i] != values[i - lambda]) {
+        return None;
+    }
+    let mut start = hare - lambda;
+    while start > 0 && values[start - 1] == values[start - 1 + lambda] {
+        start -= 1;
+    }
+    (n - start >= 2 * lambda).then_some((start, lambda))
+}
+
+/// Estimation sans biais de la probabilité que deux valeurs indépendantes soient égales.
+/// `sorted` : Valeurs triées.
+fn collision_probability(sorted: &[u64]) -> f64 {
+    let n = sorted.len() as f64;
+    let mut same_pairs = 0.0;
+    for run in sorted.chunk_by(|a, b| a == b) {
+        let count = run.len() as f64;
+        same_pairs += count * (count - 1.0);
+    }
+    same_pairs / (n * (n - 1.0))
+}
+
+/// Plus longue sous-séquence répétée à partir du tableau des suffixes (LCP de Kasai).
+/// Retourne : `(longueur, première position, seconde position)`.
+fn longest_repeat(text: &[u32], suffix_array: &[u32]) -> (usize, usize, usize) {
+    let n = text.len();
+    let mut rank = vec![0u32; n];
+    for (index, &suffix) in suffix_array.iter().enumerate() {
+        rank[suffix as usize] = index as u32;
+    }
+
+    let mut best = (0, 0, 0);
+    let mut lcp = 0usize;
+    for i in 0..n {
+        let r = rank[i] as usize;
+        if r == 0 {
+            lcp = 0;
+            continue;
+        }
+        let j = suffix_array[r - 1] as usize;
+        while i + lcp < n && j + lcp < n && text[i + lcp] == text[j + lcp] {
+            lcp += 1;
+        }
+        if lcp > best.0 {
+            best = (lcp, i.min(j), i.max(j));
+        }
+        lcp = lcp.saturating_sub(1);
+    }
+    best
+}
+
+/// Construit le tableau des suffixes par tri induit (SA-IS) en temps linéaire.
+/// `s` : Texte sur l'alphabet `0..=upper`.
+fn sa_is(s: &[u32], upper: u32) -> Vec<u32> {
+    let n = s.len();
+    match n {
+        0 => return Vec::new(),
+        1 => return vec![0],
+        2 => return if s[0] < s[1] { vec![0, 1] } else { vec![1, 0] },
+        _ => {}
+    }
+
+    let upper = upper as usize;
+    let mut sa = vec![EMPTY; n];
+    // `ls[i]` vaut vrai si le suffixe i est de type S (plus petit que le suffixe i + 1)
+    let mut ls = vec![false; n];
+    for i in (0..n - 1).rev() {
+        ls[i] = if s[i] == s[i + 1] { ls[i + 1] } else { s[i] < s[i + 1] };
+    }
+
+    let mut sum_l = vec![0usize; upper + 1];
+    let mut sum_s = vec![0usize; upper + 1];
+    for i in 0..n {
+        if !ls[i] {
+            sum_s[s[i] as usize] += 1;
+        } else if (s[i] as usize) < upper {
+            sum_l[s[i] as usize + 1] += 1;
+        }
+    }
+    for i in 0..=upper {
+        sum_s[i] += sum_l[i];
+        if i < upper {
+            sum_l[i + 1] += sum_s[i];
+        }
+    }
+
+    let induce = |lms: &[u32], sa: &mut [u32]| {
+        sa.fill(EMPTY);
+        let mut buffer = sum_s.clone();
+        for &d in lms {
+            let d = d as usize;
+            if d == n {
+                continue;
+            }
+            let c = s[d] as usize;
+            sa[buffer[c]] = d as u32;
+            buffer[c] += 1;
+        }
+        let mut buffer = sum_l.clone();
+        let c = s[n - 1] as usize;
+        sa[buffer[c]] = (n - 1) as u32;
+        buffer[c] += 1;
+        for i in 0..n {
+            let v = sa[i];
+            if v != EMPTY && v >= 1 && !ls[v as usize - 1] {
+                let c = s[v as usize - 1] as usize;
+                sa[buffer[c]] = v - 1;
+                buffer[c] += 1;
+            }
+        }
+        let mut buffer = sum_l.clone();
+        for i in (0..n).rev() {
+            let v = sa[i];
+            if v != EMPTY && v >= 1 && ls[v as usize - 1] {
+                let c = s[v as usize - 1] as usize + 1;
+                buffer[c] -= 1;
+                sa[buffer[c]] = v - 1;
+            }
+        }
+    };
+
+    let mut lms_map = vec![EMPTY; n + 1];
+    let mut lms = Vec::new();
+    for i in 1..n {
+        if !ls[i - 1] && ls[i] {
+            lms_map[i] = lms.len() as u32;
+            lms.push(i as u32);
+        }
+    }
+    let m = lms.len();
+    induce(&lms, &mut sa);
+
+    if m > 0 {
+        let mut sorted_lms: Vec<u32> = sa.iter().copied().filter(|&v| lms_map[v as usize] != EMPTY).collect();
+        let mut rec_s = vec![0u32; m];
+        let mut rec_upper = 0u32;
+        rec_s[lms_map[sorted_lms[0] as usize] as usize] = 0;
+        for i in 1..m {
+            let mut l = sorted_lms[i - 1] as usize;
+            let mut r = sorted_lms[i] as usize;
+            let end_l = if (lms_map[l] as usize) + 1 < m { lms[lms_map[l] as usize + 1] as usize } else { n };
+            let end_r = if (lms_map[r] as usize) + 1 < m { lms[lms_map[r] as usize + 1] as usize } else { n };
+            let mut same = true;
+            if end_l - l != end_r - r {
+                same = false;
+            } else {
+                while l < end_l && s[l] == s[r] {
+                    l += 1;
+                    r += 1;
+                }
+                if l == n || s[l] != s[r] {
+                    same = false;
+                }
+            }
+            if !same {
+                rec_upper += 1;
+            }
+            rec_s[lms_map[sorted_lms[i] as usize] as usize] = rec_upper;
+        }
+
+        let rec_sa = sa_is(&rec_s, rec_upper);
+        for (slot, &index) in sorted_lms.iter_mut().zip(&rec_sa) {
+            *slot = lms[index as usize];
+        }
+        induce(&sorted_lms, &mut sa);
+    }
+    sa
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn generate_random_seeds() -> Vec<u64> {
+        let mut rng = rand::thread_rng();
+        (0..1000).map(|_| rng.gen::<u64>()).collect()
+    }
+
+    #[test]
+    fn test_cycle_detection_with_random_seeds() {
+        let seeds = generate_random_seeds();
+        let result = test_cycle_detection(&seeds);
+        println!("{}", result.details);
+        assert!(result.passed, "Échec du test de détection de cycle : score = {:?}", result.score);
+    }
+
+    #[test]
+    fn test_cycle_detection_with_functional_generator() {
+        // Générateur dont la sortie est l'état : x -> x² + 1 mod 1009, qui entre dans un cycle
+        let mut state = 3u64;
+        let seeds: Vec<u64> = (0..1000)
+            .map(|_| {
+                state = (state * state + 1) % 1009;
+                state
+            })
+            .collect();
+        let report = detect_cycle(&seeds);
+        let period = report.period.expect("Période attendue");
+        let start = report.cycle_start.unwrap();
+        assert_eq!(report.method.as_deref(), Some("brent"));
+        assert!((start..1000 - period).all(|i| seeds[i] == seeds[i + period]));
+        assert!(!test_cycle_detection(&seeds).passed);
+    }
+
+    #[test]
+    fn test_cycle_detection_with_truncated_output() {
+        // Sortie tronquée (un seul bit de poids faible par mot, non fonctionnelle) répétée avec une période de 50
+        let mut rng = rand::thread_rng();
+        let block: Vec<u64> = (0..50).map(|_| rng.gen::<u64>() & 1).collect();
+        let mut seeds: Vec<u64> = (0..7).map(|_| rng.gen::<u64>() | 2).collect();
+        seeds.extend(block.iter().cycle().take(500));
+        let report = detect_cycle(&seeds);
+        assert_eq!(report.period, Some(50));
+        assert_eq!(report.method.as_deref(), Some("tableau des suffixes"));
+    }
+
+    #[test]
+    fn test_suffix_array_matches_naive_sort() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let len = rng.gen_range(1..200);
+            let text: Vec<u32> = (0..len).map(|_| rng.gen_range(0..4)).collect();
+            let mut naive: Vec<u32> = (0..len as u32).collect();
+            naive.sort_by(|&a, &b| text[a as usize..].cmp(&text[b as usize..]));
+            assert_eq!(sa_is(&text, 3), naive);
+        }
+    }
+}
@@ -0,0 +1,370 @@
+use crate::tests::bit_stream::{note_dropped_bits, BitStream};
+use crate::types::TestResult;
+use crate::tests::stats::{chi_square_p_value, seeds_to_bytes};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+// Nom du groupe de résultats, identique au nom de l'outil de référence
+const ENT_GROUP: &str = "ENT";
+// Bornes de la p-value du chi-square (mêmes repères que `ent` : < 1 % ou > 99 % = non aléatoire)
+const CHI_SQUARE_P_MIN: f64 = 0.01;
+const CHI_SQUARE_P_MAX: f64 = 0.99;
+// Nombre d'écarts-types tolérés pour la moyenne, Pi et la corrélation série
+const SIGMA_TOLERANCE: f64 = 3.0;
+// Nombre d'octets par point Monte Carlo (24 bits pour X, 24 bits pour Y), comme dans `ent`
+const MONTE_CARLO_BYTES: usize = 6;
+
+/// En-tête du format CSV concis de `ent -t`.
+pub const ENT_TERSE_HEADER: &str = "0,File-bytes,Entropy,Chi-square,Mean,Monte-Carlo-Pi,Serial-Correlation";
+
+/// Résumé des statistiques calculées par l'outil `ent` (Fourmilab) sur un flux d'octets.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EntSummary {
+    /// Nombre d'octets analysés.
+    pub total_bytes: usize,
+    /// Entropie en bits par octet.
+    pub entropy: f64,
+    /// Réduction de taille optimale (en %) obtenue par un compresseur idéal.
+    pub optimum_compression: f64,
+    /// Statistique du chi-square sur la distribution des 256 valeurs d'octets.
+    pub chi_square: f64,
+    /// Pourcentage de tirages aléatoires qui dépasseraient `chi_square`.
+    pub chi_square_exceedance: f64,
+    /// Moyenne arithmétique des octets (127.5 attendu).
+    pub mean: f64,
+    /// Estimation de Pi par la méthode de Monte Carlo.
+    pub monte_carlo_pi: f64,
+    /// Erreur relative (en %) de l'estimation de Pi.
+    pub monte_carlo_error: f64,
+    /// Nombre de points Monte Carlo utilisés.
+    pub monte_carlo_points: usize,
+    /// Coefficient de corrélation série entre octets successifs (0.0 attendu).
+    pub serial_correlation: f64,
+}
+
+impl EntSummary {
+    /// Calcule les statistiques ENT d'un flux d'octets.
+    /// `bytes` : Les octets à analyser.
+    /// Retourne : Un `EntSummary` (valeurs nulles si `bytes` est vide).
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let total = bytes.len();
+        let mut counts = [0usize; 256];
+        for &byte in bytes {
+            counts[byte as usize] += 1;
+        }
+
+        let n = total as f64;
+        let entropy = if total == 0 {
+            0.0
+        } else {
+            counts
+                .iter()
+                .filter(|&&count| count > 0)
+                .map(|&count| {
+                    let p = count as f64 / n;
+                    -p * p.log2()
+                })
+                .sum()
+        };
+
+        let expected = n / 256.0;
+        let chi_square = if total == 0 {
+            0.0
+        } else {
+            counts
+                .iter()
+                .map(|&count| {
+                    let diff = count as f64 - expected;
+                    diff * diff / expected
+                })
+                .sum()
+        };
+
+        let mean = if total == 0 {
+            0.0
+        } else {
+            bytes.iter().map(|&byte| byte as f64).sum::<f64>() / n
+        };
+
+        let (monte_carlo_pi, monte_carlo_points) = monte_carlo_pi(bytes);
+
+        EntSummary {
+            total_bytes: total,
+            entropy,
+            optimum_compression: (8.0 - entropy) / 8.0 * 100.0,
+            chi_square,
+            chi_square_exceedance: if total == 0 {
+                0.0
+            } else {
+                chi_square_p_value(chi_square, 255.0) * 100.0
+            },
+            mean,
+            monte_carlo_pi,
+            monte_carlo_error: (std::f64::consts::PI - monte_carlo_pi).abs() / std::f64::consts::PI * 100.0,
+            monte_carlo_points,
+            serial_correlation: serial_correlation(bytes),
+        }
+    }
+
+    /// Formate le résumé au format CSV concis de `ent -t`.
+    /// Retourne : L'en-tête et la ligne de valeurs, terminés par un saut de ligne.
+    pub fn to_terse_csv(&self) -> String {
+        format!("{}\n{}\n", ENT_TERSE_HEADER, self.to_terse_row())
+    }
+
+    /// Ligne de valeurs du format concis de `ent -t`, sans en-tête ni saut de ligne.
+    pub fn to_terse_row(&self) -> String {
+        format!(
+            "1,{},{:.6},{:.6},{:.6},{:.6},{:.6}",
+            self.total_bytes,
+            self.entropy,
+            self.chi_square,
+            self.mean,
+            self.monte_carlo_pi,
+            self.serial_correlation
+        )
+    }
+
+    /// Ligne du format concis de `ent -t` pour un flux de `total_bytes` octets sans résumé ENT (trop court
+    /// pour l'analyse) : la première colonne vaut `error` au lieu de `1` et les statistiques sont vides.
+    pub fn terse_error_row(total_bytes: usize) -> String {
+        format!("error,{},,,,,", total_bytes)
+    }
+
+    /// Convertit le résumé en une liste de `TestResult` regroupés sous le groupe "ENT".
+    pub fn to_test_results(&self) -> Vec<TestResult> {
+        let n = self.total_bytes as f64;
+        let group = Some(ENT_GROUP.to_string());
+
+        // Déficit d'entropie toléré : espérance + 3 écarts-types du chi-square à 255 ddl, ramené en bits
+        let entropy_deficit_max = (255.0 + SIGMA_TOLERANCE * 510f64.sqrt()) / (2.0 * n * std::f64::consts::LN_2);
+        let entropy_min = 8.0 - entropy_deficit_max;
+
+        let chi_square_p = self.chi_square_exceedance / 100.0;
+
+        // Écart-type de la moyenne d'octets uniformes sur [0, 255]
+        let mean_sigma = ((256.0 * 256.0 - 1.0) / 12.0 / n).sqrt();
+        let mean_tolerance = SIGMA_TOLERANCE * mean_sigma;
+
+        // Écart-type de l'estimation de Pi (loi binomiale de paramètre π/4)
+        let quarter_pi = std::f64::consts::FRAC_PI_4;
+        let pi_sigma = 4.0 * (quarter_pi * (1.0 - quarter_pi) / self.monte_carlo_points as f64).sqrt();
+        let pi_tolerance = SIGMA_TOLERANCE * pi_sigma;
+
+        let correlation_tolerance = SIGMA_TOLERANCE / n.sqrt();
+
+        vec![
+            TestResult {
+                test_name: "ENT : Entropie".to_string(),
+                passed: self.entropy >= entropy_min,
+                score: self.entropy,
+                details: format!(
+                    "Entropie : {:.6} bits par octet, compression optimale : {:.2} %",
+                    self.entropy, self.optimum_compression
+                ),
+                thresholds: Some((entropy_min, 8.0)),
+                group: group.clone(),
+                data: Some(json!({ "optimum_compression": self.optimum_compression })),
+                ..Default::default()
+            },
+            TestResult {
+                test_name: "ENT : Chi-square".to_string(),
+                passed: (CHI_SQUARE_P_MIN..=CHI_SQUARE_P_MAX).contains(&chi_square_p),
+                score: self.chi_square,
+                details: format!(
+                    "Chi-square : {:.2} pour {} octets, dépassé aléatoirement dans {:.2} % des cas",
+                    self.chi_square, self.total_bytes, self.chi_square_exceedance
+                ),
+                thresholds: Some((CHI_SQUARE_P_MIN, CHI_SQUARE_P_MAX)),
+                p_value: Some(chi_square_p),
+                group: group.clone(),
+                ..Default::default()
+            },
+            TestResult {
+                test_name: "ENT : Moyenne arithmétique".to_string(),
+                passed: (self.mean - 127.5).abs() <= mean_tolerance,
+                score: self.mean,
+                details: format!("Moyenne des octets : {:.4} (127.5 = aléatoire)", self.mean),
+                thresholds: Some((127.5 - mean_tolerance, 127.5 + mean_tolerance)),
+                group: group.clone(),
+                ..Default::default()
+            },
+            TestResult {
+                test_name: "ENT : Monte Carlo Pi".to_string(),
+                passed: (self.monte_carlo_pi - std::f64::consts::PI).abs() <= pi_tolerance,
+                score: self.monte_carlo_pi,
+                details: format!(
+                    "Valeur de Pi : {:.9} (erreur {:.2} %) sur {} points",
+                    self.monte_carlo_pi, self.monte_carlo_error, self.monte_carlo_points
+                ),
+                thresholds: Some((std::f64::consts::PI - pi_tolerance, std::f64::consts::PI + pi_tolerance)),
+                group: group.clone(),
+                data: Some(json!({ "error_percent": self.monte_carlo_error })),
+                ..Default::default()
+            },
+            TestResult {
+                test_name: "ENT : Corrélation série".to_string(),
+                passed: self.serial_correlation.abs() <= correlation_tolerance,
+                score: self.serial_correlation,
+                details: format!(
+                    "Coefficient de corrélation série : {:.6} (0.0 = non corrélé)",
+                    self.serial_correlation
+                ),
+                thresholds: Some((-correlation_tolerance, correlation_tolerance)),
+                group,
+                ..Default::default()
+            },
+        ]
+    }
+}
+
+/// Exécute l'analyse de type `ent` sur les octets des seeds.
+/// `seeds` : Tableau de seeds, lus octet par octet en little-endian.
+/// Retourne : Le groupe de `TestResult` ENT, avec le résumé complet dans le champ `data` du premier résultat.
+pub fn test_ent(seeds: &[u64]) -> Vec<TestResult> {
+    ent_results(&seeds_to_bytes(seeds))
+}
+
+/// Exécute l'analyse de type `ent` sur les octets d'un flux (voir `BitStream::bytes`).
+/// `stream` : Flux analysé ; les bits d'un octet ou d'un échantillon incomplet en fin de flux sont ignorés
+/// et signalés dans les détails du premier résultat.
+/// Retourne : Le groupe de `TestResult` ENT, avec le résumé complet dans le champ `data` du premier résultat.
+pub fn test_ent_stream(stream: &BitStream) -> Vec<TestResult> {
+    let bytes = stream.bytes();
+    let mut results = ent_results(&bytes);
+    let unit = if stream.sample_bits().is_multiple_of(8) { "échantillon" } else { "octet" };
+    note_dropped_bits(&mut results[0], stream.len_bits() - bytes.len() * 8, unit);
+    results
+}
+
+// Groupe de résultats ENT d'un flux d'octets
+fn ent_results(bytes: &[u8]) -> Vec<TestResult> {
+    if bytes.len() < MONTE_CARLO_BYTES {
+        return vec![TestResult {
+            test_name: "ENT".to_string(),
+            passed: false,
+            score: 0.0,
+            details: "Erreur : Pas assez de données pour l'analyse ENT.".to_string(),
+            thresholds: None,
+            group: Some(ENT_GROUP.to_string()),
+            not_applicable: true,
+            ..Default::default()
+        }];
+    }
+
+    let summary = EntSummary::from_bytes(bytes);
+    let mut results = summary.to_test_results();
+    if let Some(first) = results.first_mut() {
+        first.data = serde_json::to_value(&summary).ok();
+    }
+    results
+}
+
+/// Retrouve le résumé ENT enregistré par `test_ent` dans des résultats (rapport relu, exécution terminée).
+/// Retourne : Le résumé, ou `None` si l'analyse ENT n'a pas été exécutée ou n'a pas pu l'être.
+pub fn ent_summary(results: &[TestResult]) -> Option<EntSummary> {
+    results
+        .iter()
+        .filter(|result| result.group.as_deref() == Some(ENT_GROUP))
+        .find_map(|result| serde_json::from_value(result.data.clone()?).ok())
+}
+
+/// Estime Pi en plaçant des points (X, Y) de 24 bits dans un quart de cercle, comme `ent`.
+/// Retourne : L'estimation de Pi et le nombre de points utilisés.
+fn monte_carlo_pi(bytes: &[u8]) -> (f64, usize) {
+    let half = MONTE_CARLO_BYTES / 2;
+    let radius = 256f64.powi(half as i32) - 1.0;
+    let in_circle_limit = radius * radius;
+
+    let mut points = 0usize;
+    let mut inside = 0usize;
+    for chunk in bytes.chunks_exact(MONTE_CARLO_BYTES) {
+        let coordinate = |part: &[u8]| part.iter().fold(0.0, |acc, &byte| acc * 256.0 + byte as f64);
+        let x = coordinate(&chunk[..half]);
+        let y = coordinate(&chunk[half..]);
+        points += 1;
+        if x * x + y * y <= in_circle_limit {
+            inside += 1;
+        }
+    }
+
+    if points == 0 {
+        (0.0, 0)
+    } else {
+        (4.0 * inside as f64 / points as f64, points)
+    }
+}
+
+/// Calcule le coefficient de corrélation série entre octets successifs (circulaire, comme `ent`).
+fn serial_correlation(bytes: &[u8]) -> f64 {
+    if bytes.is_empty() {
+        return 0.0;
+    }
+
+    let n = bytes.len() as f64;
+    let mut sum_products = 0.0;
+    let mut sum = 0.0;
+    let mut sum_squares = 0.0;
+    for (i, &byte) in bytes.iter().enumerate() {
+        let current = byte as f64;
+        let next = bytes[(i + 1) % bytes.len()] as f64;
+        sum_products += current * next;
+        sum += current;
+        sum_squares += current * current;
+    }
+
+    let denominator = n * sum_squares - sum * sum;
+    if denominator == 0.0 {
+        -100_000.0 // Valeur sentinelle utilisée par `ent` pour une variance nulle
+    } else {
+        (n * sum_products - sum * sum) / denominator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn generate_random_seeds() -> Vec<u64> {
+        let mut rng = rand::thread_rng();
+        (0..1000).map(|_| rng.gen::<u64>()).collect()
+    }
+
+    #[test]
+    fn test_ent_with_random_seeds() {
+        let seeds = generate_random_seeds();
+        let results = test_ent(&seeds);
+        assert_eq!(results.len(), 5);
+        assert!(results.iter().all(|result| result.group.as_deref() == Some(ENT_GROUP)));
+        let summary: EntSummary = serde_json::from_value(results[0].data.clone().unwrap()).unwrap();
+        assert_eq!(summary.total_bytes, 8000);
+        assert!(summary.entropy > 7.9, "Entropie trop faible : {}", summary.entropy);
+    }
+
+    #[test]
+    fn test_ent_with_constant_bytes() {
+        let summary = EntSummary::from_bytes(&[0x41; 600]);
+        assert_eq!(summary.entropy, 0.0);
+        assert_eq!(summary.optimum_compression, 100.0);
+        assert_eq!(summary.mean, 65.0);
+        assert_eq!(summary.serial_correlation, -100_000.0);
+        assert!(summary.to_test_results().iter().all(|result| !result.passed));
+    }
+
+    #[test]
+    fn test_ent_terse_csv_format() {
+        let summary = EntSummary::from_bytes(&[0, 1, 2, 3, 4, 5]);
+        let csv = summary.to_terse_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "0,File-bytes,Entropy,Chi-square,Mean,Monte-Carlo-Pi,Serial-Correlation");
+        assert!(lines[1].starts_with("1,6,2.584963,"));
+
+        // Le résumé est relu depuis les résultats, y compris après un aller-retour JSON
+        let results = test_ent(&[0x0123_4567_89AB_CDEF, 42]);
+        let results: Vec<TestResult> = serde_json::from_str(&serde_json::to_string(&results).unwrap()).unwrap();
+        let summary = ent_summary(&results).unwrap();
+        assert_eq!(summary.total_bytes, 16);
+        assert!(ent_summary(&test_ent(&[])).is_none());
+    }
+}
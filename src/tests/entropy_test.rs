@@ -1,4 +1,5 @@
 use crate::types::TestResult; // Importation de TestResult pour structurer les résultats des tests
+use crate::tests::bit_stream::{BitStream, StreamAccumulator};
 
 // Constantes pour le Test d'Entropie, définissant les seuils de réussite
 const ENTROPY_TEST_MIN: f64 = 0.99; // Valeur minimale pour une entropie acceptable
@@ -8,8 +9,39 @@ const ENTROPY_TEST_MAX: f64 = 1.01; // Valeur maximale pour une entropie accepta
 /// `seeds` : Un tableau de seeds de type u64.
 /// Retourne : Une structure TestResult contenant les résultats du test d'entropie.
 pub fn test_entropy(seeds: &[u64]) -> TestResult {
-    let entropy = calculate_entropy(seeds); // Calcul de l'entropie
-    let passed = entropy >= ENTROPY_TEST_MIN && entropy <= ENTROPY_TEST_MAX;
+    test_entropy_stream(&BitStream::from_u64s(seeds))
+}
+
+/// Exécute le test d'entropie de Shannon sur un flux de bits de longueur quelconque.
+/// `stream` : Flux de bits à analyser.
+/// Retourne : Une structure TestResult contenant les résultats du test d'entropie.
+pub fn test_entropy_stream(stream: &BitStream) -> TestResult {
+    let mut accumulator = EntropyAccumulator::default();
+    accumulator.update(stream);
+    accumulator.finish()
+}
+
+/// Compteurs du test d'entropie, mis à jour morceau par morceau.
+#[derive(Debug, Clone, Default)]
+pub struct EntropyAccumulator {
+    ones: u64,
+    bits: u64,
+}
+
+impl StreamAccumulator for EntropyAccumulator {
+    fn update(&mut self, chunk: &BitStream) {
+        self.ones += chunk.count_ones();
+        self.bits += chunk.len_bits() as u64;
+    }
+
+    fn finish(&self) -> TestResult {
+        entropy_result(calculate_entropy(self.ones, self.bits))
+    }
+}
+
+/// Construit le résultat du test à partir de l'entropie calculée.
+fn entropy_result(entropy: f64) -> TestResult {
+    let passed = (ENTROPY_TEST_MIN..=ENTROPY_TEST_MAX).contains(&entropy);
 
     TestResult {
         test_name: "Test d'entropie de Shannon".to_string(),
@@ -17,22 +49,16 @@ pub fn test_entropy(seeds: &[u64]) -> TestResult {
         score: entropy,
         details: format!("Entropie calculée : {:.2}", entropy),
         thresholds: Some((ENTROPY_TEST_MIN, ENTROPY_TEST_MAX)),
+        ..Default::default()
     }
 }
 
-/// Calcule l'entropie de Shannon basée sur les bits dans les seeds.
-/// `seeds` : Tableau de seeds.
+/// Calcule l'entropie de Shannon basée sur les bits du flux.
+/// `ones` : Nombre de bits à 1.
+/// `bits` : Nombre total de bits.
 /// Retourne : L'entropie de Shannon calculée.
-fn calculate_entropy(seeds: &[u64]) -> f64 {
-    let mut count_ones = 0; // Compteur de bits à 1
-    let mut total_bits = 0; // Compteur total de bits
-
-    for seed in seeds {
-        count_ones += seed.count_ones(); // Comptabilise les bits à 1 dans chaque seed
-        total_bits += 64; // Chaque seed est un u64, donc 64 bits
-    }
-
-    let p = count_ones as f64 / total_bits as f64; // Probabilité d'observer un bit à 1
+fn calculate_entropy(ones: u64, bits: u64) -> f64 {
+    let p = ones as f64 / bits as f64; // Probabilité d'observer un bit à 1
     if p == 0.0 || p == 1.0 {
         0.0 // Cas limite pour éviter log2(0)
     } else {
@@ -1,8 +1,40 @@
+use crate::battery::{run_tests, BatteryConfig, BatteryTest};
+use crate::tests::time_seed_test::TimeSeedSearch;
 use crate::types::TestResult;
-use crate::tests::{
-    test_frequence, test_entropy, test_poker, test_periodicity, test_correlation,
-    test_sequence_length, test_collisions,
-};
+
+/// Tests de l'analyse de fichier, dans l'ordre des résultats (ENT en dernier, qui produit un groupe de
+/// résultats).
+///
+/// # Remarques
+/// * La recherche de graine temporelle n'en fait pas partie : elle essaie des dizaines de milliers de
+///   graines par générateur et n'est exécutée que sur demande (voir `file_analysis_config_with_time_seed`).
+pub const FILE_ANALYSIS_TESTS: [BatteryTest; 13] = [
+    BatteryTest::Frequency,
+    BatteryTest::Entropy,
+    BatteryTest::Poker,
+    BatteryTest::Periodicity,
+    BatteryTest::Correlation,
+    BatteryTest::SequenceLength,
+    BatteryTest::Collisions,
+    BatteryTest::Compression,
+    BatteryTest::Autocorrelation,
+    BatteryTest::CycleDetection,
+    BatteryTest::Predictability,
+    BatteryTest::StateRecovery,
+    BatteryTest::Ent,
+];
+
+/// Configuration de la batterie pour l'analyse de fichier : les tests de `FILE_ANALYSIS_TESTS`.
+pub fn file_analysis_config() -> BatteryConfig {
+    BatteryConfig::default().tests(&FILE_ANALYSIS_TESTS)
+}
+
+/// Configuration de l'analyse de fichier avec, avant ENT, la recherche de graine temporelle sur `search`.
+pub fn file_analysis_config_with_time_seed(search: TimeSeedSearch) -> BatteryConfig {
+    let (ent, tests) = FILE_ANALYSIS_TESTS.split_last().unwrap();
+    let tests = [tests, &[BatteryTest::TimeSeed, *ent]].concat();
+    BatteryConfig::default().tests(&tests).time_seed(search)
+}
 
 /// Analyse les données RNG issues d'un fichier.
 ///
@@ -14,29 +46,30 @@ use crate::tests::{
 ///
 /// # Remarques
 /// * Si le vecteur `data` est vide, retourne un `TestResult` avec une erreur.
+/// * Les tests sont exécutés en parallèle sur le parallélisme disponible (voir `analyze_rng_with_threads`).
 pub fn analyze_rng_from_file(data: Vec<u64>) -> Vec<TestResult> {
+    analyze_rng_with_threads(&data, 0)
+}
+
+/// Analyse les données RNG sur `threads` threads (0 : parallélisme disponible).
+///
+/// # Remarques
+/// * Les résultats sont dans le même ordre et identiques à ceux d'une exécution séquentielle.
+pub fn analyze_rng_with_threads(data: &[u64], threads: usize) -> Vec<TestResult> {
     if data.is_empty() {
-        return vec![TestResult {
-            test_name: "Analyse RNG".to_string(),
-            passed: false,
-            score: 0.0,
-            details: "Les données fournies sont vides.".to_string(),
-            thresholds: None,
-        }];
+        return vec![empty_data_result()];
     }
+    run_tests(data, &file_analysis_config().threads(threads)).results
+}
 
-    let tests: Vec<fn(&[u64]) -> TestResult> = vec![
-        test_frequence,
-        test_entropy,
-        test_poker,
-        test_periodicity,
-        test_correlation,
-        test_sequence_length,
-        test_collisions,
-    ];
-
-    tests
-        .iter()
-        .map(|test| test(&data)) // Applique chaque test à `data`
-        .collect() // Collecte les résultats dans un vecteur
+/// Résultat d'erreur de l'analyse d'un fichier sans valeur.
+pub fn empty_data_result() -> TestResult {
+    TestResult {
+        test_name: "Analyse RNG".to_string(),
+        passed: false,
+        score: 0.0,
+        details: "Les données fournies sont vides.".to_string(),
+        thresholds: None,
+        ..Default::default()
+    }
 }
@@ -1,3 +1,19 @@
+use crate::tests::BitStream;
+use crate::types::TestResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::num::{IntErrorKind, ParseIntError};
+use std::str::FromStr;
+
+// Tailles de mot acceptées pour les fichiers binaires bruts
+const RAW_WORD_BITS: [u32; 4] = [8, 16, 32, 64];
+// Nombre maximal d'enregistrements rejetés détaillés dans un rapport de lecture
+const MAX_REPORTED_REJECTIONS: usize = 10;
+// Longueur maximale de l'extrait d'un enregistrement rejeté, en caractères
+const MAX_EXCERPT_CHARS: usize = 40;
+// Séparateurs reconnus des fichiers délimités, par ordre de priorité
+const DELIMITERS: [char; 3] = ['\t', ';', ','];
+
 /// Parse un fichier CSV en un vecteur de valeurs RNG.
 /// 
 /// # Arguments
@@ -7,23 +23,978 @@
 /// Un vecteur de `u64` contenant les valeurs RNG parsées.
 ///
 /// # Remarques
-/// * Ignore les lignes vides ou mal formatées.
+/// * Ignore les lignes vides ou mal formatées (voir `parse_decimal` pour le détail des lignes rejetées).
 /// * Supprime les espaces et caractères inutiles autour des valeurs.
 pub fn parse_csv(file_content: String) -> Vec<u64> {
-    file_content
-        .lines() // Sépare le contenu du fichier par lignes.
-        .filter_map(|line| {
-            let trimmed = line.trim(); // Supprime les espaces inutiles.
-            if trimmed.is_empty() {
-                return None; // Ignore les lignes vides.
-            }
-            match trimmed.parse::<u64>() {
-                Ok(value) => Some(value), // Ajoute la valeur si le parsing réussit.
-                Err(_) => {
-                    eprintln!("Ligne ignorée : '{}', parsing échoué.", trimmed); // Journalise les erreurs.
-                    None // Ignore les lignes invalides.
-                }
+    parse_decimal(&file_content).values()
+}
+
+/// Enregistrement rejeté lors de la lecture d'un fichier.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RejectedRecord {
+    /// Numéro de ligne (à partir de 1).
+    pub line: usize,
+    /// Extrait de l'enregistrement.
+    pub content: String,
+    /// Raison du rejet.
+    pub reason: String,
+}
+
+/// Rapport de lecture d'un fichier : flux accepté et enregistrements rejetés.
+///
+/// # Remarques
+/// * Seuls les `MAX_REPORTED_REJECTIONS` premiers rejets sont détaillés ; `rejected` les compte tous.
+/// * Le flux n'est pas sérialisé : le rapport accompagne les résultats, il ne les remplace pas.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ParseReport {
+    /// Format effectivement lu (après détection automatique).
+    pub format: InputFormat,
+    /// Valeur de la colonne de regroupement, pour un fichier délimité lu par groupes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Flux accepté, avec sa longueur en bits et la largeur de ses échantillons (64 bits pour les formats
+    /// décimal, JSON et CSV, la taille des mots pour le binaire, 1 bit pour le format bits).
+    #[serde(skip)]
+    pub stream: BitStream,
+    /// Nombre d'enregistrements acceptés.
+    pub accepted: usize,
+    /// Nombre d'enregistrements rejetés.
+    pub rejected: usize,
+    /// Premiers enregistrements rejetés, dans l'ordre du fichier.
+    pub rejections: Vec<RejectedRecord>,
+}
+
+impl ParseReport {
+    /// Rapport d'une lecture sans rejet ; chaque échantillon du flux compte pour un enregistrement.
+    pub fn accepted(format: InputFormat, stream: BitStream) -> Self {
+        ParseReport { format, accepted: stream.len_samples(), stream, ..Default::default() }
+    }
+
+    /// Valeurs de 64 bits du flux accepté (voir `BitStream::words64`).
+    pub fn values(&self) -> Vec<u64> {
+        self.stream.words64()
+    }
+
+    fn accept(&mut self, value: u64) {
+        self.stream.push(value);
+        self.accepted += 1;
+    }
+
+    pub(crate) fn reject(&mut self, line: usize, content: &str, reason: String) {
+        self.rejected += 1;
+        if self.rejections.len() < MAX_REPORTED_REJECTIONS {
+            let mut excerpt: String = content.chars().take(MAX_EXCERPT_CHARS).collect();
+            if content.chars().count() > MAX_EXCERPT_CHARS {
+                excerpt.push('…');
             }
+            self.rejections.push(RejectedRecord { line, content: excerpt, reason });
+        }
+    }
+
+    /// Vrai si aucun enregistrement n'a été rejeté.
+    pub fn is_clean(&self) -> bool {
+        self.rejected == 0
+    }
+
+    /// Résumé lisible : compteurs puis premières lignes rejetées.
+    pub fn summary(&self) -> String {
+        let mut summary = match &self.group {
+            Some(group) => format!("Groupe « {} » : ", group),
+            None => String::new(),
+        };
+        summary.push_str(&format!(
+            "{} enregistrement(s) accepté(s), {} rejeté(s).",
+            self.accepted, self.rejected
+        ));
+        for rejection in &self.rejections {
+            summary.push_str(&format!(
+                " Ligne {} : « {} » {}.",
+                rejection.line, rejection.content, rejection.reason
+            ));
+        }
+        if self.rejected > self.rejections.len() {
+            let hidden = self.rejected - self.rejections.len();
+            summary.push_str(&format!(" ({} autre(s) rejet(s) non détaillé(s).)", hidden));
+        }
+        summary
+    }
+
+    /// Mode strict : refuse toute lecture comportant au moins un rejet.
+    ///
+    /// Retourne : Le rapport s'il est propre, sinon le résumé des rejets.
+    pub fn into_strict(self) -> Result<Self, String> {
+        if self.is_clean() {
+            Ok(self)
+        } else {
+            Err(format!("Lecture stricte refusée : {}", self.summary()))
+        }
+    }
+
+    /// Résultat « Lecture du fichier » à joindre aux résultats de l'analyse.
+    ///
+    /// # Remarques
+    /// * Le score est la proportion d'enregistrements acceptés ; le rapport complet est dans `data`.
+    pub fn to_test_result(&self) -> TestResult {
+        let total = self.accepted + self.rejected;
+        TestResult {
+            test_name: "Lecture du fichier".to_string(),
+            passed: self.is_clean(),
+            score: if total == 0 { 0.0 } else { self.accepted as f64 / total as f64 },
+            details: self.summary(),
+            thresholds: Some((1.0, 1.0)),
+            data: serde_json::to_value(self).ok(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Lit des entiers décimaux, un par ligne, en détaillant les lignes rejetées.
+/// `content` : Contenu du fichier ; les lignes vides sont ignorées.
+///
+/// Retourne : Le rapport de lecture.
+pub fn parse_decimal(content: &str) -> ParseReport {
+    let mut report = ParseReport { format: InputFormat::Decimal, ..Default::default() };
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        match trimmed.parse::<u64>() {
+            Ok(value) => report.accept(value),
+            Err(err) => report.reject(index + 1, trimmed, unsigned_error(&err, trimmed)),
+        }
+    }
+    report
+}
+
+/// Raison du rejet d'un entier décimal non signé.
+fn unsigned_error(err: &ParseIntError, text: &str) -> String {
+    match err.kind() {
+        IntErrorKind::PosOverflow => "dépasse la valeur maximale de 64 bits".to_string(),
+        _ if text.starts_with('-') => "est négatif".to_string(),
+        _ => "n'est pas un entier décimal".to_string(),
+    }
+}
+
+/// Lit un tableau JSON d'entiers, en détaillant les éléments rejetés.
+/// `content` : Contenu du fichier.
+///
+/// Retourne : Le rapport de lecture, ou une erreur si le JSON est invalide ou n'est pas un tableau.
+pub fn parse_json(content: &str) -> Result<ParseReport, String> {
+    let elements: Vec<serde_json::Value> =
+        serde_json::from_str(content).map_err(|err| format!("JSON invalide : {}", err))?;
+    let lines = json_element_lines(content);
+    let mut report = ParseReport { format: InputFormat::Json, ..Default::default() };
+    for (index, element) in elements.iter().enumerate() {
+        match element.as_u64() {
+            Some(value) => report.accept(value),
+            None => {
+                let reason = match element {
+                    serde_json::Value::Number(number) if number.is_i64() => "est négatif".to_string(),
+                    serde_json::Value::Number(_) => "n'est pas un entier de 64 bits".to_string(),
+                    _ => "n'est pas un nombre".to_string(),
+                };
+                report.reject(lines.get(index).copied().unwrap_or(1), &element.to_string(), reason);
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Numéro de ligne du début de chaque élément d'un tableau JSON (premier niveau seulement).
+fn json_element_lines(content: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let (mut line, mut depth) = (1, 0usize);
+    let (mut in_string, mut escaped, mut expecting) = (false, false, false);
+    for c in content.chars() {
+        if c == '\n' {
+            line += 1;
+        }
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        if c.is_whitespace() {
+            continue;
+        }
+        if depth == 1 && expecting && c != ']' {
+            lines.push(line);
+            expecting = false;
+        }
+        match c {
+            '"' => in_string = true,
+            '[' | '{' => {
+                depth += 1;
+                expecting = depth == 1;
+            }
+            ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 1 => expecting = true,
+            _ => {}
+        }
+    }
+    lines
+}
+
+/// Ordre des octets d'un mot binaire.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Endianness {
+    /// Octet de poids faible en premier (x86, `/dev/urandom` lu comme des entiers natifs).
+    #[default]
+    Little,
+    /// Octet de poids fort en premier.
+    Big,
+}
+
+impl FromStr for Endianness {
+    type Err = String;
+
+    /// Accepte `little` / `le` et `big` / `be`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "little" | "le" => Ok(Endianness::Little),
+            "big" | "be" => Ok(Endianness::Big),
+            _ => Err(format!("Boutisme inconnu : {} (attendu : little, big)", value)),
+        }
+    }
+}
+
+/// Ordre des bits dans chaque octet d'une capture binaire.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BitOrder {
+    /// Premier bit capturé en poids fort de l'octet (convention usuelle).
+    #[default]
+    MsbFirst,
+    /// Premier bit capturé en poids faible de l'octet : les bits de chaque octet sont inversés.
+    LsbFirst,
+}
+
+impl FromStr for BitOrder {
+    type Err = String;
+
+    /// Accepte `msb_first` / `msb` et `lsb_first` / `lsb`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "msb_first" | "msb" => Ok(BitOrder::MsbFirst),
+            "lsb_first" | "lsb" => Ok(BitOrder::LsbFirst),
+            _ => Err(format!("Ordre des bits inconnu : {} (attendu : msb_first, lsb_first)", value)),
+        }
+    }
+}
+
+/// Format d'un fichier binaire brut (`/dev/urandom`, capture de générateur matériel).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawFormat {
+    /// Taille des mots en bits : 8, 16, 32 ou 64.
+    pub word_bits: u32,
+    /// Ordre des octets dans chaque mot.
+    pub endianness: Endianness,
+    /// Ordre des bits dans chaque octet.
+    pub bit_order: BitOrder,
+}
+
+impl Default for RawFormat {
+    /// Mots de 64 bits petit-boutistes, bits dans l'ordre naturel.
+    fn default() -> Self {
+        RawFormat { word_bits: 64, endianness: Endianness::Little, bit_order: BitOrder::MsbFirst }
+    }
+}
+
+impl RawFormat {
+    /// Construit un format en vérifiant la taille des mots.
+    pub fn new(word_bits: u32, endianness: Endianness, bit_order: BitOrder) -> Result<Self, String> {
+        if !RAW_WORD_BITS.contains(&word_bits) {
+            return Err(format!("Taille de mot non supportée : {} bits (attendu : 8, 16, 32 ou 64)", word_bits));
+        }
+        Ok(RawFormat { word_bits, endianness, bit_order })
+    }
+}
+
+/// Parse un fichier binaire brut en un flux de mots.
+///
+/// # Arguments
+/// * `bytes` : Contenu brut du fichier.
+/// * `format` : Taille des mots, boutisme et ordre des bits.
+///
+/// # Retourne
+/// Le flux des mots lus, échantillons de `format.word_bits` bits, ou un message d'erreur.
+///
+/// # Remarques
+/// * Les mots gardent leur largeur : les tests sur les bits et les échantillons les lisent tels quels, les
+///   tests sur les mots les regroupent en valeurs de 64 bits, premier mot en poids faible (comme `next_u64`
+///   des générateurs 32 bits, voir `BitStream::words64`).
+/// * Un fichier dont la taille n'est pas un multiple de la taille des mots est refusé plutôt que tronqué.
+pub fn parse_raw(bytes: &[u8], format: &RawFormat) -> Result<BitStream, String> {
+    let format = RawFormat::new(format.word_bits, format.endianness, format.bit_order)?;
+    let word_bytes = (format.word_bits / 8) as usize;
+    if !bytes.len().is_multiple_of(word_bytes) {
+        return Err(format!(
+            "Taille de {} octets non multiple de {} : {} octet(s) ne forment pas un mot de {} bits complet.",
+            bytes.len(),
+            word_bytes,
+            bytes.len() % word_bytes,
+            format.word_bits
+        ));
+    }
+
+    let words = bytes.chunks_exact(word_bytes).map(|word| {
+        let word = word.iter().map(|&byte| match format.bit_order {
+            BitOrder::MsbFirst => byte,
+            BitOrder::LsbFirst => byte.reverse_bits(),
+        });
+        match format.endianness {
+            Endianness::Little => word.rev().fold(0u64, |acc, byte| (acc << 8) | byte as u64),
+            Endianness::Big => word.fold(0u64, |acc, byte| (acc << 8) | byte as u64),
+        }
+    });
+    Ok(BitStream::from_samples(words, format.word_bits))
+}
+
+/// Format d'un fichier texte de valeurs RNG.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InputFormat {
+    /// Détection automatique (voir `detect_format`).
+    #[default]
+    Auto,
+    /// Entiers décimaux, un par ligne (`parse_csv`).
+    Decimal,
+    /// Tableau JSON d'entiers.
+    Json,
+    /// Octets en hexadécimal, avec ou sans séparateurs (espaces, `:`, `-`, `,`, préfixes `0x`).
+    Hex,
+    /// Octets en base64 ou base64url, avec ou sans `=` final.
+    Base64,
+    /// Bits ASCII `0` / `1` (format des fichiers de données NIST), premier bit en poids fort.
+    Bits,
+    /// Fichier délimité (CSV, TSV) à plusieurs colonnes (voir `parse_delimited`).
+    #[serde(alias = "tsv")]
+    Csv,
+}
+
+impl FromStr for InputFormat {
+    type Err = String;
+
+    /// Lit l'identifiant d'un format (`auto`, `decimal`, `json`, `hex`, `base64`, `bits`, `csv` ou `tsv`).
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_ascii_lowercase())).map_err(|_| {
+            format!("Format inconnu : {} (attendu : auto, decimal, json, hex, base64, bits, csv)", value)
         })
-        .collect() // Collecte les valeurs valides dans un vecteur.
+    }
+}
+
+/// Encodage des valeurs de la colonne lue dans un fichier délimité.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueEncoding {
+    /// Entiers décimaux non signés de 64 bits.
+    #[default]
+    Unsigned,
+    /// Entiers décimaux signés de 64 bits, lus en complément à deux.
+    Signed,
+    /// Entiers hexadécimaux d'au plus 16 chiffres, avec ou sans préfixe `0x`.
+    Hex,
+}
+
+impl FromStr for ValueEncoding {
+    type Err = String;
+
+    /// Accepte `unsigned` / `u64`, `signed` / `i64` et `hex`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "unsigned" | "u64" => Ok(ValueEncoding::Unsigned),
+            "signed" | "i64" => Ok(ValueEncoding::Signed),
+            "hex" => Ok(ValueEncoding::Hex),
+            _ => Err(format!("Encodage inconnu : {} (attendu : unsigned, signed, hex)", value)),
+        }
+    }
+}
+
+impl ValueEncoding {
+    /// Lit une valeur de 64 bits.
+    ///
+    /// Retourne : La valeur, ou la raison du rejet.
+    pub fn parse(&self, text: &str) -> Result<u64, String> {
+        match self {
+            ValueEncoding::Unsigned => text.parse::<u64>().map_err(|err| unsigned_error(&err, text)),
+            ValueEncoding::Signed => text.parse::<i64>().map(|value| value as u64).map_err(|err| {
+                match err.kind() {
+                    IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                        "sort de l'intervalle des entiers signés de 64 bits".to_string()
+                    }
+                    _ => "n'est pas un entier décimal".to_string(),
+                }
+            }),
+            ValueEncoding::Hex => {
+                let digits = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")).unwrap_or(text);
+                if digits.len() > 16 && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err("dépasse 16 chiffres hexadécimaux".to_string());
+                }
+                u64::from_str_radix(digits, 16).map_err(|_| "n'est pas un entier hexadécimal".to_string())
+            }
+        }
+    }
+}
+
+/// Colonne d'un fichier délimité, désignée par son nom (ligne d'en-tête) ou son index.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnSelector {
+    /// Index de la colonne, à partir de 0.
+    Index(usize),
+    /// Nom de la colonne dans la ligne d'en-tête.
+    Name(String),
+}
+
+impl FromStr for ColumnSelector {
+    type Err = String;
+
+    /// Un entier désigne un index (à partir de 0), tout autre texte un nom de colonne.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err("Colonne vide.".to_string());
+        }
+        Ok(value.parse().map(ColumnSelector::Index).unwrap_or_else(|_| ColumnSelector::Name(value.to_string())))
+    }
+}
+
+impl ColumnSelector {
+    /// Index de la colonne dans une ligne de `width` champs, d'en-tête `header`.
+    fn resolve(&self, header: Option<&[String]>, width: usize) -> Result<usize, String> {
+        match self {
+            ColumnSelector::Index(index) if *index < width => Ok(*index),
+            ColumnSelector::Index(index) => {
+                Err(format!("Colonne {} absente : la première ligne n'a que {} champ(s).", index, width))
+            }
+            ColumnSelector::Name(name) => {
+                let header = header.ok_or_else(|| format!("Colonne « {} » demandée sans ligne d'en-tête.", name))?;
+                header.iter().position(|column| column == name).ok_or_else(|| {
+                    format!("Colonne « {} » introuvable (colonnes : {}).", name, header.join(", "))
+                })
+            }
+        }
+    }
+}
+
+/// Lit un séparateur de champs : un caractère, ou `tab` pour la tabulation.
+pub fn parse_delimiter(value: &str) -> Result<char, String> {
+    match value {
+        "tab" | "\\t" | "\t" => Ok('\t'),
+        _ => {
+            let mut chars = value.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c != '"' && !c.is_alphanumeric() => Ok(c),
+                _ => Err(format!("Séparateur invalide : « {} » (attendu : un caractère ou tab).", value)),
+            }
+        }
+    }
+}
+
+/// Lecture d'un fichier délimité (CSV, TSV).
+///
+/// # Remarques
+/// * Champs absents : séparateur détecté sur la première ligne (tabulation, `;` puis `,`), en-tête
+///   détecté (première ligne sans aucune valeur numérique, ou colonne désignée par son nom), colonne
+///   unique du fichier.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct DelimitedFormat {
+    /// Séparateur des champs.
+    pub delimiter: Option<char>,
+    /// Présence d'une ligne d'en-tête.
+    pub header: Option<bool>,
+    /// Colonne des valeurs à analyser.
+    pub column: Option<ColumnSelector>,
+    /// Encodage des valeurs.
+    pub encoding: ValueEncoding,
+    /// Colonne de regroupement : chaque valeur distincte forme un flux analysé séparément.
+    pub group_by: Option<ColumnSelector>,
+}
+
+/// Découpe une ligne délimitée en champs ; les champs entre guillemets peuvent contenir le séparateur
+/// et des guillemets doublés.
+fn split_fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let (mut field, mut quoted) = (String::new(), false);
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted || field.trim().is_empty() => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+/// Séparateur d'une ligne délimitée : le premier de `DELIMITERS` présent dans la ligne.
+fn detect_delimiter(line: &str) -> Option<char> {
+    DELIMITERS.into_iter().find(|&delimiter| line.contains(delimiter))
+}
+
+/// Vrai si la ligne ressemble à un en-tête : aucun champ n'est un entier décimal ni, pour l'encodage
+/// hexadécimal, une valeur hexadécimale.
+fn looks_like_header(fields: &[String], encoding: ValueEncoding) -> bool {
+    fields.iter().all(|field| {
+        field.parse::<i64>().is_err()
+            && field.parse::<u64>().is_err()
+            && (encoding != ValueEncoding::Hex || ValueEncoding::Hex.parse(field).is_err())
+    })
+}
+
+/// Lit une colonne d'un fichier délimité, éventuellement par groupes.
+/// `content` : Contenu du fichier.
+/// `format` : Séparateur, en-tête, colonne, encodage et regroupement.
+///
+/// Retourne : Un rapport de lecture par groupe, dans l'ordre de première apparition (un seul rapport,
+/// sans groupe, si `group_by` est absent), ou une erreur si la colonne ne peut pas être déterminée.
+///
+/// # Remarques
+/// * Une ligne trop courte ou dont la valeur est invalide est rejetée avec son numéro de ligne ; une
+///   ligne sans colonne de regroupement est rejetée dans le groupe de clé vide.
+pub fn parse_delimited(content: &str, format: &DelimitedFormat) -> Result<Vec<ParseReport>, String> {
+    let mut lines = content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).peekable();
+    let first = lines.peek().map(|(_, line)| *line).ok_or_else(|| "Le contenu est vide.".to_string())?;
+    let delimiter = format.delimiter.or_else(|| detect_delimiter(first)).unwrap_or(',');
+    let first_fields = split_fields(first, delimiter);
+
+    let by_name = |selector: &Option<ColumnSelector>| matches!(selector, Some(ColumnSelector::Name(_)));
+    let has_header = format.header.unwrap_or_else(|| {
+        by_name(&format.column) || by_name(&format.group_by) || looks_like_header(&first_fields, format.encoding)
+    });
+    let header = if has_header {
+        lines.next();
+        Some(first_fields.as_slice())
+    } else {
+        None
+    };
+
+    let column = match &format.column {
+        Some(selector) => selector.resolve(header, first_fields.len())?,
+        None if first_fields.len() == 1 => 0,
+        None => {
+            let columns = header.map_or(format!("index 0 à {}", first_fields.len() - 1), |names| names.join(", "));
+            return Err(format!(
+                "Fichier à {} colonnes : choisissez la colonne à analyser ({}).",
+                first_fields.len(),
+                columns
+            ));
+        }
+    };
+    let group_column =
+        format.group_by.as_ref().map(|selector| selector.resolve(header, first_fields.len())).transpose()?;
+
+    let new_report = |group: Option<String>| ParseReport { format: InputFormat::Csv, group, ..Default::default() };
+    let mut reports = Vec::new();
+    let mut groups: HashMap<String, usize> = HashMap::new();
+    if group_column.is_none() {
+        reports.push(new_report(None));
+    }
+
+    for (index, line) in lines {
+        let fields = split_fields(line, delimiter);
+        let report = match group_column {
+            None => &mut reports[0],
+            Some(group_column) => {
+                let key = fields.get(group_column).cloned().unwrap_or_default();
+                let position = *groups.entry(key.clone()).or_insert_with(|| {
+                    reports.push(new_report(Some(key)));
+                    reports.len() - 1
+                });
+                &mut reports[position]
+            }
+        };
+        let needed = column.max(group_column.unwrap_or(0)) + 1;
+        if fields.len() < needed {
+            report.reject(index + 1, line.trim(), format!("n'a que {} champ(s) sur {}", fields.len(), needed));
+            continue;
+        }
+        match format.encoding.parse(&fields[column]) {
+            Ok(value) => report.accept(value),
+            Err(reason) => report.reject(index + 1, line.trim(), reason),
+        }
+    }
+    Ok(reports)
+}
+
+/// Devine le format d'un contenu texte.
+///
+/// # Remarques
+/// * Ordre des essais : JSON (`[` initial), bits (uniquement `0` / `1`, au moins 64), fichier délimité
+///   avec en-tête, décimal (au moins la moitié des lignes sont des entiers de 64 bits, les autres étant
+///   rejetées à la lecture), fichier délimité sans en-tête dont la première ligne n'a que des entiers
+///   décimaux, hexadécimal, autre fichier délimité sans en-tête, puis base64.
+/// * Des octets hexadécimaux séparés par des virgules et faits de chiffres (`01,02,03`) sont donc lus
+///   comme un fichier CSV : le format hexadécimal doit alors être demandé explicitement.
+/// * Un fichier délimité a au moins deux champs sur sa première ligne, et autant sur au moins la moitié
+///   des lignes.
+/// * Un contenu ambigu (par exemple des octets hexadécimaux composés uniquement de chiffres) peut être
+///   lu avec un format explicite.
+pub fn detect_format(content: &str) -> Result<InputFormat, String> {
+    let trimmed = content.trim();
+    if trimmed.is_empty() {
+        return Err("Le contenu est vide.".to_string());
+    }
+    if trimmed.starts_with('[') {
+        return Ok(InputFormat::Json);
+    }
+
+    let significant: Vec<char> = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+    if significant.len() >= 64 && significant.iter().all(|&c| c == '0' || c == '1') {
+        return Ok(InputFormat::Bits);
+    }
+    let lines: Vec<&str> = trimmed.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+    let delimited = detect_delimiter(lines[0]).filter(|&delimiter| {
+        let width = split_fields(lines[0], delimiter).len();
+        let same_width = lines.iter().filter(|line| split_fields(line, delimiter).len() == width).count();
+        width >= 2 && same_width * 2 >= lines.len()
+    });
+    if let Some(delimiter) = delimited {
+        if looks_like_header(&split_fields(lines[0], delimiter), ValueEncoding::Unsigned) {
+            return Ok(InputFormat::Csv);
+        }
+    }
+    if lines.iter().filter(|line| line.parse::<u64>().is_ok()).count() * 2 >= lines.len() {
+        return Ok(InputFormat::Decimal);
+    }
+    // Un CSV numérique sans en-tête (ex. `horodatage,jeton`) passerait aussi pour des octets hexadécimaux
+    if let Some(delimiter) = delimited {
+        if split_fields(lines[0], delimiter).iter().all(|field| field.parse::<u64>().is_ok()) {
+            return Ok(InputFormat::Csv);
+        }
+    }
+    if hex_tokens(trimmed).all(|token| token.chars().all(|c| c.is_ascii_hexdigit())) {
+        return Ok(InputFormat::Hex);
+    }
+    if delimited.is_some() {
+        return Ok(InputFormat::Csv);
+    }
+    if significant.iter().all(|&c| c.is_ascii_alphanumeric() || "+/-_=".contains(c)) {
+        return Ok(InputFormat::Base64);
+    }
+    Err("Format non reconnu : attendu décimal, JSON, CSV, hexadécimal, base64 ou bits 0/1.".to_string())
+}
+
+/// Parse un contenu texte en valeurs de 64 bits, les enregistrements invalides étant ignorés.
+///
+/// # Arguments
+/// * `content` : Contenu du fichier.
+/// * `format` : Format du contenu, ou `InputFormat::Auto` pour le détecter.
+/// * `raw` : Lecture des octets décodés des formats hexadécimal et base64 (voir `parse_raw`).
+///
+/// # Retourne
+/// Le flux lu, ou un message d'erreur.
+///
+/// # Remarques
+/// * Les octets hexadécimaux et base64 doivent former des mots complets de `raw.word_bits` bits, et un
+///   chiffre hexadécimal isolé est refusé : rien n'est complété ni tronqué. Le format bits accepte une
+///   longueur quelconque.
+pub fn parse_text(content: &str, format: InputFormat, raw: &RawFormat) -> Result<BitStream, String> {
+    parse_text_report(content, format, raw).map(|report| report.stream)
+}
+
+/// Parse un contenu texte en détaillant les enregistrements rejetés (voir `parse_text`).
+///
+/// # Retourne
+/// Le rapport de lecture, ou un message d'erreur si le contenu ne peut pas être lu du tout.
+///
+/// # Remarques
+/// * Les formats décimal, JSON et CSV rejettent des enregistrements isolés (une ligne, un élément) ; les
+///   formats hexadécimal, base64 et bits décrivent un flux d'octets continu, où toute erreur est fatale.
+/// * Un fichier CSV est lu avec `DelimitedFormat::default()` (voir `parse_text_groups` pour choisir la
+///   colonne et regrouper les lignes).
+pub fn parse_text_report(content: &str, format: InputFormat, raw: &RawFormat) -> Result<ParseReport, String> {
+    let format = match format {
+        InputFormat::Auto => detect_format(content)?,
+        format => format,
+    };
+    match format {
+        InputFormat::Csv => Ok(parse_delimited(content, &DelimitedFormat::default())?.remove(0)),
+        InputFormat::Auto | InputFormat::Decimal => Ok(parse_decimal(content)),
+        InputFormat::Json => parse_json(content),
+        InputFormat::Hex => Ok(ParseReport::accepted(format, parse_raw(&decode_hex(content)?, raw)?)),
+        InputFormat::Base64 => Ok(ParseReport::accepted(format, parse_raw(&decode_base64(content)?, raw)?)),
+        InputFormat::Bits => Ok(ParseReport::accepted(format, parse_bits(content)?)),
+    }
+}
+
+/// Parse un contenu texte en un ou plusieurs flux (voir `parse_text_report`).
+/// `delimited` : Lecture des fichiers CSV ; seul le format CSV produit plusieurs flux (`group_by`).
+///
+/// Retourne : Un rapport de lecture par flux, ou un message d'erreur.
+pub fn parse_text_groups(
+    content: &str,
+    format: InputFormat,
+    raw: &RawFormat,
+    delimited: &DelimitedFormat,
+) -> Result<Vec<ParseReport>, String> {
+    let format = match format {
+        InputFormat::Auto => detect_format(content)?,
+        format => format,
+    };
+    match format {
+        InputFormat::Csv => parse_delimited(content, delimited),
+        format => Ok(vec![parse_text_report(content, format, raw)?]),
+    }
+}
+
+/// Jetons hexadécimaux d'un contenu, séparateurs et préfixes `0x` retirés.
+fn hex_tokens(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .split(|c: char| c.is_whitespace() || matches!(c, ':' | '-' | ','))
+        .filter(|token| !token.is_empty())
+        .map(|token| token.strip_prefix("0x").or_else(|| token.strip_prefix("0X")).unwrap_or(token))
+}
+
+/// Décode des octets hexadécimaux ; chaque jeton doit contenir un nombre pair de chiffres.
+fn decode_hex(content: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        for token in hex_tokens(line) {
+            if !token.len().is_multiple_of(2) {
+                return Err(format!(
+                    "Ligne {} : jeton hexadécimal « {} » de longueur impaire : 4 bits en trop.",
+                    index + 1,
+                    token
+                ));
+            }
+            for pair in token.as_bytes().chunks_exact(2) {
+                let byte = std::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or_else(|| format!("Ligne {} : caractère non hexadécimal dans « {} ».", index + 1, token))?;
+                bytes.push(byte);
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+/// Décode du base64 standard ou base64url, espaces ignorés et `=` final facultatif.
+fn decode_base64(content: &str) -> Result<Vec<u8>, String> {
+    let symbols: Vec<char> = content.chars().filter(|c| !c.is_whitespace()).collect();
+    let data_len = symbols.iter().rposition(|&c| c != '=').map_or(0, |last| last + 1);
+    if symbols.len() - data_len > 2 || (symbols.len() > data_len && !symbols.len().is_multiple_of(4)) {
+        return Err("Remplissage base64 (`=`) invalide.".to_string());
+    }
+    if data_len % 4 == 1 {
+        return Err("Longueur base64 invalide : 6 bits en trop.".to_string());
+    }
+
+    let mut bytes = Vec::with_capacity(data_len * 3 / 4);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for &symbol in &symbols[..data_len] {
+        let value = match symbol {
+            'A'..='Z' => symbol as u32 - 'A' as u32,
+            'a'..='z' => symbol as u32 - 'a' as u32 + 26,
+            '0'..='9' => symbol as u32 - '0' as u32 + 52,
+            '+' | '-' => 62,
+            '/' | '_' => 63,
+            _ => return Err(format!("Caractère base64 invalide : « {} ».", symbol)),
+        };
+        buffer = (buffer << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(bytes)
+}
+
+/// Lit des bits ASCII `0` / `1`, en nombre quelconque, dans l'ordre du fichier.
+fn parse_bits(content: &str) -> Result<BitStream, String> {
+    let mut stream = BitStream::new(1);
+    for (index, line) in content.lines().enumerate() {
+        for c in line.chars().filter(|c| !c.is_whitespace()) {
+            let bit = match c {
+                '0' => 0,
+                '1' => 1,
+                _ => {
+                    return Err(format!(
+                        "Ligne {} : caractère « {} » inattendu : seuls 0 et 1 sont acceptés.",
+                        index + 1,
+                        c
+                    ))
+                }
+            };
+            stream.push(bit);
+        }
+    }
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_raw_formats() {
+        let bytes: Vec<u8> = (1..=16).collect();
+        let little = parse_raw(&bytes, &RawFormat::default()).unwrap();
+        assert_eq!(little, BitStream::from_u64s(&[0x0807_0605_0403_0201, 0x100F_0E0D_0C0B_0A09]));
+
+        let big = RawFormat::new(64, Endianness::Big, BitOrder::MsbFirst).unwrap();
+        assert_eq!(parse_raw(&bytes, &big).unwrap().words64()[0], 0x0102_0304_0506_0708);
+
+        // Mots de 32 bits gros-boutistes, gardés à 32 bits ; les tests sur les mots les lisent premier mot en
+        // poids faible
+        let big32 = RawFormat::new(32, "be".parse().unwrap(), BitOrder::MsbFirst).unwrap();
+        let stream = parse_raw(&bytes, &big32).unwrap();
+        assert_eq!((stream.sample_bits(), stream.len_samples()), (32, 4));
+        assert_eq!(stream.samples().next(), Some(0x0102_0304));
+        assert_eq!(stream.words64()[0], 0x0506_0708_0102_0304);
+
+        // Les octets seuls ne dépendent pas du boutisme ; l'ordre des bits inverse chaque octet
+        let bytes8 = RawFormat::new(8, Endianness::Big, "lsb".parse().unwrap()).unwrap();
+        assert_eq!(parse_raw(&bytes, &bytes8).unwrap().words64()[0], 0x10E0_60A0_20C0_4080);
+        // Des octets isolés n'ont pas à former des valeurs de 64 bits
+        let odd = parse_raw(&bytes[..5], &bytes8).unwrap();
+        assert_eq!((odd.len_bits(), odd.sample_bits()), (40, 8));
+        assert_eq!(ParseReport::accepted(InputFormat::Auto, odd).accepted, 5);
+    }
+
+    #[test]
+    fn test_parse_raw_rejects_invalid_input() {
+        assert!(RawFormat::new(24, Endianness::Little, BitOrder::MsbFirst).is_err());
+        assert!("middle".parse::<Endianness>().is_err());
+        let error = parse_raw(&[0u8; 12], &RawFormat::default()).unwrap_err();
+        assert!(error.contains("4 octet(s)"), "{}", error);
+        let words16 = RawFormat::new(16, Endianness::Little, BitOrder::MsbFirst).unwrap();
+        assert!(parse_raw(&[0u8; 3], &words16).unwrap_err().contains("mot de 16 bits"));
+    }
+
+    #[test]
+    fn test_detect_and_parse_text_formats() {
+        let raw = RawFormat::default();
+        let expected = BitStream::from_u64s(&[0x0807_0605_0403_0201]);
+        assert_eq!(detect_format("01 02 03 04 05 06 07 08"), Ok(InputFormat::Hex));
+        assert_eq!(parse_text("0x01:02:03:04 0x05-06-07-08", InputFormat::Auto, &raw).unwrap(), expected);
+        // Des octets hexadécimaux faits de chiffres sont ambigus : le format explicite l'emporte
+        assert_eq!(detect_format("0102030405060708"), Ok(InputFormat::Decimal));
+        assert_eq!(parse_text("0102030405060708", InputFormat::Hex, &raw).unwrap(), expected);
+        assert_eq!(detect_format("AQIDBAUGBwg="), Ok(InputFormat::Base64));
+        assert_eq!(parse_text("AQIDBAUGBwg=", InputFormat::Auto, &raw).unwrap(), expected);
+        assert_eq!(parse_text("AQID BAUG Bwg", InputFormat::Base64, &raw).unwrap(), expected);
+        // base64url : les symboles 62 et 63 s'écrivent `-` et `_` au lieu de `+` et `/`
+        let url = parse_text("-_-_-_-_-_-", InputFormat::Auto, &raw).unwrap();
+        assert_eq!(url.len_samples(), 1);
+        assert_eq!(url, parse_text("+/+/+/+/+/+", InputFormat::Base64, &raw).unwrap());
+
+        let bits = format!("{}\n{}", "1".repeat(32), "0".repeat(31) + "1");
+        assert_eq!(detect_format(&bits), Ok(InputFormat::Bits));
+        assert_eq!(parse_text(&bits, InputFormat::Auto, &raw).unwrap().words64(), vec![0xFFFF_FFFF_0000_0001]);
+        assert_eq!(parse_text("[1, 2]", InputFormat::Auto, &raw).unwrap(), BitStream::from_u64s(&[1, 2]));
+        assert_eq!(parse_text("12\n34", InputFormat::Auto, &raw).unwrap(), BitStream::from_u64s(&[12, 34]));
+        assert_eq!("base64".parse::<InputFormat>(), Ok(InputFormat::Base64));
+    }
+
+    #[test]
+    fn test_unaligned_text_is_rejected() {
+        let raw = RawFormat::default();
+        // Les bits sont gardés en nombre quelconque ; seuls les octets doivent former des mots complets
+        let bits = parse_text(&"1".repeat(70), InputFormat::Bits, &raw).unwrap();
+        assert_eq!((bits.len_bits(), bits.sample_bits(), bits.count_ones()), (70, 1, 70));
+        let bytes8 = RawFormat::new(8, Endianness::Little, BitOrder::MsbFirst).unwrap();
+        assert_eq!(parse_text("010203", InputFormat::Hex, &bytes8).unwrap().len_bits(), 24);
+        assert!(parse_text("0102030405060708a", InputFormat::Hex, &raw).unwrap_err().contains("4 bits"));
+        assert!(parse_text("010203", InputFormat::Hex, &raw).unwrap_err().contains("3 octet(s)"));
+        assert!(parse_text("AQIDBAUGBwgJ", InputFormat::Base64, &raw).is_err());
+        assert!(parse_text("AQIDB", InputFormat::Base64, &raw).unwrap_err().contains("6 bits"));
+        assert!(detect_format("valeurs : ?!").is_err());
+    }
+
+    #[test]
+    fn test_parse_report_details_rejected_lines() {
+        let report = parse_decimal("12\n\nabc\n-5\n18446744073709551616\n 34 \n");
+        assert_eq!(report.values(), vec![12, 34]);
+        assert_eq!((report.accepted, report.rejected), (2, 3));
+        let lines: Vec<usize> = report.rejections.iter().map(|rejection| rejection.line).collect();
+        assert_eq!(lines, vec![3, 4, 5]);
+        assert!(report.rejections[1].reason.contains("négatif"));
+        assert!(report.rejections[2].reason.contains("maximale"));
+        assert!(report.clone().into_strict().unwrap_err().contains("Ligne 3 : « abc »"));
+        assert_eq!(report.to_test_result().score, 0.4);
+
+        let json = "[1,\n  \"x\",\n  -2, 3.5,\n  4]";
+        let report = parse_text_report(json, InputFormat::Auto, &RawFormat::default()).unwrap();
+        assert_eq!(report.values(), vec![1, 4]);
+        let lines: Vec<usize> = report.rejections.iter().map(|rejection| rejection.line).collect();
+        assert_eq!(lines, vec![2, 3, 3]);
+        assert!(parse_json("[1, 2").unwrap_err().contains("JSON invalide"));
+        assert!(parse_json("{\"a\": 1}").is_err());
+
+        // Seuls les premiers rejets sont détaillés
+        let report = parse_decimal(&"x\n".repeat(25));
+        assert_eq!((report.rejected, report.rejections.len()), (25, MAX_REPORTED_REJECTIONS));
+        assert!(report.summary().contains("15 autre(s)"));
+        assert!(parse_decimal("1\n2").into_strict().is_ok());
+        assert_eq!(detect_format("12\nabc\n34\n-1"), Ok(InputFormat::Decimal));
+        assert!(parse_text("0102030405060708\n0z", InputFormat::Hex, &RawFormat::default())
+            .unwrap_err()
+            .starts_with("Ligne 2"));
+    }
+
+    #[test]
+    fn test_parse_delimited_columns_and_groups() {
+        let csv = "timestamp,user,token\n1700000000,alice,0xFF\n1700000001,bob,10\n\
+                   1700000002,alice,zz\n1700000003,bob\n";
+        assert_eq!(detect_format(csv), Ok(InputFormat::Csv));
+        let error = parse_text(csv, InputFormat::Auto, &RawFormat::default()).unwrap_err();
+        assert!(error.contains("timestamp, user, token"), "{}", error);
+
+        let format = DelimitedFormat {
+            column: Some("token".parse().unwrap()),
+            encoding: ValueEncoding::Hex,
+            group_by: Some(ColumnSelector::Index(1)),
+            ..Default::default()
+        };
+        let groups = parse_text_groups(csv, InputFormat::Auto, &RawFormat::default(), &format).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!((groups[0].group.as_deref(), groups[0].values()), (Some("alice"), vec![0xFF]));
+        assert_eq!(groups[0].rejections[0].line, 4);
+        assert_eq!((groups[1].group.as_deref(), groups[1].values()), (Some("bob"), vec![0x10]));
+        assert!(groups[1].rejections[0].reason.contains("2 champ(s) sur 3"));
+
+        // TSV sans en-tête, valeurs signées, guillemets
+        let tsv = "1\t-1\n\"2\"\t9223372036854775807\n3\t9223372036854775808\n";
+        let format = DelimitedFormat {
+            column: Some(ColumnSelector::Index(1)),
+            encoding: ValueEncoding::Signed,
+            ..Default::default()
+        };
+        let report = parse_delimited(tsv, &format).unwrap().remove(0);
+        assert_eq!(report.values(), vec![u64::MAX, i64::MAX as u64]);
+        assert_eq!((report.rejected, report.rejections[0].line), (1, 3));
+        assert_eq!(split_fields("a,\"b,\"\"c\"\"\",d", ','), vec!["a", "b,\"c\"", "d"]);
+        let missing = DelimitedFormat { column: Some("c".parse().unwrap()), ..Default::default() };
+        assert!(parse_delimited("a;b\n1;2", &missing).unwrap_err().contains("introuvable"));
+        assert_eq!(parse_delimiter("tab"), Ok('\t'));
+        assert_eq!("tsv".parse::<InputFormat>(), Ok(InputFormat::Csv));
+        // Une colonne unique reste lue comme des lignes décimales
+        assert_eq!(detect_format("1\n2\n3"), Ok(InputFormat::Decimal));
+        // CSV numérique sans en-tête : des colonnes de même largeur, pas des octets hexadécimaux
+        let logs = "1700000000,12345678\n1700000001,87654321\n1700000002,5\n";
+        assert_eq!(detect_format(logs), Ok(InputFormat::Csv));
+        assert_eq!(detect_format("12345678,87654321"), Ok(InputFormat::Csv));
+        let error = parse_text(logs, InputFormat::Auto, &RawFormat::default()).unwrap_err();
+        assert!(error.contains("index 0 à 1"), "{}", error);
+        let token = DelimitedFormat { column: Some(ColumnSelector::Index(1)), ..Default::default() };
+        let groups = parse_text_groups(logs, InputFormat::Auto, &RawFormat::default(), &token).unwrap();
+        assert_eq!(groups[0].values(), vec![12345678, 87654321, 5]);
+        assert_eq!(detect_format("01,ff,02"), Ok(InputFormat::Hex));
+    }
 }
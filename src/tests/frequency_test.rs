@@ -1,4 +1,5 @@
 use crate::types::TestResult; // Importation de TestResult pour structurer les résultats des tests
+use crate::tests::bit_stream::{BitStream, StreamAccumulator};
 
 // Constantes pour le Test de Fréquence, définissant les seuils de réussite
 const FREQUENCY_TEST_MIN: f64 = 0.45; // Fréquence minimale acceptable des '1'
@@ -8,8 +9,39 @@ const FREQUENCY_TEST_MAX: f64 = 0.55; // Fréquence maximale acceptable des '1'
 /// `seeds` : Un tableau de seeds de type u64.
 /// Retourne : Une structure TestResult avec les résultats du test de fréquence.
 pub fn test_frequence(seeds: &[u64]) -> TestResult {
-    let frequency = calculate_frequency(seeds); // Calcul de la fréquence des '1'
-    let passed = frequency >= FREQUENCY_TEST_MIN && frequency <= FREQUENCY_TEST_MAX;
+    test_frequence_stream(&BitStream::from_u64s(seeds))
+}
+
+/// Exécute le test de fréquence des bits sur un flux de longueur quelconque.
+/// `stream` : Flux de bits à analyser.
+/// Retourne : Une structure TestResult avec les résultats du test de fréquence.
+pub fn test_frequence_stream(stream: &BitStream) -> TestResult {
+    let mut accumulator = FrequencyAccumulator::default();
+    accumulator.update(stream);
+    accumulator.finish()
+}
+
+/// Compteurs du test de fréquence, mis à jour morceau par morceau.
+#[derive(Debug, Clone, Default)]
+pub struct FrequencyAccumulator {
+    ones: u64,
+    bits: u64,
+}
+
+impl StreamAccumulator for FrequencyAccumulator {
+    fn update(&mut self, chunk: &BitStream) {
+        self.ones += chunk.count_ones();
+        self.bits += chunk.len_bits() as u64;
+    }
+
+    fn finish(&self) -> TestResult {
+        frequency_result(calculate_frequency(self.ones, self.bits))
+    }
+}
+
+/// Construit le résultat du test à partir de la fréquence des '1'.
+fn frequency_result(frequency: f64) -> TestResult {
+    let passed = (FREQUENCY_TEST_MIN..=FREQUENCY_TEST_MAX).contains(&frequency);
 
     TestResult {
         test_name: "Test de fréquence des bits".to_string(),
@@ -17,16 +49,17 @@ pub fn test_frequence(seeds: &[u64]) -> TestResult {
         score: frequency,
         details: format!("Fréquence des '1' : {:.2}%", frequency * 100.0),
         thresholds: Some((FREQUENCY_TEST_MIN, FREQUENCY_TEST_MAX)),
+        ..Default::default()
     }
 }
 
-/// Calcule la fréquence des bits '1' dans les seeds.
-/// `seeds` : Tableau de seeds.
+/// Calcule la fréquence des bits '1'.
+/// `ones` : Nombre de bits '1'.
+/// `bits` : Nombre total de bits.
 /// Retourne : La fréquence des '1' calculée.
-fn calculate_frequency(seeds: &[u64]) -> f64 {
-    // Somme des bits '1' dans chaque seed, divisé par le nombre total de bits
-    let count = seeds.iter().map(|seed| seed.count_ones() as u64).sum::<u64>();
-    count as f64 / (seeds.len() * 64) as f64
+fn calculate_frequency(ones: u64, bits: u64) -> f64 {
+    // Nombre de bits '1', divisé par le nombre total de bits
+    ones as f64 / bits as f64
 }
 
 #[cfg(test)]
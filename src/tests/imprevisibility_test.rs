@@ -19,6 +19,7 @@ pub fn test_imprevisibility(seeds: &[u64]) -> TestResult {
             entropy, IMPREVISIBILITY_THRESHOLD
         ),
         thresholds: Some((IMPREVISIBILITY_THRESHOLD, 1.0)),
+        ..Default::default()
     }
 }
 
@@ -0,0 +1,141 @@
+use crate::types::{TestResult, Verdict};
+
+/// Génère un rapport JUnit XML, pour les tableaux de bord d'intégration continue.
+/// `suites` : Nom et résultats de chaque suite analysée (une `testsuite` par suite, par exemple une par
+/// groupe d'un fichier CSV).
+/// Retourne : Le document XML ; chaque `TestResult` est un `testcase`.
+///
+/// # Remarques
+/// * Un résultat rejeté est un `failure` de type `weak` ou `fail` (voir `Verdict`), dont le message donne
+///   le score, les seuils et la p-value, et le contenu les détails.
+/// * Un test qui n'a pas pu s'appliquer aux données (`TestResult::not_applicable`) est `skipped`.
+pub fn generate_junit_report(suites: &[(&str, &[TestResult])]) -> String {
+    let total: usize = suites.iter().map(|(_, results)| results.len()).sum();
+    let total_failures: usize = suites.iter().map(|(_, results)| failures(results)).sum();
+    let total_skipped: usize = suites.iter().map(|(_, results)| skipped(results)).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"seed_tester\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\">\n",
+        total, total_failures, total_skipped
+    ));
+    for (name, results) in suites {
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\">\n",
+            escape_xml(name),
+            results.len(),
+            failures(results),
+            skipped(results)
+        ));
+        for result in results.iter() {
+            xml.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"seed_tester.{}\"",
+                escape_xml(&result.test_name),
+                escape_xml(name)
+            ));
+            if result.passed {
+                xml.push_str(" />\n");
+            } else if result.not_applicable {
+                xml.push_str(&format!(">\n      <skipped message=\"{}\" />\n    </testcase>\n", escape_xml(&result.details)));
+            } else {
+                let verdict = match result.verdict() {
+                    Verdict::Weak => "weak",
+                    _ => "fail",
+                };
+                xml.push_str(&format!(
+                    ">\n      <failure type=\"{}\" message=\"{}\">{}</failure>\n    </testcase>\n",
+                    verdict,
+                    escape_xml(&failure_message(result)),
+                    escape_xml(&result.details)
+                ));
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Nombre de résultats rejetés, hors tests non applicables.
+fn failures(results: &[TestResult]) -> usize {
+    results.iter().filter(|result| !result.passed && !result.not_applicable).count()
+}
+
+/// Nombre de tests non applicables.
+fn skipped(results: &[TestResult]) -> usize {
+    results.iter().filter(|result| result.not_applicable).count()
+}
+
+/// Résumé d'un rejet : verdict, score, seuils et p-value.
+fn failure_message(result: &TestResult) -> String {
+    let mut message = format!("{} : score {}", result.verdict().label(), result.score);
+    if let Some((low, high)) = result.thresholds {
+        message.push_str(&format!(", seuils [{}, {}]", low, high));
+    }
+    if let Some(p_value) = result.p_value {
+        message.push_str(&format!(", p-value {:e}", p_value));
+    }
+    message
+}
+
+/// Échappe un texte pour un attribut ou un contenu XML (les caractères de contrôle interdits en XML 1.0
+/// sont retirés).
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\t' | '\r' => escaped.push(character),
+            character if character.is_control() => {}
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_junit_failures_skips_and_escaping() {
+        let results = vec![
+            TestResult { test_name: "Test de fréquence des bits".to_string(), passed: true, ..Default::default() },
+            TestResult {
+                test_name: "Test de compression".to_string(),
+                passed: false,
+                score: 0.7,
+                details: "Ratio < attendu & \"gzip\"".to_string(),
+                thresholds: Some((0.9, 1.0)),
+                p_value: Some(0.002),
+                ..Default::default()
+            },
+            TestResult {
+                test_name: "Test de détection de cycle".to_string(),
+                passed: false,
+                details: "Erreur : Pas assez de seeds pour rechercher un cycle.".to_string(),
+                not_applicable: true,
+                ..Default::default()
+            },
+        ];
+
+        let xml = generate_junit_report(&[("générateur mt19937", &results)]);
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"seed_tester\" tests=\"3\" failures=\"1\" errors=\"0\" skipped=\"1\">"));
+        assert!(xml.contains("<testcase name=\"Test de fréquence des bits\" classname=\"seed_tester.générateur mt19937\" />"));
+        assert!(xml.contains(
+            "<failure type=\"weak\" message=\"FAIBLE : score 0.7, seuils [0.9, 1], p-value 2e-3\">Ratio &lt; attendu &amp; &quot;gzip&quot;</failure>"
+        ));
+        assert!(xml.contains("<skipped message=\"Erreur : Pas assez de seeds pour rechercher un cycle.\" />"));
+        assert_eq!(xml.matches("<testsuite ").count(), 1);
+        assert!(xml.ends_with("</testsuite>\n</testsuites>\n"));
+
+        // Seul le marqueur du test compte, pas le texte des détails
+        let error = TestResult { details: "Erreur : lecture impossible".to_string(), ..Default::default() };
+        assert_eq!((failures(std::slice::from_ref(&error)), skipped(&[error])), (1, 0));
+    }
+}
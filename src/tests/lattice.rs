@@ -0,0 +1,126 @@
+//! Réduction de réseaux euclidiens (LLL entier) utilisée par les attaques sur générateurs tronqués.
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+/// Réduit une base de réseau par l'algorithme LLL entier (Cohen, algorithme 2.6.7, δ = 3/4).
+/// `basis` : Vecteurs de la base (lignes), linéairement indépendants, réduits sur place.
+///
+/// # Remarques
+/// * Tous les calculs sont exacts (entiers multiprécision), sans nombre flottant.
+/// * Si la base est liée, elle est laissée partiellement réduite.
+pub fn lll_reduce(basis: &mut [Vec<BigInt>]) {
+    let n = basis.len();
+    if n < 2 {
+        return;
+    }
+
+    // Indices 1..=n comme dans l'algorithme de référence ; d[0] = 1
+    let dot = |a: &[BigInt], b: &[BigInt]| a.iter().zip(b).fold(BigInt::zero(), |acc, (x, y)| acc + x * y);
+    let mut d = vec![BigInt::zero(); n + 1];
+    let mut lambda = vec![vec![BigInt::zero(); n + 1]; n + 1];
+    d[0] = BigInt::one();
+    d[1] = dot(&basis[0], &basis[0]);
+
+    let mut k = 2;
+    let mut k_max = 1;
+    while k <= n {
+        if k > k_max {
+            k_max = k;
+            for j in 1..=k {
+                let mut u = dot(&basis[k - 1], &basis[j - 1]);
+                for i in 1..j {
+                    u = (&d[i] * &u - &lambda[k][i] * &lambda[j][i]) / &d[i - 1];
+                }
+                if j < k {
+                    lambda[k][j] = u;
+                } else {
+                    if u.is_zero() {
+                        return; // Vecteurs liés
+                    }
+                    d[k] = u;
+                }
+            }
+        }
+
+        loop {
+            reduce(basis, &mut lambda, &d, k, k - 1);
+            let left = BigInt::from(4) * &d[k] * &d[k - 2];
+            let right = BigInt::from(3) * &d[k - 1] * &d[k - 1] - BigInt::from(4) * &lambda[k][k - 1] * &lambda[k][k - 1];
+            if left < right {
+                swap(basis, &mut lambda, &mut d, k, k_max);
+                k = (k - 1).max(2);
+            } else {
+                for l in (1..k - 1).rev() {
+                    reduce(basis, &mut lambda, &d, k, l);
+                }
+                k += 1;
+                break;
+            }
+        }
+    }
+}
+
+/// Étape RED(k, l) : réduit b_k par un multiple entier de b_l.
+#[allow(clippy::needless_range_loop)] // Indices identiques à l'algorithme de référence
+fn reduce(basis: &mut [Vec<BigInt>], lambda: &mut [Vec<BigInt>], d: &[BigInt], k: usize, l: usize) {
+    let two_lambda = BigInt::from(2) * &lambda[k][l];
+    if two_lambda.abs() <= d[l] {
+        return;
+    }
+
+    // q = arrondi(λ_kl / d_l)
+    let q = (&two_lambda + &d[l]).div_floor(&(BigInt::from(2) * &d[l]));
+    let (head, tail) = basis.split_at_mut(k - 1);
+    for (target, source) in tail[0].iter_mut().zip(&head[l - 1]) {
+        *target -= &q * source;
+    }
+    lambda[k][l] = &lambda[k][l] - &q * &d[l];
+    for i in 1..l {
+        let correction = &q * &lambda[l][i];
+        lambda[k][i] -= correction;
+    }
+}
+
+/// Étape SWAPI(k) : échange b_k et b_(k-1) et met à jour les coefficients de Gram-Schmidt entiers.
+#[allow(clippy::needless_range_loop)] // Indices identiques à l'algorithme de référence
+fn swap(basis: &mut [Vec<BigInt>], lambda: &mut [Vec<BigInt>], d: &mut [BigInt], k: usize, k_max: usize) {
+    basis.swap(k - 1, k - 2);
+    for j in 1..k - 1 {
+        let tmp = lambda[k][j].clone();
+        lambda[k][j] = lambda[k - 1][j].clone();
+        lambda[k - 1][j] = tmp;
+    }
+
+    let l = lambda[k][k - 1].clone();
+    let b = (&d[k - 2] * &d[k] + &l * &l) / &d[k - 1];
+    for i in k + 1..=k_max {
+        let t = lambda[i][k].clone();
+        lambda[i][k] = (&d[k] * &lambda[i][k - 1] - &l * &t) / &d[k - 1];
+        lambda[i][k - 1] = (&b * &t + &l * &lambda[i][k]) / &d[k];
+    }
+    d[k - 1] = b;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lll_finds_short_vector() {
+        // Réseau {(x, y) : y ≡ 1000 x mod 1_000_003}, de déterminant m
+        let m = 1_000_003i64;
+        let mut basis = vec![
+            vec![BigInt::from(1), BigInt::from(1000)],
+            vec![BigInt::from(0), BigInt::from(m)],
+        ];
+        lll_reduce(&mut basis);
+        let norm = |v: &Vec<BigInt>| v.iter().map(|x| x * x).fold(BigInt::zero(), |acc, x| acc + x);
+        // Le premier vecteur réduit est au plus de l'ordre de sqrt(4/3 · m)
+        assert!(norm(&basis[0]) <= BigInt::from(4 * m / 3 + 1));
+        // Le réseau est conservé : déterminant |det| = m
+        let det = &basis[0][0] * &basis[1][1] - &basis[0][1] * &basis[1][0];
+        assert_eq!(det.abs(), BigInt::from(m));
+    }
+}
@@ -8,10 +8,23 @@ pub mod block_test;
 pub mod imprevisibility_test;
 pub mod periodicity_advanced_test;
 pub mod collision_test;
+pub mod ent_test;
+pub mod compression_test;
+pub mod autocorrelation_test;
+pub mod cycle_detection_test;
+pub mod predictability_test;
+pub mod state_recovery_test;
+pub mod time_seed_test;
 pub mod pdf_generator;
+pub mod csv_generator;
+pub mod junit_generator;
+pub mod tap_generator;
 pub mod analysis;
 pub mod file_analysis;
 pub mod file_parser;
+pub mod stats;
+pub mod bit_stream;
+pub mod lattice;
 
 // Regroupement logique des exports pour une meilleure lisibilité
 // Export des fonctions de tests RNG
@@ -25,9 +38,29 @@ pub use self::block_test::test_blocs;
 pub use self::imprevisibility_test::test_imprevisibility;
 pub use self::periodicity_advanced_test::test_periodicity_advanced;
 pub use self::collision_test::test_collisions;
+pub use self::ent_test::{test_ent, test_ent_stream};
+pub use self::compression_test::{test_compression, test_compression_stream};
+pub use self::autocorrelation_test::{
+    test_autocorrelation, test_autocorrelation_stream, test_autocorrelation_with_lags, LagRange,
+};
+pub use self::cycle_detection_test::test_cycle_detection;
+pub use self::predictability_test::test_predictability;
+pub use self::state_recovery_test::test_state_recovery;
+pub use self::time_seed_test::{test_time_seed, test_time_seed_with_search};
 
 // Export des outils supplémentaires
+pub use self::bit_stream::{note_dropped_bits, BitStream, GroupCarry, StreamAccumulator};
 pub use self::pdf_generator::generate_pdf_report;
+pub use self::csv_generator::generate_csv_report;
+pub use self::junit_generator::generate_junit_report;
+pub use self::tap_generator::generate_tap_report;
 pub use self::analysis::analyze_seed_patterns;
-pub use self::file_analysis::analyze_rng_from_file;
-pub use self::file_parser::parse_csv;
+pub use self::file_analysis::{
+    analyze_rng_from_file, analyze_rng_with_threads, empty_data_result, file_analysis_config,
+    file_analysis_config_with_time_seed, FILE_ANALYSIS_TESTS,
+};
+pub use self::file_parser::{
+    detect_format, parse_csv, parse_decimal, parse_delimited, parse_delimiter, parse_json, parse_raw, parse_text,
+    parse_text_groups, parse_text_report, BitOrder,
+    ColumnSelector, DelimitedFormat, Endianness, InputFormat, ParseReport, RawFormat, RejectedRecord, ValueEncoding,
+};
@@ -4,13 +4,19 @@ use printpdf::*;
 use std::fs::File;
 #[cfg(feature = "pdf_export")]
 use std::io::{BufWriter, Cursor};
-use crate::types::TestResult;
+use crate::report::Report;
+#[cfg(feature = "pdf_export")]
+use crate::types::Verdict;
+
+// Ordonnée sous laquelle les résultats continuent sur une nouvelle page, en millimètres
+#[cfg(feature = "pdf_export")]
+const PAGE_BOTTOM_MM: f64 = 20.0;
 
-/// Génère un rapport PDF basé sur les résultats des tests RNG.
-/// `test_results` : Une référence à un vecteur contenant les résultats des tests.
+/// Génère un rapport PDF d'une exécution : ses métadonnées, puis les résultats de chaque suite.
+/// `report` : Rapport de l'exécution.
 /// Retourne : Un `Vec<u8>` représentant les données du fichier PDF ou une erreur.
 #[cfg(feature = "pdf_export")]
-pub fn generate_pdf_report(test_results: &[TestResult]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+pub fn generate_pdf_report(report: &Report) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     println!("Début de la génération du PDF...");
 
     // Chemin absolu vers la police
@@ -19,7 +25,7 @@ pub fn generate_pdf_report(test_results: &[TestResult]) -> Result<Vec<u8>, Box<d
 
     // Création d'un nouveau document PDF
     let (doc, page1, layer1) = PdfDocument::new("Rapport de Tests RNG", Mm(210.0), Mm(297.0), "Layer 1");
-    let current_layer = doc.get_page(page1).get_layer(layer1);
+    let mut current_layer = doc.get_page(page1).get_layer(layer1);
 
     // Chargement de la police
     let font = doc.add_external_font(File::open(font_path)?)?;
@@ -38,46 +44,73 @@ pub fn generate_pdf_report(test_results: &[TestResult]) -> Result<Vec<u8>, Box<d
         &font,
     );
 
+    // Métadonnées de l'exécution
+    let metadata = &report.metadata;
+    let summary = &report.summary;
+    let lines = [
+        format!("Exécution : {}", metadata.id.as_deref().unwrap_or("-")),
+        format!("Entrée : {} ({} valeurs)", metadata.input.description, metadata.num_values),
+        format!("Outil : {} {} ; début : {}", report.tool.name, report.tool.version, metadata.started_at),
+        format!(
+            "Verdict : {} ({} réussi(s), {} faible(s), {} échec(s))",
+            summary.verdict.label(),
+            summary.passed,
+            summary.weak,
+            summary.failed
+        ),
+    ];
+    current_layer.set_font(&font, 10.0);
+    for (index, line) in lines.iter().enumerate() {
+        current_layer.use_text(line.as_str(), 10.0, Mm(12.0), Mm(260.0 - 6.0 * index as f64), &font);
+    }
+
     // En-tête du tableau
     current_layer.set_font(&font, 16.0);
-    current_layer.use_text("Résultats des Tests RNG", 16.0, Mm(10.0), Mm(250.0), &font);
+    current_layer.use_text("Résultats des Tests RNG", 16.0, Mm(10.0), Mm(230.0), &font);
 
     // Ligne pour démarquer l’en-tête
-    draw_line(&current_layer, Mm(10.0), Mm(248.0), Mm(200.0), Mm(248.0));
-
-    // Affichage des résultats dans un tableau
-    let mut y_offset = 240.0;
-    for result in test_results.iter() {
-        let color = if result.passed {
-            Color::Rgb(Rgb::new(0.0, 0.5, 0.0, None)) // Vert pour réussi
-        } else {
-            Color::Rgb(Rgb::new(0.5, 0.0, 0.0, None)) // Rouge pour échoué
-        };
-
-        // Nom du test
-        current_layer.set_font(&font, 12.0);
-        current_layer.set_fill_color(color.clone());
-        current_layer.use_text(&result.test_name, 12.0, Mm(12.0), Mm(y_offset), &font);
-
-        // Résultat
-        current_layer.use_text(
-            if result.passed { "Réussi" } else { "Échoué" },
-            12.0,
-            Mm(80.0),
-            Mm(y_offset),
-            &font,
-        );
-
-        // Score
-        current_layer.use_text(
-            format!("{:.2}", result.score),
-            12.0,
-            Mm(150.0),
-            Mm(y_offset),
-            &font,
-        );
-
-        y_offset -= 10.0; // Décalage vertical pour la prochaine ligne
+    draw_line(&current_layer, Mm(10.0), Mm(228.0), Mm(200.0), Mm(228.0));
+
+    // Affichage des résultats dans un tableau, sur autant de pages que nécessaire
+    let mut y_offset = 220.0;
+    for sequence in &report.sequences {
+        for reported in &sequence.results {
+            if y_offset < PAGE_BOTTOM_MM {
+                let (page, layer) = doc.add_page(Mm(210.0), Mm(297.0), "Layer 1");
+                current_layer = doc.get_page(page).get_layer(layer);
+                draw_border(&current_layer, Mm(10.0), Mm(10.0), Mm(200.0), Mm(287.0));
+                y_offset = 275.0;
+            }
+            let result = &reported.result;
+            let color = match reported.verdict {
+                Verdict::Pass => Color::Rgb(Rgb::new(0.0, 0.5, 0.0, None)), // Vert pour réussi
+                Verdict::Weak => Color::Rgb(Rgb::new(0.6, 0.4, 0.0, None)), // Orange pour faible
+                Verdict::Fail => Color::Rgb(Rgb::new(0.5, 0.0, 0.0, None)), // Rouge pour échoué
+            };
+            let name = match &sequence.group {
+                Some(group) => format!("[{}] {}", group, result.test_name),
+                None => result.test_name.clone(),
+            };
+
+            // Nom du test
+            current_layer.set_font(&font, 12.0);
+            current_layer.set_fill_color(color.clone());
+            current_layer.use_text(name, 12.0, Mm(12.0), Mm(y_offset), &font);
+
+            // Verdict
+            current_layer.use_text(reported.verdict.label(), 12.0, Mm(120.0), Mm(y_offset), &font);
+
+            // Score
+            current_layer.use_text(
+                format!("{:.2}", result.score),
+                12.0,
+                Mm(150.0),
+                Mm(y_offset),
+                &font,
+            );
+
+            y_offset -= 10.0; // Décalage vertical pour la prochaine ligne
+        }
     }
 
     println!("Ajout des résultats terminé. Sauvegarde du PDF...");
@@ -91,7 +124,7 @@ pub fn generate_pdf_report(test_results: &[TestResult]) -> Result<Vec<u8>, Box<d
 
 /// Stub pour `generate_pdf_report` si `pdf_export` n'est pas activé.
 #[cfg(not(feature = "pdf_export"))]
-pub fn generate_pdf_report(_test_results: &[TestResult]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+pub fn generate_pdf_report(_report: &Report) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     Err("La génération PDF n'est pas activée. Activez la fonctionnalité 'pdf_export' pour utiliser cette fonction.".into())
 }
 
@@ -19,6 +19,7 @@ pub fn test_periodicity_advanced(seeds: &[u64]) -> TestResult {
             unique_patterns, max_repeats, PERIODICITY_THRESHOLD
         ),
         thresholds: Some((0.0, PERIODICITY_THRESHOLD as f64)),
+        ..Default::default()
     }
 }
 
@@ -30,7 +31,7 @@ fn calculate_pattern_repeats(seeds: &[u64]) -> (usize, usize) {
 
     let mut pattern_counts = HashMap::new();
     for window in seeds.windows(3) {
-        let pattern = format!("{:?}", window);
+        let pattern = (window[0], window[1], window[2]); // Clé sans allocation
         *pattern_counts.entry(pattern).or_insert(0) += 1;
     }
 
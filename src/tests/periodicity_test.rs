@@ -1,4 +1,5 @@
 use crate::types::TestResult; // Importation de TestResult pour structurer les résultats des tests
+use crate::tests::bit_stream::{BitStream, StreamAccumulator};
 
 // Constantes pour le Test de Périodicité, définissant les seuils de réussite
 const PERIODICITY_TEST_MIN: f64 = 0.3; // Taux de répétition minimal acceptable
@@ -8,8 +9,47 @@ const PERIODICITY_TEST_MAX: f64 = 0.7; // Taux de répétition maximal acceptabl
 /// `seeds` : Un tableau de seeds de type u64.
 /// Retourne : Une structure TestResult avec les résultats du test de périodicité.
 pub fn test_periodicity(seeds: &[u64]) -> TestResult {
-    let periodicity = calculate_periodicity(seeds); // Calcul de la périodicité des bits
-    let passed = periodicity >= PERIODICITY_TEST_MIN && periodicity <= PERIODICITY_TEST_MAX;
+    test_periodicity_stream(&BitStream::from_u64s(seeds))
+}
+
+/// Exécute le test de périodicité entre échantillons consécutifs d'un flux, à leur largeur réelle.
+/// `stream` : Flux d'échantillons.
+/// Retourne : Une structure TestResult avec les résultats du test de périodicité.
+pub fn test_periodicity_stream(stream: &BitStream) -> TestResult {
+    let mut accumulator = PeriodicityAccumulator::default();
+    accumulator.update(stream);
+    accumulator.finish()
+}
+
+/// Bits répétés entre échantillons successifs, comptés morceau par morceau.
+#[derive(Debug, Clone, Default)]
+pub struct PeriodicityAccumulator {
+    previous: Option<u64>,
+    repeats: u64,
+    total: u64,
+}
+
+impl StreamAccumulator for PeriodicityAccumulator {
+    fn update(&mut self, chunk: &BitStream) {
+        let width = chunk.sample_bits();
+        for sample in chunk.samples() {
+            if let Some(previous) = self.previous {
+                let bits_in_common = width - (previous ^ sample).count_ones();
+                self.repeats += bits_in_common as u64;
+                self.total += width as u64;
+            }
+            self.previous = Some(sample);
+        }
+    }
+
+    fn finish(&self) -> TestResult {
+        periodicity_result(calculate_periodicity(self.repeats, self.total))
+    }
+}
+
+/// Construit le résultat du test à partir du taux de répétition.
+fn periodicity_result(periodicity: f64) -> TestResult {
+    let passed = (PERIODICITY_TEST_MIN..=PERIODICITY_TEST_MAX).contains(&periodicity);
 
     TestResult {
         test_name: "Test de périodicité des bits".to_string(),
@@ -17,22 +57,15 @@ pub fn test_periodicity(seeds: &[u64]) -> TestResult {
         score: periodicity,
         details: format!("Taux de répétition : {:.2}%", periodicity * 100.0),
         thresholds: Some((PERIODICITY_TEST_MIN, PERIODICITY_TEST_MAX)),
+        ..Default::default()
     }
 }
 
-/// Calcule la périodicité des bits '1' dans les seeds.
-/// `seeds` : Tableau de seeds.
-/// Retourne : Le taux de répétition des bits entre les seeds successifs.
-fn calculate_periodicity(seeds: &[u64]) -> f64 {
-    let mut repeats = 0;
-    let mut total = 0;
-
-    for i in 0..seeds.len() - 1 {
-        let bits_in_common = (seeds[i] ^ seeds[i + 1]).count_zeros();
-        repeats += bits_in_common;
-        total += 64;
-    }
-
+/// Calcule la périodicité des bits entre échantillons successifs.
+/// `repeats` : Nombre de bits identiques entre échantillons successifs.
+/// `total` : Nombre de bits comparés.
+/// Retourne : Le taux de répétition des bits entre les échantillons successifs.
+fn calculate_periodicity(repeats: u64, total: u64) -> f64 {
     if total == 0 {
         0.0
     } else {
@@ -1,5 +1,5 @@
 use crate::types::TestResult; // Importation de TestResult pour structurer les résultats des tests
-use std::collections::HashMap; // Utilisé pour calculer la fréquence des motifs dans les seeds
+use crate::tests::bit_stream::{BitStream, GroupCarry, StreamAccumulator};
 
 // Constantes de seuil pour le test de Poker
 const POKER_TEST_MIN: f64 = 10.0;
@@ -9,8 +9,46 @@ const POKER_TEST_MAX: f64 = 22.0; // Seuil ajusté
 /// `seeds`: Un tableau d'entiers `u64` représentant les seeds.
 /// Retourne : Une structure `TestResult` avec les résultats du test de Poker.
 pub fn test_poker(seeds: &[u64]) -> TestResult {
-    let poker_stat = calculate_poker_stat(seeds); // Calcul du score du test de Poker
-    let passed = poker_stat >= POKER_TEST_MIN && poker_stat <= POKER_TEST_MAX;
+    test_poker_stream(&BitStream::from_u64s(seeds))
+}
+
+/// Exécute le test de Poker sur un flux de bits.
+/// `stream`: Flux de bits, découpé en motifs consécutifs de 4 bits.
+/// Retourne : Une structure `TestResult` avec les résultats du test de Poker.
+pub fn test_poker_stream(stream: &BitStream) -> TestResult {
+    let mut accumulator = PokerAccumulator::default();
+    accumulator.update(stream);
+    accumulator.finish()
+}
+
+/// Fréquences des motifs de 4 bits, mises à jour morceau par morceau.
+#[derive(Debug, Clone)]
+pub struct PokerAccumulator {
+    counts: [u64; 16],
+    carry: GroupCarry,
+}
+
+impl Default for PokerAccumulator {
+    fn default() -> Self {
+        PokerAccumulator { counts: [0; 16], carry: GroupCarry::new(4) }
+    }
+}
+
+impl StreamAccumulator for PokerAccumulator {
+    fn update(&mut self, chunk: &BitStream) {
+        // Comptabilisation des occurrences de chaque motif de 4 bits
+        let counts = &mut self.counts;
+        self.carry.feed(chunk, |pattern| counts[pattern as usize] += 1);
+    }
+
+    fn finish(&self) -> TestResult {
+        poker_result(calculate_poker_stat(&self.counts))
+    }
+}
+
+/// Construit le résultat du test à partir de la statistique de Poker.
+fn poker_result(poker_stat: f64) -> TestResult {
+    let passed = (POKER_TEST_MIN..=POKER_TEST_MAX).contains(&poker_stat);
 
     TestResult {
         test_name: "Test de Poker".to_string(),
@@ -18,24 +56,17 @@ pub fn test_poker(seeds: &[u64]) -> TestResult {
         score: poker_stat,
         details: format!("Statistique de Poker calculée : {:.2}", poker_stat),
         thresholds: Some((POKER_TEST_MIN, POKER_TEST_MAX)),
+        ..Default::default()
     }
 }
 
-/// Calcule la statistique de Poker en utilisant les 4 derniers bits de chaque seed.
-/// `seeds`: Tableau d'entiers `u64` représentant les seeds.
+/// Calcule la statistique de Poker sur tous les motifs de 4 bits du flux.
+/// `counts`: Nombre d'occurrences de chaque motif ; les derniers bits d'un motif incomplet sont ignorés.
 /// Retourne : Le score de la statistique de Poker.
-fn calculate_poker_stat(seeds: &[u64]) -> f64 {
-    let mut frequency_map = HashMap::new(); // Dictionnaire pour stocker les fréquences des motifs
-
-    // Comptabilisation des occurrences des 4 derniers bits de chaque seed
-    for seed in seeds {
-        let pattern = seed & 0xF; // Conserve les 4 derniers bits
-        *frequency_map.entry(pattern).or_insert(0) += 1; // Compte le motif
-    }
-
+fn calculate_poker_stat(counts: &[u64; 16]) -> f64 {
     // Calcul de la statistique de Poker basée sur les fréquences observées
-    let n = seeds.len() as f64;
-    let sum_frequencies: f64 = frequency_map.values().map(|&count| (count as f64).powi(2)).sum();
+    let n = counts.iter().sum::<u64>() as f64;
+    let sum_frequencies: f64 = counts.iter().map(|&count| (count as f64).powi(2)).sum();
     (16.0 / n) * sum_frequencies - n // Calcul ajusté pour la statistique de Poker
 }
 
@@ -0,0 +1,525 @@
+use crate::types::TestResult;
+use crate::tests::lattice::lll_reduce;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Signed, ToPrimitive};
+use serde::{Deserialize, Serialize};
+
+// Nombre de sorties utilisées pour retrouver les paramètres (le reste sert à vérifier la prédiction)
+const RECOVERY_WINDOW: usize = 16;
+// Nombre minimal de valeurs réservées à la vérification de la prédiction
+const VERIFICATION_OUTPUTS: usize = 4;
+// Nombre minimal de sorties pour tenter une analyse
+const MIN_OUTPUTS: usize = 12;
+// Nombre de sorties utilisées par l'attaque par réseau sur les générateurs tronqués
+const LATTICE_OUTPUTS: usize = 8;
+// Nombre maximal de multiplicateurs candidats lorsqu'une différence n'est pas inversible
+const MAX_MULTIPLIER_CANDIDATES: u128 = 256;
+// Nombre de valeurs futures prédites, fournies comme preuve
+const PREDICTED_VALUES: usize = 5;
+
+/// Paramètres d'un générateur congruentiel linéaire x_(n+1) = (a·x_n + c) mod m,
+/// dont la sortie est `(x >> shift)` restreinte à `output_bits` bits.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LcgParameters {
+    /// Nom de la famille reconnue, ou description générique.
+    pub name: String,
+    /// Module m (jusqu'à 2^64).
+    pub modulus: u128,
+    /// Multiplicateur a.
+    pub multiplier: u64,
+    /// Incrément c.
+    pub increment: u64,
+    /// Nombre de bits de poids faible de l'état masqués dans la sortie.
+    pub shift: u32,
+    /// Nombre de bits de sortie.
+    pub output_bits: u32,
+}
+
+impl LcgParameters {
+    /// Calcule l'état suivant.
+    pub fn step(&self, state: u64) -> u64 {
+        ((self.multiplier as u128 * state as u128 + self.increment as u128) % self.modulus) as u64
+    }
+
+    /// Extrait la sortie visible d'un état.
+    pub fn output(&self, state: u64) -> u64 {
+        let mask = if self.output_bits >= 64 { u64::MAX } else { (1u64 << self.output_bits) - 1 };
+        (state >> self.shift) & mask
+    }
+}
+
+/// Paramètres retrouvés et démonstration de la prédiction sur les données.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LcgRecovery {
+    /// Paramètres du générateur.
+    pub parameters: LcgParameters,
+    /// État interne ayant produit la première valeur.
+    pub initial_state: u64,
+    /// Nombre de valeurs utilisées pour retrouver les paramètres.
+    pub recovered_from: usize,
+    /// Nombre de valeurs suivantes correctement prédites.
+    pub predicted_correctly: usize,
+    /// Nombre de valeurs suivantes comparées à la prédiction.
+    pub checked: usize,
+    /// Prochaines valeurs que produira le générateur après la fin des données.
+    pub next_values: Vec<u64>,
+}
+
+/// Familles connues de générateurs congruentiels à sortie tronquée (état masqué).
+fn truncated_families() -> Vec<LcgParameters> {
+    let family = |name: &str, modulus: u128, multiplier: u64, increment: u64, shift: u32, output_bits: u32| LcgParameters {
+        name: name.to_string(),
+        modulus,
+        multiplier,
+        increment,
+        shift,
+        output_bits,
+    };
+    vec![
+        family("java.util.Random (nextInt)", 1 << 48, 0x5_DEEC_E66D, 0xB, 16, 32),
+        family("drand48 / lrand48", 1 << 48, 0x5_DEEC_E66D, 0xB, 17, 31),
+        // Le bit 31 de l'état n'influence jamais la sortie : l'état utile est pris modulo 2^31
+        family("MSVC rand()", 1 << 31, 214_013, 2_531_011, 16, 15),
+        family("Knuth MMIX (32 bits de poids fort)", 1 << 64, 6_364_136_223_846_793_005, 1_442_695_040_888_963_407, 32, 32),
+        family("musl rand()", 1 << 64, 6_364_136_223_846_793_005, 1, 33, 31),
+    ]
+}
+
+/// Familles connues de générateurs congruentiels à sortie complète, pour nommer les paramètres retrouvés.
+fn full_output_family_name(modulus: u128, multiplier: u64, increment: u64) -> String {
+    match (modulus, multiplier, increment) {
+        (2_147_483_647, 16_807, 0) => "MINSTD (Park-Miller 1988)",
+        (2_147_483_647, 48_271, 0) => "MINSTD (Park-Miller 1993)",
+        (2_147_483_648, 65_539, 0) => "RANDU",
+        (2_147_483_648, 1_103_515_245, 12_345) => "glibc rand() TYPE_0",
+        (18_446_744_073_709_551_616, 6_364_136_223_846_793_005, 1_442_695_040_888_963_407) => "Knuth MMIX",
+        (18_446_744_073_709_551_616, 1, _) => "Compteur (a = 1)",
+        _ => "LCG (paramètres retrouvés)",
+    }
+    .to_string()
+}
+
+/// Analyse de prévisibilité : tente de reconnaître un générateur congruentiel linéaire et de prédire la suite.
+/// `seeds` : Tableau de seeds, considérés comme des sorties successives.
+/// Retourne : Une structure TestResult ; le test échoue si les valeurs suivantes sont prédites.
+///
+/// # Remarques
+/// * Les générateurs à sortie complète sont retrouvés sans connaissance préalable (m, a et c).
+/// * Seules les familles à sortie tronquée de `truncated_families` (java.util.Random, drand48, MSVC
+///   rand(), MMIX, musl), de paramètres connus, sont attaquées par réduction de réseau (LLL) : un LCG
+///   tronqué d'autres paramètres n'est pas détecté, et un succès ne prouve donc pas que la source n'est
+///   pas un LCG. Les détails du résultat le rappellent.
+pub fn test_predictability(seeds: &[u64]) -> TestResult {
+    if seeds.len() < MIN_OUTPUTS {
+        return TestResult {
+            test_name: "Test de prévisibilité (LCG)".to_string(),
+            passed: false,
+            score: 0.0,
+            details: "Erreur : Pas assez de seeds pour l'analyse de prévisibilité.".to_string(),
+            thresholds: None,
+            not_applicable: true,
+            ..Default::default()
+        };
+    }
+
+    match recover_lcg(seeds) {
+        Some(recovery) => {
+            let parameters = &recovery.parameters;
+            let accuracy = recovery.predicted_correctly as f64 / recovery.checked.max(1) as f64;
+            TestResult {
+                test_name: "Test de prévisibilité (LCG)".to_string(),
+                passed: false,
+                score: accuracy,
+                details: format!(
+                    "Générateur détecté : {}, m = {}, a = {}, c = {}, sortie = bits {}..{} ; {} / {} valeurs suivantes prédites à partir de {}, prochaines valeurs : {:?}",
+                    parameters.name,
+                    parameters.modulus,
+                    parameters.multiplier,
+                    parameters.increment,
+                    parameters.shift,
+                    parameters.shift + parameters.output_bits,
+                    recovery.predicted_correctly,
+                    recovery.checked,
+                    recovery.recovered_from,
+                    recovery.next_values
+                ),
+                thresholds: Some((0.0, 0.0)),
+                p_value: Some(0.0),
+                data: serde_json::to_value(&recovery).ok(),
+                ..Default::default()
+            }
+        }
+        None => TestResult {
+            test_name: "Test de prévisibilité (LCG)".to_string(),
+            passed: true,
+            score: 0.0,
+            details: format!("Aucun générateur congruentiel linéaire reconnu. {}", detection_scope()),
+            thresholds: Some((0.0, 0.0)),
+            ..Default::default()
+        },
+    }
+}
+
+/// Portée de la détection, rappelée lorsqu'aucun générateur n'est reconnu.
+fn detection_scope() -> String {
+    let families: Vec<String> = truncated_families().into_iter().map(|family| family.name).collect();
+    format!(
+        "Détectés : LCG à sortie complète de paramètres quelconques, et à sortie tronquée pour les seules \
+         familles {} ; un LCG tronqué d'autres paramètres n'est pas détecté.",
+        families.join(", ")
+    )
+}
+
+/// Tente de retrouver un générateur congruentiel linéaire à partir des valeurs.
+/// `values` : Sorties successives du générateur.
+/// Retourne : Les paramètres et la démonstration de prédiction, si toutes les valeurs suivantes sont prédites.
+pub fn recover_lcg(values: &[u64]) -> Option<LcgRecovery> {
+    if values.len() < MIN_OUTPUTS {
+        return None;
+    }
+    let window = &values[..(values.len() - VERIFICATION_OUTPUTS).min(RECOVERY_WINDOW)];
+
+    let candidates = recover_full_output(window)
+        .into_iter()
+        .chain(recover_truncated_output(window));
+    for (parameters, initial_state, recovered_from) in candidates {
+        let recovery = demonstrate_prediction(values, parameters, initial_state, recovered_from);
+        if recovery.checked > 0 && recovery.predicted_correctly == recovery.checked {
+            return Some(recovery);
+        }
+    }
+    None
+}
+
+/// Rejoue le générateur depuis l'état initial et compte les valeurs correctement prédites.
+fn demonstrate_prediction(values: &[u64], parameters: LcgParameters, initial_state: u64, recovered_from: usize) -> LcgRecovery {
+    let mut state = initial_state;
+    let mut predicted_correctly = 0;
+    for (index, &value) in values.iter().enumerate() {
+        if index >= recovered_from && parameters.output(state) == value {
+            predicted_correctly += 1;
+        }
+        state = parameters.step(state);
+    }
+
+    let next_values = (0..PREDICTED_VALUES)
+        .map(|_| {
+            let output = parameters.output(state);
+            state = parameters.step(state);
+            output
+        })
+        .collect();
+
+    LcgRecovery {
+        parameters,
+        initial_state,
+        recovered_from,
+        predicted_correctly,
+        checked: values.len().saturating_sub(recovered_from),
+        next_values,
+    }
+}
+
+/// Retrouve m, a et c d'un générateur dont la sortie est l'état complet.
+/// Retourne : Une liste de candidats (paramètres, état initial, nombre de valeurs utilisées).
+///
+/// # Remarques
+/// * Les modules candidats supérieurs à 2^64 sont écartés : l'état tient sur 64 bits, et `mul_mod` comme
+///   `LcgParameters` supposent un module d'au plus 2^64.
+fn recover_full_output(window: &[u64]) -> Vec<(LcgParameters, u64, usize)> {
+    let max_value = *window.iter().max().unwrap_or(&0) as u128;
+
+    let mut moduli = Vec::new();
+    if let Some(multiple) = modulus_multiple(window) {
+        // Le PGCD peut contenir un petit facteur parasite : on essaie aussi ses quotients
+        for divisor in 1..=64u128 {
+            if multiple % divisor == 0 && multiple / divisor > max_value {
+                moduli.push(multiple / divisor);
+            }
+        }
+    }
+    moduli.push(1 << 64);
+
+    moduli
+        .into_iter()
+        .filter(|&modulus| modulus > max_value && modulus <= 1 << 64)
+        .flat_map(|modulus| {
+            solve_multiplier(window, modulus)
+                .into_iter()
+                .map(move |(multiplier, increment)| LcgParameters {
+                    name: full_output_family_name(modulus, multiplier, increment),
+                    modulus,
+                    multiplier,
+                    increment,
+                    shift: 0,
+                    output_bits: 64,
+                })
+        })
+        .map(|parameters| (parameters, window[0], window.len()))
+        .collect()
+}
+
+/// Calcule un multiple du module : avec t_i = x_(i+1) - x_i, chaque t_(i+2)·t_i - t_(i+1)² est nul modulo m.
+/// Retourne : Le PGCD de ces quantités, si les valeurs tiennent sur 63 bits et que le PGCD est non nul.
+fn modulus_multiple(window: &[u64]) -> Option<u128> {
+    if window.iter().any(|&value| value >= 1 << 63) {
+        return None;
+    }
+    let differences: Vec<i128> = window.windows(2).map(|pair| pair[1] as i128 - pair[0] as i128).collect();
+    let multiple = differences
+        .windows(3)
+        .map(|t| (t[2] * t[0] - t[1] * t[1]).unsigned_abs())
+        .fold(0u128, gcd);
+    (multiple > 1).then_some(multiple)
+}
+
+/// Résout a et c pour un module donné à partir de différences successives, puis vérifie sur la fenêtre.
+fn solve_multiplier(window: &[u64], modulus: u128) -> Vec<(u64, u64)> {
+    let difference = |i: usize| (window[i + 1] as u128 + modulus - window[i] as u128 % modulus) % modulus;
+    for i in 0..window.len() - 2 {
+        let (t0, t1) = (difference(i), difference(i + 1));
+        let g = gcd(t0, modulus);
+        if g == 0 || g > MAX_MULTIPLIER_CANDIDATES || t1 % g != 0 {
+            continue;
+        }
+
+        // a ≡ (t1 / g)·(t0 / g)^-1 mod (m / g), puis relèvement modulo m
+        let reduced_modulus = modulus / g;
+        let Some(inverse) = mod_inverse(t0 / g, reduced_modulus) else { continue };
+        let base = mul_mod(t1 / g, inverse, reduced_modulus);
+        return (0..g)
+            .map(|j| (base + j * reduced_modulus) % modulus)
+            .filter_map(|multiplier| {
+                let increment = (window[1] as u128 + modulus - mul_mod(multiplier, window[0] as u128, modulus)) % modulus;
+                let verified = window.windows(2).all(|pair| {
+                    (mul_mod(multiplier, pair[0] as u128, modulus) + increment) % modulus == pair[1] as u128
+                });
+                verified.then_some((multiplier as u64, increment as u64))
+            })
+            .collect();
+    }
+    Vec::new()
+}
+
+/// Retrouve l'état caché des familles tronquées connues par réduction de réseau.
+/// Retourne : Une liste de candidats (paramètres, état initial, nombre de valeurs utilisées).
+///
+/// # Remarques
+/// * m, a et c ne sont pas recherchés : seules les familles de `truncated_families` sont essayées.
+fn recover_truncated_output(window: &[u64]) -> Vec<(LcgParameters, u64, usize)> {
+    let used = LATTICE_OUTPUTS.min(window.len());
+    truncated_families()
+        .into_iter()
+        .filter(|family| window.iter().all(|&value| value >> family.output_bits == 0))
+        .filter_map(|family| {
+            let state = lattice_initial_state(&window[..used], &family)?;
+            Some((family, state, used))
+        })
+        .collect()
+}
+
+/// Attaque par réseau (plongement de Kannan) sur un générateur tronqué de paramètres connus.
+///
+/// Les états vérifient x_i = a^i·x_0 + c_i mod m et x_i = 2^s·y_i + z_i avec z_i < 2^s inconnu.
+/// Le vecteur (x_i - c_i) appartient au réseau engendré par (1, a, ..., a^(k-1)) et m·e_i ;
+/// il est proche de la cible t_i = 2^s·y_i + 2^(s-1) - c_i, et LLL sur la base augmentée de
+/// (t, 2^(s-1)) fait apparaître l'écart, d'où x_0.
+fn lattice_initial_state(outputs: &[u64], family: &LcgParameters) -> Option<u64> {
+    let k = outputs.len();
+    let modulus = family.modulus;
+    let shift = family.shift;
+    if shift == 0 || k < 2 {
+        return None;
+    }
+
+    let mut powers = Vec::with_capacity(k);
+    let mut offsets = Vec::with_capacity(k);
+    let (mut power, mut offset) = (1u128, 0u128);
+    for _ in 0..k {
+        powers.push(power);
+        offsets.push(offset);
+        power = mul_mod(power, family.multiplier as u128, modulus);
+        offset = (mul_mod(offset, family.multiplier as u128, modulus) + family.increment as u128) % modulus;
+    }
+
+    let half = 1u128 << (shift - 1);
+    let targets: Vec<u128> = outputs
+        .iter()
+        .zip(&offsets)
+        .map(|(&output, &offset)| (((output as u128) << shift) + half + modulus - offset) % modulus)
+        .collect();
+
+    let embedding = BigInt::from(half);
+    let mut basis: Vec<Vec<BigInt>> = Vec::with_capacity(k + 1);
+    let mut first_row: Vec<BigInt> = powers.iter().map(|&p| BigInt::from(p)).collect();
+    first_row.push(BigInt::from(0));
+    basis.push(first_row);
+    for i in 1..k {
+        let mut row = vec![BigInt::from(0); k + 1];
+        row[i] = BigInt::from(modulus);
+        basis.push(row);
+    }
+    let mut target_row: Vec<BigInt> = targets.iter().map(|&t| BigInt::from(t)).collect();
+    target_row.push(embedding.clone());
+    basis.push(target_row);
+
+    lll_reduce(&mut basis);
+
+    let big_modulus = BigInt::from(modulus);
+    basis
+        .iter()
+        .filter(|row| row[k].abs() == embedding)
+        .filter_map(|row| {
+            // row = ±(t - w) : w_0 = t_0 ∓ row_0 est l'état x_0 (modulo m)
+            let error = if row[k].is_positive() { row[0].clone() } else { -row[0].clone() };
+            let state = (BigInt::from(targets[0]) - error).mod_floor(&big_modulus);
+            state.to_u64()
+        })
+        .find(|&state| {
+            let mut current = state;
+            outputs.iter().all(|&output| {
+                let matches = family.output(current) == output;
+                current = family.step(current);
+                matches
+            })
+        })
+}
+
+/// PGCD de deux entiers.
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Produit modulaire pour des opérandes et un module inférieurs ou égaux à 2^64.
+fn mul_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    if modulus == 1 << 64 {
+        (a as u64).wrapping_mul(b as u64) as u128
+    } else {
+        (a % modulus) * (b % modulus) % modulus
+    }
+}
+
+/// Inverse modulaire par l'algorithme d'Euclide étendu.
+fn mod_inverse(value: u128, modulus: u128) -> Option<u128> {
+    if modulus == 1 {
+        return Some(0);
+    }
+    let (mut old_r, mut r) = (value as i128, modulus as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+    (old_r == 1).then(|| old_s.rem_euclid(modulus as i128) as u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn generate_random_seeds() -> Vec<u64> {
+        let mut rng = rand::thread_rng();
+        (0..1000).map(|_| rng.gen::<u64>()).collect()
+    }
+
+    fn lcg_outputs(parameters: &LcgParameters, mut state: u64, count: usize) -> Vec<u64> {
+        (0..count)
+            .map(|_| {
+                let output = parameters.output(state);
+                state = parameters.step(state);
+                output
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_predictability_with_random_seeds() {
+        let seeds = generate_random_seeds();
+        let result = test_predictability(&seeds);
+        assert!(result.passed, "Faux positif : {}", result.details);
+    }
+
+    #[test]
+    fn test_predictability_recovers_minstd() {
+        let minstd = LcgParameters {
+            name: String::new(),
+            modulus: 2_147_483_647,
+            multiplier: 16_807,
+            increment: 0,
+            shift: 0,
+            output_bits: 64,
+        };
+        let seeds = lcg_outputs(&minstd, 42, 200);
+        let result = test_predictability(&seeds);
+        println!("{}", result.details);
+        assert!(!result.passed);
+        let recovery: LcgRecovery = serde_json::from_value(result.data.unwrap()).unwrap();
+        assert_eq!(recovery.parameters.modulus, 2_147_483_647);
+        assert_eq!(recovery.parameters.multiplier, 16_807);
+        assert_eq!(recovery.parameters.name, "MINSTD (Park-Miller 1988)");
+        assert_eq!(recovery.predicted_correctly, recovery.checked);
+    }
+
+    #[test]
+    fn test_predictability_recovers_64_bit_lcg() {
+        let mmix = LcgParameters {
+            name: String::new(),
+            modulus: 1 << 64,
+            multiplier: 6_364_136_223_846_793_005,
+            increment: 1_442_695_040_888_963_407,
+            shift: 0,
+            output_bits: 64,
+        };
+        let seeds = lcg_outputs(&mmix, 0xDEAD_BEEF, 100);
+        let recovery = recover_lcg(&seeds).expect("LCG 64 bits attendu");
+        assert_eq!(recovery.parameters.name, "Knuth MMIX");
+    }
+
+    #[test]
+    fn test_predictability_recovers_truncated_lcgs() {
+        for family in truncated_families() {
+            let state = rand::thread_rng().gen::<u64>() % family.modulus.min(u64::MAX as u128) as u64;
+            let seeds = lcg_outputs(&family, state, 100);
+            let recovery = recover_lcg(&seeds).unwrap_or_else(|| panic!("{} non retrouvé", family.name));
+            assert_eq!(recovery.predicted_correctly, recovery.checked);
+            assert_eq!(recovery.next_values, lcg_outputs(&family, state, 105)[100..].to_vec());
+        }
+    }
+
+    #[test]
+    fn test_predictability_states_truncated_scope() {
+        // LCG 48 bits tronqué de multiplicateur inconnu : hors des familles détectées, et dit comme tel
+        let unknown = LcgParameters {
+            name: String::new(),
+            modulus: 1 << 48,
+            multiplier: 0x2_7BB2_EE68_7B0B,
+            increment: 0x3039,
+            shift: 16,
+            output_bits: 32,
+        };
+        let result = test_predictability(&lcg_outputs(&unknown, 12345, 100));
+        assert!(result.passed);
+        assert!(result.details.contains("java.util.Random (nextInt), drand48 / lrand48"), "{}", result.details);
+        assert!(result.details.contains("d'autres paramètres n'est pas détecté"), "{}", result.details);
+    }
+
+    #[test]
+    fn test_predictability_ignores_moduli_above_64_bits() {
+        // Différences 2^(12-i)·3^i : le PGCD des t_(i+2)·t_i - t_(i+1)² dépasse 2^64
+        let mut seeds = vec![1u64 << 62];
+        for i in 0..13 {
+            seeds.push(seeds[i] + (1 << (12 - i)) * 3u64.pow(i as u32));
+        }
+        seeds.extend([5, 7, 11, 13, 17]);
+        assert!(modulus_multiple(&seeds[..15]).unwrap() > 1 << 64);
+        let result = test_predictability(&seeds);
+        assert!(result.passed, "{}", result.details);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::types::TestResult; // Import nécessaire pour structurer les résultats du test
+use crate::tests::bit_stream::{BitStream, StreamAccumulator};
 
 // Seuils pour le test de longueur de séquence
 const SEQUENCE_LENGTH_TEST_MIN: f64 = 10.0; // Valeur minimale
@@ -8,8 +9,57 @@ const SEQUENCE_LENGTH_TEST_MAX: f64 = 18.0; // Valeur maximale
 /// `seeds` : Tableau de seeds.
 /// Retourne : Une structure TestResult avec les résultats du test.
 pub fn test_sequence_length(seeds: &[u64]) -> TestResult {
-    let max_length = calculate_max_sequence_length(seeds);
-    let passed = max_length >= SEQUENCE_LENGTH_TEST_MIN && max_length <= SEQUENCE_LENGTH_TEST_MAX;
+    test_sequence_length_stream(&BitStream::from_u64s(seeds))
+}
+
+/// Test de longueur de séquence sur un flux de bits, sans coupure aux frontières des échantillons.
+/// `stream` : Flux de bits.
+/// Retourne : Une structure TestResult avec les résultats du test.
+pub fn test_sequence_length_stream(stream: &BitStream) -> TestResult {
+    let mut accumulator = SequenceLengthAccumulator::default();
+    accumulator.update(stream);
+    accumulator.finish()
+}
+
+/// Séquence de '1' en cours et plus longue séquence, mises à jour morceau par morceau.
+#[derive(Debug, Clone, Default)]
+pub struct SequenceLengthAccumulator {
+    current_len: u64,
+    max_len: u64,
+}
+
+impl StreamAccumulator for SequenceLengthAccumulator {
+    /// Parcourt le morceau par mots de 64 bits : une séquence peut chevaucher deux mots ou deux morceaux.
+    fn update(&mut self, chunk: &BitStream) {
+        let len = chunk.len_bits();
+        for start in (0..len).step_by(64) {
+            let count = (len - start).min(64) as u32;
+            let word = chunk.bits_at(start, count) << (64 - count);
+            if word.leading_ones() >= count {
+                self.current_len += count as u64;
+            } else {
+                // Séquence prolongée par les premiers bits, puis plus longue séquence interne au mot
+                self.max_len = self.max_len.max(self.current_len + word.leading_ones() as u64);
+                let (mut rest, mut inner) = (word, 0);
+                while rest != 0 {
+                    rest &= rest << 1;
+                    inner += 1;
+                }
+                self.max_len = self.max_len.max(inner);
+                self.current_len = (word >> (64 - count)).trailing_ones() as u64;
+            }
+            self.max_len = self.max_len.max(self.current_len);
+        }
+    }
+
+    fn finish(&self) -> TestResult {
+        sequence_length_result(self.max_len as f64)
+    }
+}
+
+/// Construit le résultat du test à partir de la longueur maximale de séquence.
+fn sequence_length_result(max_length: f64) -> TestResult {
+    let passed = (SEQUENCE_LENGTH_TEST_MIN..=SEQUENCE_LENGTH_TEST_MAX).contains(&max_length);
 
     TestResult {
         test_name: "Test de longueur de séquence".to_string(),
@@ -17,36 +67,24 @@ pub fn test_sequence_length(seeds: &[u64]) -> TestResult {
         score: max_length,
         details: format!("Longueur maximale de séquence de '1' : {:.2}", max_length),
         thresholds: Some((SEQUENCE_LENGTH_TEST_MIN, SEQUENCE_LENGTH_TEST_MAX)),
+        ..Default::default()
     }
 }
 
-/// Calcule la longueur maximale de séquences consécutives de '1' dans les seeds.
-/// `seeds` : Tableau de seeds.
+/// Calcule la longueur maximale de séquences consécutives de '1' dans le flux.
+/// `stream` : Flux de bits, parcouru d'un seul tenant (une séquence peut chevaucher deux échantillons).
 /// Retourne : La longueur maximale de séquence de '1'.
-fn calculate_max_sequence_length(seeds: &[u64]) -> f64 {
-    seeds
-        .iter()
-        .map(|seed| {
-            let mut max_len = 0;
-            let mut current_len = 0;
-            for i in 0..64 {
-                if seed & (1 << i) != 0 {
-                    current_len += 1;
-                    if current_len > max_len {
-                        max_len = current_len;
-                    }
-                } else {
-                    current_len = 0;
-                }
-            }
-            max_len as f64
-        })
-        .fold(0.0, f64::max) // Prend le maximum parmi toutes les séquences
+#[cfg(test)]
+fn calculate_max_sequence_length(stream: &BitStream) -> f64 {
+    let mut accumulator = SequenceLengthAccumulator::default();
+    accumulator.update(stream);
+    accumulator.max_len as f64
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{test_sequence_length, SEQUENCE_LENGTH_TEST_MIN, SEQUENCE_LENGTH_TEST_MAX};
+    use super::{calculate_max_sequence_length, test_sequence_length, SEQUENCE_LENGTH_TEST_MIN, SEQUENCE_LENGTH_TEST_MAX};
+    use crate::tests::bit_stream::BitStream;
     use rand::Rng;
 
     /// Génère des seeds aléatoires pour les tests.
@@ -67,4 +105,11 @@ mod tests {
             result.score
         );
     }
+
+    /// Une séquence à cheval sur deux mots est comptée en entier.
+    #[test]
+    fn test_sequence_crosses_word_boundary() {
+        let stream = BitStream::from_u64s(&[0xAAAA_AAAA_AAAA_AAAF, 0xF555_5555_5555_5555]);
+        assert_eq!(calculate_max_sequence_length(&stream), 8.0);
+    }
 }
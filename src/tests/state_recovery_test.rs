@@ -0,0 +1,609 @@
+use crate::types::TestResult;
+use crate::generators::mt19937::{untemper, untemper64};
+use crate::generators::xorshift::{
+    star_star_inverse, xoroshiro128_step, xorshift128_plus_step, xorshift128_step, xorshift32_step, xorshift64_star_inverse,
+    xorshift64_star_step, xorshift64_step, xoshiro256_step,
+};
+use crate::generators::{
+    LinearWord, Mt19937, Mt19937_64, Xoroshiro128Plus, Xoroshiro128StarStar, Xorshift128, Xorshift128Plus, Xorshift32,
+    Xorshift64, Xorshift64Star, Xoshiro256Plus, Xoshiro256StarStar,
+};
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use std::ops::BitXor;
+
+// Nombre minimal de valeurs suivantes qui doivent être prédites pour conclure
+const VERIFICATION_OUTPUTS: usize = 8;
+// Sorties supplémentaires utilisées au-delà du strict minimum, pour que le système soit de rang plein
+const EXTRA_OUTPUTS: usize = 2;
+// Sorties supplémentaires pour les variantes + (une seule équation par sortie)
+const EXTRA_LOW_BIT_OUTPUTS: usize = 16;
+// Nombre de valeurs futures prédites, fournies comme preuve
+const PREDICTED_VALUES: usize = 5;
+// Nombre maximal d'inconnues (bits d'état) des familles linéaires
+const MAX_STATE_BITS: usize = 256;
+
+/// État retrouvé et démonstration de la prédiction sur les données.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StateRecovery {
+    /// Générateur reconnu.
+    pub generator: String,
+    /// Découpage des données en sorties successives du générateur.
+    pub stream: String,
+    /// Nombre de sorties utilisées pour reconstruire l'état.
+    pub recovered_from: usize,
+    /// Nombre de sorties suivantes correctement prédites.
+    pub predicted_correctly: usize,
+    /// Nombre de sorties suivantes comparées à la prédiction.
+    pub checked: usize,
+    /// État initial retrouvé (familles xorshift / xoshiro uniquement ; l'état de MT est trop grand).
+    pub initial_state: Option<Vec<u64>>,
+    /// Prochaines sorties que produira le générateur après la fin des données.
+    pub next_values: Vec<u64>,
+}
+
+/// Test de récupération d'état : reconnaît la sortie brute de MT19937 / MT19937-64 et des familles
+/// xorshift / xoshiro en reconstruisant l'état interne, puis en prédisant la suite des données.
+/// `seeds` : Tableau de seeds, considérés comme des sorties successives.
+/// Retourne : Une structure TestResult ; le test échoue si la suite est prédite, avec les valeurs suivantes comme preuve.
+///
+/// # Remarques
+/// * MT19937 demande 624 sorties consécutives (312 pour MT19937-64) ; l'alignement sur la régénération de l'état est indifférent.
+/// * Les familles xorshift / xoshiro sont linéaires sur GF(2) : l'état initial est la solution d'un système linéaire.
+/// * Les valeurs supérieures à 2^32 sont aussi lues comme deux sorties de 32 bits (poids faible d'abord, comme `next_u64`).
+pub fn test_state_recovery(seeds: &[u64]) -> TestResult {
+    let minimum = linear_families()
+        .iter()
+        .map(|family| family.outputs_needed() + VERIFICATION_OUTPUTS)
+        .min()
+        .unwrap_or(0);
+    if seeds.len() < minimum {
+        return TestResult {
+            test_name: "Test de récupération d'état (MT / xorshift)".to_string(),
+            passed: false,
+            score: 0.0,
+            details: "Erreur : Pas assez de seeds pour la récupération d'état.".to_string(),
+            thresholds: None,
+            not_applicable: true,
+            ..Default::default()
+        };
+    }
+
+    match recover_state(seeds) {
+        Some(recovery) => TestResult {
+            test_name: "Test de récupération d'état (MT / xorshift)".to_string(),
+            passed: false,
+            score: recovery.predicted_correctly as f64 / recovery.checked.max(1) as f64,
+            details: format!(
+                "Générateur détecté : {} ({}) ; état reconstruit à partir de {} sorties, {} / {} sorties suivantes prédites, prochaines valeurs : {:?}",
+                recovery.generator,
+                recovery.stream,
+                recovery.recovered_from,
+                recovery.predicted_correctly,
+                recovery.checked,
+                recovery.next_values
+            ),
+            thresholds: Some((0.0, 0.0)),
+            p_value: Some(0.0),
+            data: serde_json::to_value(&recovery).ok(),
+            ..Default::default()
+        },
+        None => {
+            let words32 = if seeds.iter().all(|&seed| seed <= u32::MAX as u64) { seeds.len() } else { 2 * seeds.len() };
+            let skipped = skipped_generators(words32, seeds.len());
+            let details = if skipped.is_empty() {
+                "Aucun état de MT19937 ni de la famille xorshift / xoshiro n'a pu être reconstruit.".to_string()
+            } else {
+                format!(
+                    "Aucun état reconstruit ; générateurs non évalués (données trop courtes) : {}",
+                    skipped.join(", ")
+                )
+            };
+            TestResult {
+                test_name: "Test de récupération d'état (MT / xorshift)".to_string(),
+                passed: true,
+                score: 0.0,
+                details,
+                thresholds: Some((0.0, 0.0)),
+                ..Default::default()
+            }
+        }
+    }
+}
+
+/// Tente de reconstruire l'état d'un générateur connu à partir des valeurs.
+/// `values` : Sorties successives (mots de 64 bits, ou de 32 bits si toutes les valeurs tiennent sur 32 bits).
+/// Retourne : L'état et la démonstration de prédiction, si toutes les sorties suivantes sont prédites.
+pub fn recover_state(values: &[u64]) -> Option<StateRecovery> {
+    let (words32, stream32) = if values.iter().all(|&value| value <= u32::MAX as u64) {
+        (values.to_vec(), "mots de 32 bits")
+    } else {
+        let split = values
+            .iter()
+            .flat_map(|&value| [value & 0xFFFF_FFFF, value >> 32])
+            .collect();
+        (split, "mots de 64 bits découpés en deux sorties de 32 bits")
+    };
+
+    recover_mt19937(&words32, stream32)
+        .or_else(|| recover_mt19937_64(values))
+        .or_else(|| {
+            linear_families().iter().find_map(|family| {
+                let (stream, description) = if family.word_bits == 32 {
+                    (&words32[..], stream32)
+                } else {
+                    (values, "mots de 64 bits")
+                };
+                recover_linear(family, stream, description)
+            })
+        })
+}
+
+/// Générateurs qui n'ont pas pu être évalués faute de données.
+/// `words32` : Nombre de sorties de 32 bits lues ; `len` : nombre de valeurs.
+fn skipped_generators(words32: usize, len: usize) -> Vec<String> {
+    let mut skipped = Vec::new();
+    if words32 < Mt19937::STATE_WORDS + VERIFICATION_OUTPUTS {
+        skipped.push("MT19937".to_string());
+    }
+    if len < Mt19937_64::STATE_WORDS + VERIFICATION_OUTPUTS {
+        skipped.push("MT19937-64".to_string());
+    }
+    for family in linear_families() {
+        let available = if family.word_bits == 32 { words32 } else { len };
+        if available < family.outputs_needed() + VERIFICATION_OUTPUTS {
+            skipped.push(family.name.to_string());
+        }
+    }
+    skipped
+}
+
+/// MT19937 : chaque sortie détempérée est un mot d'état ; 624 mots consécutifs déterminent toute la suite.
+fn recover_mt19937(stream: &[u64], description: &str) -> Option<StateRecovery> {
+    let n = Mt19937::STATE_WORDS;
+    if stream.len() < n + VERIFICATION_OUTPUTS || stream.iter().any(|&value| value > u32::MAX as u64) {
+        return None;
+    }
+    let mut state = [0u32; 624];
+    for (word, &value) in state.iter_mut().zip(stream) {
+        *word = untemper(value as u32);
+    }
+    if state.iter().all(|&word| word == 0) {
+        return None; // État invalide
+    }
+
+    let mut rng = Mt19937::from_state(state, n);
+    demonstrate_prediction("MT19937", description, stream, n, None, || rng.next_u32() as u64)
+}
+
+/// MT19937-64 : identique à MT19937 avec 312 mots de 64 bits.
+fn recover_mt19937_64(stream: &[u64]) -> Option<StateRecovery> {
+    let n = Mt19937_64::STATE_WORDS;
+    if stream.len() < n + VERIFICATION_OUTPUTS {
+        return None;
+    }
+    let mut state = [0u64; 312];
+    for (word, &value) in state.iter_mut().zip(stream) {
+        *word = untemper64(value);
+    }
+    if state.iter().all(|&word| word == 0) {
+        return None;
+    }
+
+    let mut rng = Mt19937_64::from_state(state, n);
+    demonstrate_prediction("MT19937-64", "mots de 64 bits", stream, n, None, || rng.next_u64())
+}
+
+/// Compare les sorties suivant la fenêtre de reconstruction aux prédictions du générateur reconstruit.
+/// `next` produit les sorties à partir de la position `recovered_from`.
+fn demonstrate_prediction(
+    generator: &str,
+    description: &str,
+    stream: &[u64],
+    recovered_from: usize,
+    initial_state: Option<Vec<u64>>,
+    mut next: impl FnMut() -> u64,
+) -> Option<StateRecovery> {
+    let checked = stream.len() - recovered_from;
+    let predicted_correctly = stream[recovered_from..].iter().take_while(|&&value| next() == value).count();
+    if predicted_correctly < checked {
+        return None;
+    }
+    let next_values = (0..PREDICTED_VALUES).map(|_| next()).collect();
+
+    Some(StateRecovery {
+        generator: generator.to_string(),
+        stream: description.to_string(),
+        recovered_from,
+        predicted_correctly,
+        checked,
+        initial_state,
+        next_values,
+    })
+}
+
+/// Partie de la sortie qui est une fonction linéaire de l'état.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Observation {
+    /// Tous les bits de la sortie, après inversion éventuelle du brouillage.
+    Full,
+    /// Seul le bit de poids faible (variantes + : le bit de poids faible d'une somme est un XOR).
+    LowBit,
+}
+
+/// Famille de générateurs linéaires sur GF(2).
+struct LinearFamily {
+    name: &'static str,
+    state_words: usize,
+    word_bits: u32,
+    observation: Observation,
+    /// Pas symbolique, retournant la partie linéaire de la sortie.
+    step: fn(&mut [SymbolicWord]) -> SymbolicWord,
+    /// Ramène une sortie à sa partie linéaire.
+    unscramble: fn(u64) -> u64,
+    /// Générateur concret construit à partir d'un état, et sa sortie suivante.
+    replay: fn(&[u64]) -> Box<dyn FnMut() -> u64>,
+}
+
+impl LinearFamily {
+    fn state_bits(&self) -> usize {
+        self.state_words * self.word_bits as usize
+    }
+
+    /// Nombre de sorties utilisées pour écrire le système linéaire.
+    fn outputs_needed(&self) -> usize {
+        match self.observation {
+            Observation::Full => self.state_bits().div_ceil(self.word_bits as usize) + EXTRA_OUTPUTS,
+            Observation::LowBit => self.state_bits() + EXTRA_LOW_BIT_OUTPUTS,
+        }
+    }
+}
+
+/// Familles xorshift / xoshiro reconnues.
+fn linear_families() -> Vec<LinearFamily> {
+    vec![
+        LinearFamily {
+            name: "xorshift32",
+            state_words: 1,
+            word_bits: 32,
+            observation: Observation::Full,
+            step: xorshift32_step,
+            unscramble: |output| output,
+            replay: |state| {
+                let mut rng = Xorshift32::from_state([state[0]]);
+                Box::new(move || rng.next_u32() as u64)
+            },
+        },
+        LinearFamily {
+            name: "xorshift64",
+            state_words: 1,
+            word_bits: 64,
+            observation: Observation::Full,
+            step: xorshift64_step,
+            unscramble: |output| output,
+            replay: |state| {
+                let mut rng = Xorshift64::from_state([state[0]]);
+                Box::new(move || rng.next_u64())
+            },
+        },
+        LinearFamily {
+            name: "xorshift64*",
+            state_words: 1,
+            word_bits: 64,
+            observation: Observation::Full,
+            step: xorshift64_star_step,
+            unscramble: xorshift64_star_inverse,
+            replay: |state| {
+                let mut rng = Xorshift64Star::from_state([state[0]]);
+                Box::new(move || rng.next_u64())
+            },
+        },
+        LinearFamily {
+            name: "xorshift128",
+            state_words: 4,
+            word_bits: 32,
+            observation: Observation::Full,
+            step: xorshift128_step,
+            unscramble: |output| output,
+            replay: |state| {
+                let mut rng = Xorshift128::from_state([state[0], state[1], state[2], state[3]]);
+                Box::new(move || rng.next_u32() as u64)
+            },
+        },
+        LinearFamily {
+            name: "xorshift128+",
+            state_words: 2,
+            word_bits: 64,
+            observation: Observation::LowBit,
+            step: xorshift128_plus_step,
+            unscramble: |output| output & 1,
+            replay: |state| {
+                let mut rng = Xorshift128Plus::from_state([state[0], state[1]]);
+                Box::new(move || rng.next_u64())
+            },
+        },
+        LinearFamily {
+            name: "xoroshiro128+",
+            state_words: 2,
+            word_bits: 64,
+            observation: Observation::LowBit,
+            step: |state| {
+                let s1 = state[1].clone();
+                xoroshiro128_step(state) ^ s1
+            },
+            unscramble: |output| output & 1,
+            replay: |state| {
+                let mut rng = Xoroshiro128Plus::from_state([state[0], state[1]]);
+                Box::new(move || rng.next_u64())
+            },
+        },
+        LinearFamily {
+            name: "xoroshiro128**",
+            state_words: 2,
+            word_bits: 64,
+            observation: Observation::Full,
+            step: xoroshiro128_step,
+            unscramble: star_star_inverse,
+            replay: |state| {
+                let mut rng = Xoroshiro128StarStar::from_state([state[0], state[1]]);
+                Box::new(move || rng.next_u64())
+            },
+        },
+        LinearFamily {
+            name: "xoshiro256+",
+            state_words: 4,
+            word_bits: 64,
+            observation: Observation::LowBit,
+            step: |state| {
+                let [s0, _, s3] = xoshiro256_step(state);
+                s0 ^ s3
+            },
+            unscramble: |output| output & 1,
+            replay: |state| {
+                let mut rng = Xoshiro256Plus::from_state([state[0], state[1], state[2], state[3]]);
+                Box::new(move || rng.next_u64())
+            },
+        },
+        LinearFamily {
+            name: "xoshiro256**",
+            state_words: 4,
+            word_bits: 64,
+            observation: Observation::Full,
+            step: |state| {
+                let [_, s1, _] = xoshiro256_step(state);
+                s1
+            },
+            unscramble: star_star_inverse,
+            replay: |state| {
+                let mut rng = Xoshiro256StarStar::from_state([state[0], state[1], state[2], state[3]]);
+                Box::new(move || rng.next_u64())
+            },
+        },
+    ]
+}
+
+/// Écrit le système linéaire liant les sorties à l'état initial, le résout, puis vérifie la prédiction.
+fn recover_linear(family: &LinearFamily, stream: &[u64], description: &str) -> Option<StateRecovery> {
+    let outputs = family.outputs_needed();
+    if stream.len() < outputs + VERIFICATION_OUTPUTS {
+        return None;
+    }
+    if family.word_bits == 32 && stream.iter().any(|&value| value > u32::MAX as u64) {
+        return None;
+    }
+
+    // État symbolique : le bit b du mot w est l'inconnue w · word_bits + b
+    let mut state: Vec<SymbolicWord> = (0..family.state_words)
+        .map(|word| SymbolicWord::variables(word * family.word_bits as usize, family.word_bits))
+        .collect();
+    let mut equations = Vec::new();
+    for &value in &stream[..outputs] {
+        let linear = (family.step)(&mut state);
+        let observed = (family.unscramble)(value);
+        let bits = match family.observation {
+            Observation::Full => family.word_bits,
+            Observation::LowBit => 1,
+        };
+        for bit in 0..bits as usize {
+            equations.push((linear.bits[bit], (observed >> bit) & 1 == 1));
+        }
+    }
+
+    let solution = solve_gf2(equations, family.state_bits())?;
+    let word_mask = if family.word_bits == 64 { u64::MAX } else { (1u64 << family.word_bits) - 1 };
+    let initial_state: Vec<u64> = (0..family.state_words)
+        .map(|word| {
+            let offset = word * family.word_bits as usize;
+            (0..family.word_bits as usize).fold(0u64, |acc, bit| {
+                let variable = offset + bit;
+                let value = (solution[variable / 64] >> (variable % 64)) & 1;
+                acc | (value << bit)
+            }) & word_mask
+        })
+        .collect();
+    if initial_state.iter().all(|&word| word == 0) {
+        return None; // État invalide pour toutes ces familles
+    }
+
+    // Rejoue depuis le début : les sorties de la fenêtre doivent aussi correspondre
+    let mut next = (family.replay)(&initial_state);
+    if stream[..outputs].iter().any(|&value| next() != value) {
+        return None;
+    }
+    demonstrate_prediction(family.name, description, stream, outputs, Some(initial_state), next)
+}
+
+/// Combinaison linéaire des bits de l'état initial (au plus 256 inconnues).
+type Combination = [u64; MAX_STATE_BITS / 64];
+
+/// Mot dont chaque bit est une combinaison linéaire des bits de l'état initial.
+#[derive(Debug, Clone, PartialEq)]
+struct SymbolicWord {
+    bits: [Combination; 64],
+}
+
+impl SymbolicWord {
+    /// Mot dont les `width` premiers bits sont les inconnues `first`, `first + 1`, ...
+    fn variables(first: usize, width: u32) -> Self {
+        let mut bits = [[0u64; MAX_STATE_BITS / 64]; 64];
+        for (bit, combination) in bits.iter_mut().enumerate().take(width as usize) {
+            let variable = first + bit;
+            combination[variable / 64] |= 1 << (variable % 64);
+        }
+        SymbolicWord { bits }
+    }
+
+    /// Mot dont le bit `i` vaut le bit `source(i)` de `self`, ou zéro.
+    fn permute(&self, source: impl Fn(usize) -> Option<usize>) -> Self {
+        let mut bits = [[0u64; MAX_STATE_BITS / 64]; 64];
+        for (bit, combination) in bits.iter_mut().enumerate() {
+            if let Some(from) = source(bit) {
+                *combination = self.bits[from];
+            }
+        }
+        SymbolicWord { bits }
+    }
+}
+
+impl BitXor for SymbolicWord {
+    type Output = Self;
+
+    fn bitxor(mut self, other: Self) -> Self {
+        for (combination, other) in self.bits.iter_mut().zip(&other.bits) {
+            for (word, other) in combination.iter_mut().zip(other) {
+                *word ^= other;
+            }
+        }
+        self
+    }
+}
+
+impl LinearWord for SymbolicWord {
+    fn shl(&self, n: u32) -> Self {
+        self.permute(|bit| bit.checked_sub(n as usize))
+    }
+
+    fn shr(&self, n: u32) -> Self {
+        self.permute(|bit| Some(bit + n as usize).filter(|&from| from < 64))
+    }
+
+    fn rotl(&self, n: u32) -> Self {
+        self.permute(|bit| Some((bit + 64 - n as usize % 64) % 64))
+    }
+
+    fn mask(&self, mask: u64) -> Self {
+        self.permute(|bit| Some(bit).filter(|&bit| (mask >> bit) & 1 == 1))
+    }
+}
+
+/// Résout un système linéaire sur GF(2) par élimination de Gauss.
+/// `equations` : Paires (combinaison des inconnues, valeur du second membre).
+/// `unknowns` : Nombre d'inconnues.
+/// Retourne : Une solution (inconnues libres à zéro), ou `None` si le système est incohérent.
+fn solve_gf2(mut equations: Vec<(Combination, bool)>, unknowns: usize) -> Option<Combination> {
+    let mut pivots = Vec::new();
+    let mut row = 0;
+    for column in 0..unknowns {
+        let (word, bit) = (column / 64, column % 64);
+        let Some(found) = (row..equations.len()).find(|&index| (equations[index].0[word] >> bit) & 1 == 1) else {
+            continue;
+        };
+        equations.swap(row, found);
+        let (pivot_bits, pivot_value) = equations[row];
+        for (index, equation) in equations.iter_mut().enumerate() {
+            if index != row && (equation.0[word] >> bit) & 1 == 1 {
+                for (target, source) in equation.0.iter_mut().zip(&pivot_bits) {
+                    *target ^= source;
+                }
+                equation.1 ^= pivot_value;
+            }
+        }
+        pivots.push(column);
+        row += 1;
+    }
+
+    // Une équation 0 = 1 restante rend le système incohérent
+    if equations[row..].iter().any(|&(_, value)| value) {
+        return None;
+    }
+    let mut solution = [0u64; MAX_STATE_BITS / 64];
+    for (equation, &column) in equations.iter().zip(&pivots) {
+        if equation.1 {
+            solution[column / 64] |= 1 << (column % 64);
+        }
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn generate_random_seeds() -> Vec<u64> {
+        let mut rng = rand::thread_rng();
+        (0..1000).map(|_| rng.gen::<u64>()).collect()
+    }
+
+    #[test]
+    fn test_state_recovery_with_random_seeds() {
+        let seeds = generate_random_seeds();
+        let result = test_state_recovery(&seeds);
+        println!("{}", result.details);
+        assert!(result.passed, "Échec du test de récupération d'état : {}", result.details);
+    }
+
+    #[test]
+    fn test_state_recovery_detects_mt19937() {
+        // Sorties de 32 bits, puis paires de sorties regroupées en mots de 64 bits (décalées de 100 sorties)
+        let mut rng = Mt19937::new(42);
+        let words32: Vec<u64> = (0..700).map(|_| rng.next_u32() as u64).collect();
+        let recovery = recover_state(&words32).expect("MT19937 doit être reconnu");
+        assert_eq!(recovery.generator, "MT19937");
+        assert_eq!(recovery.next_values[0], rng.next_u32() as u64);
+
+        let mut rng = Mt19937::new(7);
+        (0..100).for_each(|_| {
+            rng.next_u32();
+        });
+        let words64: Vec<u64> = (0..400).map(|_| rng.next_u64()).collect();
+        let result = test_state_recovery(&words64);
+        assert!(!result.passed);
+        assert_eq!(result.p_value, Some(0.0));
+
+        let mut rng = Mt19937_64::new(5489);
+        let values: Vec<u64> = (0..400).map(|_| rng.next_u64()).collect();
+        assert_eq!(recover_state(&values).unwrap().generator, "MT19937-64");
+    }
+
+    #[test]
+    fn test_state_recovery_detects_xorshift_families() {
+        let cases: Vec<(&str, Box<dyn RngCore>, bool)> = vec![
+            ("xorshift32", Box::new(Xorshift32::new(1)), true),
+            ("xorshift64", Box::new(Xorshift64::new(2)), false),
+            ("xorshift64*", Box::new(Xorshift64Star::new(3)), false),
+            ("xorshift128", Box::new(Xorshift128::new(4)), true),
+            ("xorshift128+", Box::new(Xorshift128Plus::new(5)), false),
+            ("xoroshiro128+", Box::new(Xoroshiro128Plus::new(6)), false),
+            ("xoroshiro128**", Box::new(Xoroshiro128StarStar::new(7)), false),
+            ("xoshiro256+", Box::new(Xoshiro256Plus::new(8)), false),
+            ("xoshiro256**", Box::new(Xoshiro256StarStar::new(9)), false),
+        ];
+        for (name, mut rng, words32) in cases {
+            let values: Vec<u64> = (0..300)
+                .map(|_| if words32 { rng.next_u32() as u64 } else { rng.next_u64() })
+                .collect();
+            let recovery = recover_state(&values).unwrap_or_else(|| panic!("{} doit être reconnu", name));
+            assert_eq!(recovery.generator, name);
+            assert_eq!(recovery.checked, recovery.predicted_correctly);
+            let expected = if words32 { rng.next_u32() as u64 } else { rng.next_u64() };
+            assert_eq!(recovery.next_values[0], expected);
+        }
+    }
+
+    #[test]
+    fn test_state_recovery_rejects_zero_input() {
+        let result = test_state_recovery(&[0u64; 1000]);
+        assert!(result.passed, "{}", result.details);
+    }
+}
@@ -0,0 +1,203 @@
+//! Fonctions statistiques communes aux tests RNG (lois du chi-square et normale).
+
+/// Logarithme de la fonction Gamma (approximation de Lanczos).
+/// `x` : Valeur strictement positive.
+/// Retourne : ln(Γ(x)).
+pub fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+
+    let mut y = x;
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000_000_000_190_015;
+    for coefficient in COEFFICIENTS {
+        y += 1.0;
+        series += coefficient / y;
+    }
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+/// Fonction gamma incomplète régularisée supérieure Q(a, x).
+/// `a` : Paramètre de forme (> 0).
+/// `x` : Borne inférieure d'intégration (>= 0).
+/// Retourne : Q(a, x) = 1 - P(a, x).
+pub fn igamc(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < a + 1.0 {
+        1.0 - igam_series(a, x)
+    } else {
+        igamc_continued_fraction(a, x)
+    }
+}
+
+/// Développement en série de P(a, x), valable pour x < a + 1.
+fn igam_series(a: f64, x: f64) -> f64 {
+    let mut sum = 1.0 / a;
+    let mut term = sum;
+    let mut ap = a;
+    for _ in 0..1000 {
+        ap += 1.0;
+        term *= x / ap;
+        sum += term;
+        if term.abs() < sum.abs() * 1e-15 {
+            break;
+        }
+    }
+    (sum.ln() - x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+/// Fraction continue de Lentz pour Q(a, x), valable pour x >= a + 1.
+fn igamc_continued_fraction(a: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..1000 {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    (-x + a * x.ln() - ln_gamma(a)).exp() * h
+}
+
+/// P-value d'une statistique du chi-square (probabilité de dépasser `chi_square`).
+/// `chi_square` : Valeur observée de la statistique.
+/// `degrees_of_freedom` : Nombre de degrés de liberté.
+pub fn chi_square_p_value(chi_square: f64, degrees_of_freedom: f64) -> f64 {
+    igamc(degrees_of_freedom / 2.0, chi_square / 2.0)
+}
+
+/// Fonction d'erreur complémentaire (approximation de Tchebychev, erreur relative < 1.2e-7).
+pub fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let polynomial = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let result = t * polynomial.exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+/// P-value bilatérale d'un score z sous la loi normale centrée réduite.
+pub fn normal_two_sided_p_value(z: f64) -> f64 {
+    erfc(z.abs() / std::f64::consts::SQRT_2)
+}
+
+/// Probabilité de la loi de Kolmogorov Q(λ) = 2 Σ (-1)^(j-1) exp(-2 j² λ²), probabilité qu'un écart
+/// normalisé dépasse `lambda`.
+pub fn kolmogorov_p_value(lambda: f64) -> f64 {
+    if lambda < 0.2 {
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for j in 1..=100 {
+        let term = (-2.0 * (j * j) as f64 * lambda * lambda).exp();
+        sum += sign * term;
+        if term < 1e-12 {
+            break;
+        }
+        sign = -sign;
+    }
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// Test de Kolmogorov-Smirnov à deux échantillons : les deux échantillons suivent-ils la même loi ?
+/// `a`, `b` : Échantillons non vides (valeurs finies).
+///
+/// Retourne : La statistique D (plus grand écart entre les deux fonctions de répartition empiriques) et sa
+/// p-value asymptotique, avec la correction de Stephens pour les petits échantillons.
+pub fn ks_two_sample(a: &[f64], b: &[f64]) -> (f64, f64) {
+    let sorted = |values: &[f64]| {
+        let mut values = values.to_vec();
+        values.sort_by(f64::total_cmp);
+        values
+    };
+    let (a, b) = (sorted(a), sorted(b));
+    let (n, m) = (a.len() as f64, b.len() as f64);
+    let (mut i, mut j, mut d) = (0, 0, 0.0f64);
+    while i < a.len() && j < b.len() {
+        let value = a[i].min(b[j]);
+        while i < a.len() && a[i] <= value {
+            i += 1;
+        }
+        while j < b.len() && b[j] <= value {
+            j += 1;
+        }
+        d = d.max((i as f64 / n - j as f64 / m).abs());
+    }
+    let effective = (n * m / (n + m)).sqrt();
+    (d, kolmogorov_p_value((effective + 0.12 + 0.11 / effective) * d))
+}
+
+/// Convertit les seeds en flux d'octets (ordre little-endian, comme une copie mémoire sur x86).
+pub fn seeds_to_bytes(seeds: &[u64]) -> Vec<u8> {
+    seeds.iter().flat_map(|seed| seed.to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chi_square_p_value_known_values() {
+        // Valeurs de référence : chi2 = 3.84 pour 1 ddl -> p ≈ 0.05 ; 255 ddl à la médiane -> p ≈ 0.5
+        assert!((chi_square_p_value(3.841_458_8, 1.0) - 0.05).abs() < 1e-6);
+        assert!((chi_square_p_value(254.333, 255.0) - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_kolmogorov_smirnov_two_samples() {
+        // Valeur critique à 5 % de la loi de Kolmogorov
+        assert!((kolmogorov_p_value(1.358_1) - 0.05).abs() < 1e-4);
+        assert_eq!(kolmogorov_p_value(0.1), 1.0);
+
+        let uniform: Vec<f64> = (0..200).map(|i| (i as f64 + 0.5) / 200.0).collect();
+        let shifted: Vec<f64> = (0..150).map(|i| (i as f64 + 0.3) / 150.0).collect();
+        let (d, p) = ks_two_sample(&uniform, &shifted);
+        assert!(d < 0.02 && p > 0.99, "{} {}", d, p);
+
+        let low: Vec<f64> = uniform.iter().map(|value| value * value * value).collect();
+        let (d, p) = ks_two_sample(&uniform, &low);
+        assert!(d > 0.3 && p < 1e-6, "{} {}", d, p);
+        assert_eq!(ks_two_sample(&[0.5], &[0.5]).0, 0.0);
+    }
+
+    #[test]
+    fn test_normal_p_value_known_values() {
+        assert!((normal_two_sided_p_value(1.959_964) - 0.05).abs() < 1e-6);
+        assert!((normal_two_sided_p_value(0.0) - 1.0).abs() < 1e-6);
+    }
+}
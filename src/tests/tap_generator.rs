@@ -0,0 +1,89 @@
+use crate::types::TestResult;
+
+/// Génère un rapport TAP (Test Anything Protocol, version 13).
+/// `test_results` : Résultats dans l'ordre d'affichage, un point de test chacun.
+/// Retourne : Le flux TAP.
+///
+/// # Remarques
+/// * Chaque rejet est suivi d'un bloc YAML (verdict, score, seuils, p-value, détails).
+/// * Un test qui n'a pas pu s'appliquer aux données (`TestResult::not_applicable`) porte la directive
+///   `# SKIP` suivie de la raison.
+pub fn generate_tap_report(test_results: &[TestResult]) -> String {
+    let mut tap = format!("TAP version 13\n1..{}\n", test_results.len());
+    for (index, result) in test_results.iter().enumerate() {
+        // `#` ouvre une directive TAP : il ne doit pas apparaître dans la description
+        let description = result.test_name.replace('#', "\\#");
+        if result.passed {
+            tap.push_str(&format!("ok {} - {}\n", index + 1, description));
+        } else if result.not_applicable {
+            tap.push_str(&format!("ok {} - {} # SKIP {}\n", index + 1, description, single_line(&result.details)));
+        } else {
+            tap.push_str(&format!("not ok {} - {}\n", index + 1, description));
+            tap.push_str("  ---\n");
+            tap.push_str(&format!("  verdict: {}\n", yaml_string(result.verdict().label())));
+            tap.push_str(&format!("  score: {}\n", result.score));
+            if let Some((low, high)) = result.thresholds {
+                tap.push_str(&format!("  thresholds: [{}, {}]\n", low, high));
+            }
+            if let Some(p_value) = result.p_value {
+                tap.push_str(&format!("  p_value: {:e}\n", p_value));
+            }
+            tap.push_str(&format!("  details: {}\n", yaml_string(&result.details)));
+            tap.push_str("  ...\n");
+        }
+    }
+    tap
+}
+
+/// Texte sur une seule ligne (les sauts de ligne terminent un point de test TAP).
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Chaîne YAML entre guillemets ; la syntaxe JSON en est un sous-ensemble valide.
+fn yaml_string(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tap_points_diagnostics_and_skips() {
+        let results = vec![
+            TestResult { test_name: "Test de Poker".to_string(), passed: true, ..Default::default() },
+            TestResult {
+                test_name: "Test de récupération d'état (MT / xorshift)".to_string(),
+                passed: false,
+                score: 1.0,
+                details: "Générateur détecté : MT19937\nprochaines valeurs : [1, 2]".to_string(),
+                thresholds: Some((0.0, 0.0)),
+                p_value: Some(0.0),
+                ..Default::default()
+            },
+            TestResult {
+                test_name: "ENT #1".to_string(),
+                passed: false,
+                details: "Erreur : Pas assez de données\npour l'analyse ENT.".to_string(),
+                not_applicable: true,
+                ..Default::default()
+            },
+        ];
+
+        let tap = generate_tap_report(&results);
+        let expected = "TAP version 13\n\
+            1..3\n\
+            ok 1 - Test de Poker\n\
+            not ok 2 - Test de récupération d'état (MT / xorshift)\n  \
+            ---\n  \
+            verdict: \"ÉCHEC\"\n  \
+            score: 1\n  \
+            thresholds: [0, 0]\n  \
+            p_value: 0e0\n  \
+            details: \"Générateur détecté : MT19937\\nprochaines valeurs : [1, 2]\"\n  \
+            ...\n\
+            ok 3 - ENT \\#1 # SKIP Erreur : Pas assez de données pour l'analyse ENT.\n";
+        assert_eq!(tap, expected);
+    }
+}
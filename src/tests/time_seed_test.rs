@@ -0,0 +1,442 @@
+use crate::types::TestResult;
+use crate::generators::{GlibcRand, JavaRandom, Mt19937, MsvcRand};
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+// Largeur par défaut de la fenêtre de recherche : les dernières 24 heures
+pub const DEFAULT_WINDOW_SECONDS: i64 = 24 * 3600;
+// Marge après l'instant présent (horloges décalées)
+const DEFAULT_FUTURE_MARGIN_SECONDS: i64 = 3600;
+// Nombre maximal d'instants candidats dans une fenêtre
+const MAX_CANDIDATES: i64 = 20_000_000;
+// Nombre de bits de sortie qui doivent coïncider pour retenir une graine
+const MATCH_BITS: u32 = 96;
+// Nombre de valeurs futures prédites, fournies comme preuve
+const PREDICTED_VALUES: usize = 5;
+
+/// Générateurs couramment initialisés avec l'heure courante.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeSeededGenerator {
+    /// `srand((unsigned) time(NULL)); rand()` avec glibc (31 bits).
+    GlibcRand,
+    /// `srand((unsigned) time(NULL)); rand()` avec la bibliothèque C de Microsoft (15 bits).
+    MsvcRand,
+    /// `new java.util.Random(t).nextInt()`.
+    JavaRandomInt,
+    /// `new java.util.Random(t).nextLong()`.
+    JavaRandomLong,
+    /// `std::mt19937(t)` / `init_genrand(t)`, sorties de 32 bits.
+    Mt19937,
+    /// `random.seed(t); random.getrandbits(32)` en Python.
+    PythonRandom,
+    /// `StdRng::seed_from_u64(t).next_u64()` avec rand 0.8 (ChaCha12).
+    RustStdRng,
+}
+
+impl TimeSeededGenerator {
+    /// Tous les générateurs pris en charge.
+    pub const ALL: [TimeSeededGenerator; 7] = [
+        TimeSeededGenerator::GlibcRand,
+        TimeSeededGenerator::MsvcRand,
+        TimeSeededGenerator::JavaRandomInt,
+        TimeSeededGenerator::JavaRandomLong,
+        TimeSeededGenerator::Mt19937,
+        TimeSeededGenerator::PythonRandom,
+        TimeSeededGenerator::RustStdRng,
+    ];
+
+    /// Nom lisible du générateur.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimeSeededGenerator::GlibcRand => "glibc rand()",
+            TimeSeededGenerator::MsvcRand => "MSVC rand()",
+            TimeSeededGenerator::JavaRandomInt => "java.util.Random (nextInt)",
+            TimeSeededGenerator::JavaRandomLong => "java.util.Random (nextLong)",
+            TimeSeededGenerator::Mt19937 => "MT19937",
+            TimeSeededGenerator::PythonRandom => "Python random",
+            TimeSeededGenerator::RustStdRng => "Rust StdRng::seed_from_u64",
+        }
+    }
+
+    /// Nombre de bits de chaque sortie.
+    fn output_bits(&self) -> u32 {
+        match self {
+            TimeSeededGenerator::GlibcRand => 31,
+            TimeSeededGenerator::MsvcRand => 15,
+            TimeSeededGenerator::JavaRandomLong | TimeSeededGenerator::RustStdRng => 64,
+            _ => 32,
+        }
+    }
+
+    /// Graine effectivement transmise au générateur pour l'instant `timestamp`
+    /// (tronquée à 32 bits pour les API C, valeur absolue pour Python).
+    pub fn seed_for(&self, timestamp: i64) -> u64 {
+        match self {
+            TimeSeededGenerator::GlibcRand | TimeSeededGenerator::MsvcRand | TimeSeededGenerator::Mt19937 => {
+                timestamp as u32 as u64
+            }
+            TimeSeededGenerator::PythonRandom => timestamp.unsigned_abs(),
+            TimeSeededGenerator::JavaRandomInt | TimeSeededGenerator::JavaRandomLong | TimeSeededGenerator::RustStdRng => {
+                timestamp as u64
+            }
+        }
+    }
+
+    /// Première sortie du générateur initialisé à l'instant `timestamp` (filtre rapide de la recherche).
+    fn first_output(&self, timestamp: i64) -> u64 {
+        match self {
+            TimeSeededGenerator::Mt19937 => Mt19937::first_output(self.seed_for(timestamp) as u32) as u64,
+            _ => self.outputs(timestamp, 1)[0],
+        }
+    }
+
+    /// Premières sorties du générateur initialisé à l'instant `timestamp`.
+    pub fn outputs(&self, timestamp: i64, count: usize) -> Vec<u64> {
+        let seed = self.seed_for(timestamp);
+        match self {
+            TimeSeededGenerator::GlibcRand => {
+                let mut rng = GlibcRand::new(seed as u32);
+                (0..count).map(|_| rng.next_rand() as u64).collect()
+            }
+            TimeSeededGenerator::MsvcRand => {
+                let mut rng = MsvcRand::new(seed as u32);
+                (0..count).map(|_| rng.next_rand() as u64).collect()
+            }
+            TimeSeededGenerator::JavaRandomInt => {
+                let mut rng = JavaRandom::new(seed as i64);
+                (0..count).map(|_| rng.next_int() as u32 as u64).collect()
+            }
+            TimeSeededGenerator::JavaRandomLong => {
+                let mut rng = JavaRandom::new(seed as i64);
+                (0..count).map(|_| rng.next_long() as u64).collect()
+            }
+            TimeSeededGenerator::Mt19937 => {
+                let mut rng = Mt19937::new(seed as u32);
+                (0..count).map(|_| rng.next_u32() as u64).collect()
+            }
+            TimeSeededGenerator::PythonRandom => {
+                // random.seed(n) découpe |n| en mots de 32 bits, poids faible d'abord
+                let key: Vec<u32> = if seed >> 32 == 0 {
+                    vec![seed as u32]
+                } else {
+                    vec![seed as u32, (seed >> 32) as u32]
+                };
+                let mut rng = Mt19937::from_key(&key);
+                (0..count).map(|_| rng.next_u32() as u64).collect()
+            }
+            TimeSeededGenerator::RustStdRng => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                (0..count).map(|_| rng.next_u64()).collect()
+            }
+        }
+    }
+}
+
+impl FromStr for TimeSeededGenerator {
+    type Err = String;
+
+    /// Lit l'identifiant d'un générateur (`glibc_rand`, `msvc_rand`, `java_random_int`, ...).
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_string()))
+            .map_err(|_| format!("Générateur inconnu : {}", value))
+    }
+}
+
+/// Unité des horodatages utilisés comme graines.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeResolution {
+    /// Secondes depuis l'époque Unix (`time(NULL)`).
+    Seconds,
+    /// Millisecondes depuis l'époque Unix (`System.currentTimeMillis()`).
+    Milliseconds,
+}
+
+/// Paramètres de la recherche : fenêtre de temps et générateurs candidats.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TimeSeedSearch {
+    /// Début de la fenêtre (inclus), dans l'unité `resolution`.
+    pub start: i64,
+    /// Fin de la fenêtre (incluse), dans l'unité `resolution`.
+    pub end: i64,
+    /// Unité des horodatages.
+    pub resolution: TimeResolution,
+    /// Générateurs essayés, dans l'ordre.
+    pub generators: Vec<TimeSeededGenerator>,
+}
+
+impl TimeSeedSearch {
+    /// Nombre de graines essayées au pire : instants de la fenêtre multipliés par le nombre de générateurs.
+    pub fn candidates(&self) -> i64 {
+        (self.end.saturating_sub(self.start).saturating_add(1)).max(0).saturating_mul(self.generators.len() as i64)
+    }
+
+    /// Fenêtre de `window` secondes se terminant à l'instant `reference` (plus une marge d'une heure),
+    /// à la seconde près, avec tous les générateurs.
+    pub fn ending_at(reference: i64, window: i64) -> Self {
+        TimeSeedSearch {
+            start: reference - window,
+            end: reference + DEFAULT_FUTURE_MARGIN_SECONDS,
+            resolution: TimeResolution::Seconds,
+            generators: TimeSeededGenerator::ALL.to_vec(),
+        }
+    }
+}
+
+impl Default for TimeSeedSearch {
+    /// Les dernières 24 heures à la seconde près, avec tous les générateurs.
+    fn default() -> Self {
+        TimeSeedSearch::ending_at(Utc::now().timestamp(), DEFAULT_WINDOW_SECONDS)
+    }
+}
+
+/// Graine retrouvée et concordance avec les données.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TimeSeedMatch {
+    /// Générateur reconnu.
+    pub generator: String,
+    /// Graine transmise au générateur.
+    pub seed: u64,
+    /// Horodatage correspondant, dans l'unité `resolution`.
+    pub timestamp: i64,
+    /// Unité de l'horodatage.
+    pub resolution: TimeResolution,
+    /// Date et heure (UTC, RFC 3339) de l'horodatage.
+    pub datetime: String,
+    /// Nombre de sorties initiales reproduites par la graine.
+    pub matched_outputs: usize,
+    /// Nombre de sorties comparées.
+    pub checked: usize,
+    /// Sorties suivantes du générateur après la fin des données.
+    pub next_values: Vec<u64>,
+}
+
+/// Recherche de graine temporelle sur la fenêtre par défaut (dernières 24 heures, tous les générateurs).
+/// `seeds` : Tableau de seeds, considérés comme les premières sorties du générateur.
+/// Retourne : Une structure TestResult ; le test échoue si une graine reproduit les données.
+pub fn test_time_seed(seeds: &[u64]) -> TestResult {
+    test_time_seed_with_search(seeds, &TimeSeedSearch::default())
+}
+
+/// Recherche de graine temporelle avec une fenêtre et des générateurs choisis.
+/// `seeds` : Tableau de seeds, considérés comme les premières sorties du générateur.
+/// `search` : Fenêtre de temps, unité et générateurs candidats.
+/// Retourne : Une structure TestResult avec la graine et l'horodatage dans le champ `data` en cas de succès.
+///
+/// # Remarques
+/// * Les sorties de 32 bits ou moins sont aussi cherchées dans les valeurs de 64 bits découpées en deux (poids faible d'abord).
+/// * Une graine n'est retenue que si elle reproduit au moins 96 bits de sortie, pour écarter les coïncidences.
+pub fn test_time_seed_with_search(seeds: &[u64], search: &TimeSeedSearch) -> TestResult {
+    if let Err(message) = validate_search(search) {
+        return TestResult {
+            test_name: "Test de graine temporelle".to_string(),
+            passed: false,
+            score: 0.0,
+            details: format!("Erreur : {}", message),
+            thresholds: None,
+            not_applicable: true,
+            ..Default::default()
+        };
+    }
+    let evaluated: Vec<&TimeSeededGenerator> = search
+        .generators
+        .iter()
+        .filter(|generator| comparable_outputs(seeds, generator).len() >= outputs_needed(generator))
+        .collect();
+    if evaluated.is_empty() {
+        return TestResult {
+            test_name: "Test de graine temporelle".to_string(),
+            passed: false,
+            score: 0.0,
+            details: "Erreur : Pas assez de seeds pour la recherche de graine temporelle.".to_string(),
+            thresholds: None,
+            not_applicable: true,
+            ..Default::default()
+        };
+    }
+
+    let window = format!(
+        "{} → {}",
+        format_timestamp(search.start, search.resolution),
+        format_timestamp(search.end, search.resolution)
+    );
+    match search_time_seed(seeds, search) {
+        Some(found) => TestResult {
+            test_name: "Test de graine temporelle".to_string(),
+            passed: false,
+            score: found.matched_outputs as f64 / found.checked.max(1) as f64,
+            details: format!(
+                "Graine retrouvée : {} initialisé avec {} (horodatage {}, {}) ; {} / {} sorties reproduites, prochaines valeurs : {:?}",
+                found.generator,
+                found.seed,
+                found.timestamp,
+                found.datetime,
+                found.matched_outputs,
+                found.checked,
+                found.next_values
+            ),
+            thresholds: Some((0.0, 0.0)),
+            p_value: Some(0.0),
+            data: serde_json::to_value(&found).ok(),
+            ..Default::default()
+        },
+        None => TestResult {
+            test_name: "Test de graine temporelle".to_string(),
+            passed: true,
+            score: 0.0,
+            details: format!(
+                "Aucune graine temporelle trouvée dans la fenêtre {} pour : {}",
+                window,
+                evaluated.iter().map(|generator| generator.name()).collect::<Vec<_>>().join(", ")
+            ),
+            thresholds: Some((0.0, 0.0)),
+            ..Default::default()
+        },
+    }
+}
+
+/// Parcourt la fenêtre de temps et retourne la première graine qui reproduit le début des données.
+/// `values` : Valeurs à reproduire.
+/// `search` : Fenêtre de temps, unité et générateurs candidats.
+pub fn search_time_seed(values: &[u64], search: &TimeSeedSearch) -> Option<TimeSeedMatch> {
+    if validate_search(search).is_err() {
+        return None;
+    }
+    search.generators.iter().find_map(|generator| {
+        let expected = comparable_outputs(values, generator);
+        let needed = outputs_needed(generator);
+        if expected.len() < needed {
+            return None;
+        }
+        (search.start..=search.end).find_map(|timestamp| {
+            // Filtre rapide sur la première sortie, puis comparaison complète
+            if generator.first_output(timestamp) != expected[0] {
+                return None;
+            }
+            let outputs = generator.outputs(timestamp, expected.len() + PREDICTED_VALUES);
+            let matched_outputs = outputs.iter().zip(&expected).take_while(|(a, b)| a == b).count();
+            (matched_outputs >= needed).then(|| TimeSeedMatch {
+                generator: generator.name().to_string(),
+                seed: generator.seed_for(timestamp),
+                timestamp,
+                resolution: search.resolution,
+                datetime: format_timestamp(timestamp, search.resolution),
+                matched_outputs,
+                checked: expected.len(),
+                next_values: outputs[expected.len()..].to_vec(),
+            })
+        })
+    })
+}
+
+/// Vérifie que la fenêtre est ordonnée et de taille raisonnable.
+fn validate_search(search: &TimeSeedSearch) -> Result<(), String> {
+    if search.end < search.start {
+        return Err("La fin de la fenêtre précède son début.".to_string());
+    }
+    if search.end.saturating_sub(search.start) >= MAX_CANDIDATES {
+        return Err(format!("La fenêtre dépasse {} instants candidats.", MAX_CANDIDATES));
+    }
+    if search.generators.is_empty() {
+        return Err("Aucun générateur candidat.".to_string());
+    }
+    Ok(())
+}
+
+/// Valeurs des données comparables aux sorties du générateur : telles quelles, ou découpées
+/// en deux mots de 32 bits si le générateur sort au plus 32 bits et que les données sont plus larges.
+fn comparable_outputs(values: &[u64], generator: &TimeSeededGenerator) -> Vec<u64> {
+    if generator.output_bits() <= 32 && values.iter().any(|&value| value > u32::MAX as u64) {
+        values.iter().flat_map(|&value| [value & 0xFFFF_FFFF, value >> 32]).collect()
+    } else {
+        values.to_vec()
+    }
+}
+
+/// Nombre de sorties qui doivent coïncider pour atteindre `MATCH_BITS` bits.
+fn outputs_needed(generator: &TimeSeededGenerator) -> usize {
+    MATCH_BITS.div_ceil(generator.output_bits()) as usize
+}
+
+/// Date et heure UTC (RFC 3339) d'un horodatage.
+fn format_timestamp(timestamp: i64, resolution: TimeResolution) -> String {
+    let datetime = match resolution {
+        TimeResolution::Seconds => DateTime::<Utc>::from_timestamp(timestamp, 0),
+        TimeResolution::Milliseconds => DateTime::<Utc>::from_timestamp_millis(timestamp),
+    };
+    datetime.map(|datetime| datetime.to_rfc3339()).unwrap_or_else(|| timestamp.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn generate_random_seeds() -> Vec<u64> {
+        let mut rng = rand::thread_rng();
+        (0..1000).map(|_| rng.gen::<u64>()).collect()
+    }
+
+    fn search_around(timestamp: i64, resolution: TimeResolution) -> TimeSeedSearch {
+        TimeSeedSearch {
+            start: timestamp - 2000,
+            end: timestamp + 1000,
+            resolution,
+            generators: TimeSeededGenerator::ALL.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_time_seed_with_random_seeds() {
+        let seeds = generate_random_seeds();
+        let result = test_time_seed_with_search(&seeds, &search_around(1_700_000_000, TimeResolution::Seconds));
+        println!("{}", result.details);
+        assert!(result.passed, "Échec du test de graine temporelle : {}", result.details);
+    }
+
+    #[test]
+    fn test_time_seed_recovers_each_generator() {
+        let timestamp = 1_700_000_000;
+        for generator in TimeSeededGenerator::ALL {
+            let values = generator.outputs(timestamp, 20);
+            let found = search_time_seed(&values, &search_around(timestamp, TimeResolution::Seconds))
+                .unwrap_or_else(|| panic!("{} doit être retrouvé", generator.name()));
+            assert_eq!(found.timestamp, timestamp);
+            assert_eq!(found.matched_outputs, 20);
+            assert_eq!(found.next_values, generator.outputs(timestamp, 25)[20..].to_vec());
+        }
+    }
+
+    #[test]
+    fn test_time_seed_recovers_packed_java_milliseconds() {
+        // nextInt() regroupés par paires dans des mots de 64 bits
+        let timestamp = 1_700_000_000_123;
+        let ints = TimeSeededGenerator::JavaRandomInt.outputs(timestamp, 8);
+        let values: Vec<u64> = ints.chunks(2).map(|pair| pair[0] | (pair[1] << 32)).collect();
+        let search = TimeSeedSearch {
+            generators: vec![TimeSeededGenerator::JavaRandomInt],
+            ..search_around(timestamp, TimeResolution::Milliseconds)
+        };
+        let result = test_time_seed_with_search(&values, &search);
+        assert!(!result.passed);
+        let found: TimeSeedMatch = serde_json::from_value(result.data.unwrap()).unwrap();
+        assert_eq!(found.seed, timestamp as u64);
+        assert_eq!(found.datetime, "2023-11-14T22:13:20.123+00:00");
+    }
+
+    #[test]
+    fn test_time_seed_rejects_invalid_window() {
+        let search = TimeSeedSearch { start: 10, end: 0, ..TimeSeedSearch::default() };
+        assert!(!test_time_seed_with_search(&generate_random_seeds(), &search).passed);
+        assert_eq!("python_random".parse::<TimeSeededGenerator>(), Ok(TimeSeededGenerator::PythonRandom));
+        assert!("inconnu".parse::<TimeSeededGenerator>().is_err());
+        let search = search_around(0, TimeResolution::Seconds);
+        assert_eq!(search.candidates(), 3001 * 7);
+        assert_eq!(TimeSeedSearch { start: 10, end: 0, ..search }.candidates(), 0);
+    }
+}
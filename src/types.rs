@@ -1,10 +1,84 @@
 use serde::{Serialize, Deserialize}; // Assurez-vous que `Deserialize` est inclus
+use crate::runner::RunConfig;
 
-#[derive(Serialize, Deserialize, Debug)] // Ajout de `Debug` pour le débogage
+// P-value en deçà de laquelle (ou au-delà de 1 - seuil) un rejet est franc plutôt que limite
+const WEAK_P_VALUE: f64 = 1e-4;
+
+/// Verdict d'un résultat, du meilleur au pire.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// Résultat dans ses seuils.
+    Pass,
+    /// Rejet limite : p-value entre 1e-4 et le seuil du test, fréquente pour un générateur sain sur
+    /// une longue batterie.
+    Weak,
+    /// Rejet franc : p-value extrême, défaut structurel (état reconstruit, graine retrouvée) ou test
+    /// sans p-value hors de ses seuils.
+    Fail,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)] // Ajout de `Debug` pour le débogage
 pub struct TestResult {
     pub test_name: String,
     pub passed: bool,
     pub score: f64,
     pub details: String,
     pub thresholds: Option<(f64, f64)>, // Ajout du champ thresholds
+    /// P-value associée au test, lorsque la statistique en fournit une.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p_value: Option<f64>,
+    /// Nom du groupe auquel appartient le résultat (ex. "ENT"), pour l'affichage regroupé.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Données structurées propres au test (métriques, séries, preuves).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    /// Paramètres de l'exécution ayant produit le résultat (générateur, graine, version), pour la rejouer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<RunConfig>,
+    /// Vrai si le test n'a pas pu s'appliquer aux données (données insuffisantes, paramètres de recherche
+    /// invalides) : le résultat n'est pas un rejet, il est signalé comme ignoré (JUnit `skipped`, TAP
+    /// `SKIP`) et exclu des verdicts et des comparaisons.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub not_applicable: bool,
+}
+
+impl Verdict {
+    /// Libellé affiché (`OK`, `FAIBLE`, `ÉCHEC`).
+    pub fn label(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "OK",
+            Verdict::Weak => "FAIBLE",
+            Verdict::Fail => "ÉCHEC",
+        }
+    }
+}
+
+impl TestResult {
+    /// Verdict du résultat (voir `Verdict`).
+    pub fn verdict(&self) -> Verdict {
+        match self.p_value {
+            _ if self.passed => Verdict::Pass,
+            Some(p) if (WEAK_P_VALUE..=1.0 - WEAK_P_VALUE).contains(&p) => Verdict::Weak,
+            _ => Verdict::Fail,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verdict_separates_borderline_rejections() {
+        let result = |passed, p_value| TestResult { passed, p_value, ..Default::default() };
+        assert_eq!(result(true, Some(1e-6)).verdict(), Verdict::Pass);
+        assert_eq!(result(false, Some(0.004)).verdict(), Verdict::Weak);
+        assert_eq!(result(false, Some(0.995)).verdict(), Verdict::Weak);
+        assert_eq!(result(false, Some(1e-7)).verdict(), Verdict::Fail);
+        assert_eq!(result(false, Some(0.0)).verdict(), Verdict::Fail);
+        assert_eq!(result(false, None).verdict(), Verdict::Fail);
+        assert!(Verdict::Pass < Verdict::Weak && Verdict::Weak < Verdict::Fail);
+    }
 }
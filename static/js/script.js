@@ -46,6 +46,15 @@ document.addEventListener('DOMContentLoaded', function () {
         }
     };
 
+    /**
+     * Extrait les résultats de toutes les suites d'un rapport (schéma : /schema/report.json).
+     * @param {Object} report - Rapport renvoyé par le serveur.
+     * @returns {Array} Résultats, préfixés par le groupe de leur suite.
+     */
+    const reportResults = (report) => (report && report.sequences || []).flatMap((sequence) =>
+        sequence.results.map((result) =>
+            sequence.group ? { ...result, test_name: `[${sequence.group}] ${result.test_name}` } : result));
+
     /**
      * Met à jour un conteneur avec les résultats des tests RNG.
      * @param {Array} results - Résultats à afficher.
@@ -115,8 +124,8 @@ document.addEventListener('DOMContentLoaded', function () {
                 });
 
                 if (!response.ok) throw new Error(`Erreur HTTP : ${response.status}`);
-                const results = await response.json();
-                updateResults(results);
+                const report = await response.json();
+                updateResults(reportResults(report));
                 showAlert('Tests exécutés avec succès.', 'success');
             } catch (error) {
                 console.error('Erreur lors de l\'exécution des tests :', error);
@@ -150,8 +159,8 @@ document.addEventListener('DOMContentLoaded', function () {
                 });
 
                 if (!response.ok) throw new Error(`Erreur HTTP : ${response.status}`);
-                const results = await response.json();
-                updateResults(results, 'file-results-container');
+                const report = await response.json();
+                updateResults(reportResults(report), 'file-results-container');
                 showAlert('Analyse du fichier réussie.', 'success');
             } catch (error) {
                 console.error('Erreur lors de l\'analyse du fichier :', error);
@@ -195,8 +204,8 @@ document.addEventListener('DOMContentLoaded', function () {
             try {
                 const response = await fetch('/refresh_results');
                 if (!response.ok) throw new Error(`Erreur HTTP : ${response.status}`);
-                const results = await response.json();
-                updateResults(results);
+                const report = await response.json();
+                updateResults(reportResults(report));
                 showAlert('Résultats rafraîchis avec succès.', 'success');
             } catch (error) {
                 console.error('Erreur lors du rafraîchissement :', error);